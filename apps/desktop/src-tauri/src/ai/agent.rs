@@ -1,7 +1,33 @@
+use crate::ai::chat::AgentSession;
 use crate::error::Result;
+use crate::types::AgentChatMessage;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
+const CHAT_SYSTEM_PROMPT: &str = "You are RowFlow's assistant for exploring PostgreSQL databases. \
+Answer briefly and conversationally. When the user asks about their data, offer to search the \
+database for them.";
+
+/// Openings that refine the previous data question rather than ask a new one
+const FOLLOW_UP_PREFIXES: [&str; 16] = [
+    "and",
+    "also",
+    "now",
+    "then",
+    "instead",
+    "only",
+    "just",
+    "more",
+    "filter",
+    "sort",
+    "order",
+    "group",
+    "limit",
+    "same",
+    "what about",
+    "how about",
+];
+
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +35,9 @@ pub enum AgentIntent {
     Greeting,
     SmallTalk,
     DatabaseQuery,
+    SchemaQuestion,
+    DataModification,
+    Visualization,
     Unknown,
 }
 
@@ -22,65 +51,29 @@ pub struct AgentState {
     pub response: Option<String>,
     pub sql: Option<String>,
     pub should_search: bool,
+    /// `sql` may write, so it must never run without the user's approval
+    #[serde(default)]
+    pub requires_confirmation: bool,
 }
 
 pub struct Agent {
-    endpoint: String,
+    client: crate::ai::ollama::OllamaClient,
     chat_model: String,
 }
 
 impl Agent {
-    pub fn new(endpoint: String, chat_model: String) -> Self {
-        Self { endpoint, chat_model }
+    pub fn new(client: crate::ai::ollama::OllamaClient, chat_model: String) -> Self {
+        Self { client, chat_model }
     }
 
     fn create_client(&self) -> crate::ai::ollama::OllamaClient {
-        crate::ai::ollama::OllamaClient::new(Some(self.endpoint.clone()))
+        self.client.clone()
     }
 
     /// Classify user intent using LLM with heuristic fallback
     pub async fn classify_intent(&self, message: &str) -> Result<AgentIntent> {
-        let msg_lower = message.trim().to_lowercase();
-
-        // Quick heuristic check for obvious greetings (before LLM call for speed)
-        let obvious_greetings =
-            ["hi", "hello", "hey", "good morning", "good afternoon", "good evening", "greetings"];
-        if obvious_greetings
-            .iter()
-            .any(|g| msg_lower == *g || msg_lower.starts_with(&format!("{} ", g)))
-        {
-            return Ok(AgentIntent::Greeting);
-        }
-
-        // Check for database query keywords - if found, skip LLM and return database_query
-        let db_keywords = [
-            "find",
-            "show",
-            "list",
-            "get",
-            "search",
-            "look",
-            "check",
-            "have",
-            "do we have",
-            "how many",
-            "what",
-            "where",
-            "who",
-            "which",
-            "select",
-            "query",
-            "data",
-            "bake",
-            "mouse",
-            "cake",
-            "product",
-            "user",
-            "order",
-            "table",
-        ];
-        if db_keywords.iter().any(|k| msg_lower.contains(k)) {
-            return Ok(AgentIntent::DatabaseQuery);
+        if let Some(intent) = classify_by_keywords(message) {
+            return Ok(intent);
         }
 
         // For ambiguous cases, use LLM classification
@@ -89,6 +82,9 @@ impl Agent {
 
 - greeting: ONLY simple greetings like "hi", "hello", "hey" (nothing else)
 - small_talk: ONLY casual chat like "how are you", "thanks", "ok" (no data questions)
+- schema_question: questions about the database structure, such as tables, columns, types, keys or indexes
+- data_modification: requests to insert, update, or delete data or change tables
+- visualization: requests to chart, plot, or graph data
 - database_query: ANY question about finding/showing/listing/checking data, products, users, or anything in a database
 
 Message: "{}"
@@ -96,7 +92,7 @@ Message: "{}"
 If it asks about finding, showing, listing, checking, or searching for ANYTHING, it's database_query.
 If unsure, choose database_query.
 
-Respond with ONLY one word: greeting, small_talk, or database_query"#,
+Respond with ONLY one word: greeting, small_talk, schema_question, data_modification, visualization, or database_query"#,
             message.trim()
         );
 
@@ -117,6 +113,12 @@ Respond with ONLY one word: greeting, small_talk, or database_query"#,
             AgentIntent::Greeting
         } else if intent_str.contains("small_talk") || intent_str.contains("smalltalk") {
             AgentIntent::SmallTalk
+        } else if intent_str.contains("schema") {
+            AgentIntent::SchemaQuestion
+        } else if intent_str.contains("modification") {
+            AgentIntent::DataModification
+        } else if intent_str.contains("visualization") {
+            AgentIntent::Visualization
         } else {
             // Default to database query for anything else
             AgentIntent::DatabaseQuery
@@ -147,6 +149,25 @@ Respond with ONLY one word: greeting, small_talk, or database_query"#,
                 state.response =
                     Some("Let me search your database for relevant information...".to_string());
             }
+            AgentIntent::SchemaQuestion => {
+                // Schema answers come from the catalog rather than embedded rows
+                state.should_search = false;
+                state.response = Some(
+                    "That's a question about your database structure. You can browse schemas, tables, columns, keys and indexes in the schema panel.".to_string()
+                );
+            }
+            AgentIntent::DataModification => {
+                // The assistant never changes data itself
+                state.should_search = false;
+                state.response = Some(
+                    "I can't change data for you, but I can help you find the rows you want to change. Edit them in the table view or run the change in the query editor.".to_string()
+                );
+            }
+            AgentIntent::Visualization => {
+                // Charts need the underlying rows, so search for them like a data question
+                state.should_search = true;
+                state.response = Some("Let me find the data you want to visualize...".to_string());
+            }
             AgentIntent::Unknown => {
                 // Default to treating as database query if uncertain
                 state.should_search = true;
@@ -158,6 +179,12 @@ Respond with ONLY one word: greeting, small_talk, or database_query"#,
         Ok(())
     }
 
+    /// Attach generated SQL to the state, flagging anything that isn't read-only for confirmation
+    pub fn attach_sql(state: &mut AgentState, sql: String) {
+        state.requires_confirmation = !crate::commands::script::is_read_only_sql(&sql);
+        state.sql = Some(sql);
+    }
+
     /// Process a user message through the agent workflow (LangGraph-style)
     pub async fn process_message(&self, message: String) -> Result<AgentState> {
         let mut state = AgentState {
@@ -167,6 +194,7 @@ Respond with ONLY one word: greeting, small_talk, or database_query"#,
             response: None,
             sql: None,
             should_search: false,
+            requires_confirmation: false,
         };
 
         // Step 1: Classify intent
@@ -177,4 +205,304 @@ Respond with ONLY one word: greeting, small_talk, or database_query"#,
 
         Ok(state)
     }
+
+    /// Process a message that may follow up on earlier turns of `session`
+    ///
+    /// Follow-ups to a data question are treated as data questions too, and every data question
+    /// carries the previous question, SQL and result summary in `context`.
+    pub async fn process_session_message(
+        &self,
+        session: &AgentSession,
+        message: String,
+    ) -> Result<AgentState> {
+        let mut state = if session.last_sql.is_some() && is_follow_up(&message) {
+            let mut state = AgentState {
+                message,
+                intent: AgentIntent::DatabaseQuery,
+                context: None,
+                response: None,
+                sql: None,
+                should_search: false,
+                requires_confirmation: false,
+            };
+            self.generate_response(&mut state).await?;
+            state
+        } else {
+            self.process_message(message).await?
+        };
+
+        if state.should_search {
+            state.context = session.follow_up_context();
+        }
+
+        Ok(state)
+    }
+
+    /// Process a message as the next turn of a conversation
+    ///
+    /// Data questions get the same response as `process_session_message`; anything else is
+    /// answered by the chat model with the session's history, streaming the reply to
+    /// `on_content`.
+    pub async fn chat_message(
+        &self,
+        session: &AgentSession,
+        message: String,
+        on_content: &(dyn Fn(&str) + Sync),
+    ) -> Result<AgentState> {
+        let mut state = self.process_session_message(session, message).await?;
+        if !matches!(state.intent, AgentIntent::Greeting | AgentIntent::SmallTalk) {
+            return Ok(state);
+        }
+
+        let mut messages = Vec::with_capacity(session.messages.len() + 2);
+        messages.push(AgentChatMessage {
+            role: "system".to_string(),
+            content: CHAT_SYSTEM_PROMPT.to_string(),
+        });
+        messages.extend_from_slice(&session.messages);
+        messages
+            .push(AgentChatMessage { role: "user".to_string(), content: state.message.clone() });
+
+        let reply = self.client.chat_stream(&self.chat_model, &messages, on_content).await?;
+        state.response = Some(reply.content);
+        Ok(state)
+    }
+}
+
+/// Whether `message` (already lowercased) contains any of the phrases as whole words
+fn mentions_any(message: &str, phrases: &[&str]) -> bool {
+    let words: String =
+        message.chars().map(|ch| if ch.is_alphanumeric() { ch } else { ' ' }).collect();
+    let padded = format!(" {} ", words.split_whitespace().collect::<Vec<_>>().join(" "));
+    phrases.iter().any(|phrase| padded.contains(&format!(" {} ", phrase)))
+}
+
+/// Classify the message from its wording alone, or `None` when it needs the model
+fn classify_by_keywords(message: &str) -> Option<AgentIntent> {
+    let msg_lower = message.trim().to_lowercase();
+
+    // Quick heuristic check for obvious greetings (before LLM call for speed)
+    let obvious_greetings =
+        ["hi", "hello", "hey", "good morning", "good afternoon", "good evening", "greetings"];
+    if obvious_greetings
+        .iter()
+        .any(|g| msg_lower == *g || msg_lower.starts_with(&format!("{} ", g)))
+    {
+        return Some(AgentIntent::Greeting);
+    }
+
+    // Charts and the schema are often asked about with a lookup opening ("show me a chart of
+    // ..."), so only other lookups are settled here; a verb later in the message, as in "show
+    // orders I need to remove", then can't turn the question into a change
+    let lookup_openings = [
+        "show",
+        "list",
+        "find",
+        "get",
+        "search",
+        "look up",
+        "how many",
+        "count",
+        "do we have",
+        "which",
+        "who",
+        "select",
+    ];
+    let visualization_phrases = [
+        "a chart",
+        "chart of",
+        "chart the",
+        "bar chart",
+        "line chart",
+        "pie chart",
+        "a graph",
+        "graph of",
+        "graph the",
+        "a plot",
+        "plot of",
+        "plot the",
+        "a histogram",
+        "histogram of",
+        "visualize the",
+        "visualise the",
+        "a visualization",
+        "a dashboard",
+    ];
+    let schema_keywords = [
+        "schema",
+        "schemas",
+        "column",
+        "columns",
+        "structure",
+        "describe",
+        "data type",
+        "data types",
+        "foreign key",
+        "foreign keys",
+        "primary key",
+        "index",
+        "indexes",
+        "constraint",
+        "constraints",
+        "what tables",
+        "which tables",
+        "list tables",
+    ];
+
+    if starts_with_any(&msg_lower, &lookup_openings)
+        && !mentions_any(&msg_lower, &visualization_phrases)
+        && !mentions_any(&msg_lower, &schema_keywords)
+    {
+        return Some(AgentIntent::DatabaseQuery);
+    }
+
+    // A change opens with its verb ("delete order 5") or names what it acts on ("please drop
+    // the table"), so a bare verb elsewhere in the message isn't enough
+    let modification_verbs = [
+        "insert", "update", "delete", "remove", "add", "create", "change", "set", "rename", "drop",
+        "truncate",
+    ];
+    let modification_objects = [
+        "the", "a", "an", "all", "this", "these", "those", "every", "row", "rows", "record",
+        "records", "table", "column", "into", "from", "new",
+    ];
+    if starts_with_any(&msg_lower, &modification_verbs)
+        || mentions_verb_with_object(&msg_lower, &modification_verbs, &modification_objects)
+    {
+        return Some(AgentIntent::DataModification);
+    }
+
+    if mentions_any(&msg_lower, &visualization_phrases) {
+        return Some(AgentIntent::Visualization);
+    }
+
+    if mentions_any(&msg_lower, &schema_keywords) {
+        return Some(AgentIntent::SchemaQuestion);
+    }
+
+    // Check for database query keywords - if found, skip LLM and return database_query
+    let db_keywords = [
+        "find",
+        "show",
+        "list",
+        "get",
+        "search",
+        "look",
+        "check",
+        "have",
+        "do we have",
+        "how many",
+        "what",
+        "where",
+        "who",
+        "which",
+        "select",
+        "query",
+        "data",
+        "bake",
+        "mouse",
+        "cake",
+        "product",
+        "user",
+        "order",
+        "table",
+    ];
+    if db_keywords.iter().any(|k| msg_lower.contains(k)) {
+        return Some(AgentIntent::DatabaseQuery);
+    }
+
+    None
+}
+
+/// Whether the message opens with one of the phrases, as whole words
+fn starts_with_any(message: &str, phrases: &[&str]) -> bool {
+    let words: String =
+        message.chars().map(|ch| if ch.is_alphanumeric() { ch } else { ' ' }).collect();
+    let padded = format!("{} ", words.split_whitespace().collect::<Vec<_>>().join(" "));
+    phrases.iter().any(|phrase| padded.starts_with(&format!("{} ", phrase)))
+}
+
+/// Whether any verb is directly followed by one of the objects, e.g. "delete the"
+fn mentions_verb_with_object(message: &str, verbs: &[&str], objects: &[&str]) -> bool {
+    verbs.iter().any(|verb| {
+        objects.iter().any(|object| mentions_any(message, &[&format!("{} {}", verb, object)]))
+    })
+}
+
+fn is_follow_up(message: &str) -> bool {
+    let message = message.trim().to_lowercase();
+    FOLLOW_UP_PREFIXES.iter().any(|prefix| {
+        message == *prefix
+            || message.strip_prefix(prefix).is_some_and(|rest| rest.starts_with([' ', ',']))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        classify_by_keywords, is_follow_up, mentions_any, AgentIntent, FOLLOW_UP_PREFIXES,
+    };
+
+    fn intent(message: &str) -> Option<AgentIntent> {
+        classify_by_keywords(message)
+    }
+
+    #[test]
+    fn mentions_match_whole_words_only() {
+        assert!(mentions_any("please delete the old orders", &["delete"]));
+        assert!(mentions_any("plot sales over time!", &["over time"]));
+        assert!(!mentions_any("when was it last updated", &["update"]));
+        assert!(!mentions_any("show me the pies", &["pie"]));
+    }
+
+    #[test]
+    fn recognizes_follow_ups_by_their_opening() {
+        for prefix in FOLLOW_UP_PREFIXES {
+            assert!(is_follow_up(prefix), "{}", prefix);
+            assert!(is_follow_up(&format!("{} for last month", prefix)), "{}", prefix);
+        }
+        assert!(is_follow_up("  And, only the active ones"));
+        assert!(is_follow_up("What about Canada?"));
+
+        assert!(!is_follow_up("android users"));
+        assert!(!is_follow_up("orders placed today"));
+        assert!(!is_follow_up("show the newest users"));
+        assert!(!is_follow_up(""));
+    }
+
+    #[test]
+    fn classifies_changes_only_when_they_act_on_something() {
+        for message in [
+            "delete order 5",
+            "please delete the cancelled orders",
+            "can you add a new product called Widget",
+            "insert into users values ('ada')",
+            "drop table staging_orders",
+        ] {
+            assert!(matches!(intent(message), Some(AgentIntent::DataModification)), "{}", message);
+        }
+
+        for message in [
+            "show orders I need to remove",
+            "list the users who want to delete their account",
+            "how many products did we add last week",
+            "which orders were updated today",
+            "find rows where the trend is going down",
+        ] {
+            assert!(matches!(intent(message), Some(AgentIntent::DatabaseQuery)), "{}", message);
+        }
+    }
+
+    #[test]
+    fn classifies_charts_even_after_a_lookup_opening() {
+        for message in
+            ["show me a chart of sales by month", "plot the signups", "bar chart of revenue"]
+        {
+            assert!(matches!(intent(message), Some(AgentIntent::Visualization)), "{}", message);
+        }
+
+        for message in ["show the pie orders", "list orders over time", "get the dashboard users"] {
+            assert!(matches!(intent(message), Some(AgentIntent::DatabaseQuery)), "{}", message);
+        }
+    }
 }
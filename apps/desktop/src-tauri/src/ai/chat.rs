@@ -0,0 +1,132 @@
+use crate::types::AgentChatMessage;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Messages kept per session; the oldest are dropped first
+const MAX_SESSION_MESSAGES: usize = 20;
+
+/// What the agent remembers about one conversation
+#[derive(Debug, Clone, Default)]
+pub struct AgentSession {
+    pub messages: Vec<AgentChatMessage>,
+    pub last_question: Option<String>,
+    pub last_sql: Option<String>,
+    pub last_result_summary: Option<String>,
+}
+
+impl AgentSession {
+    /// The previous data question and what it produced, for resolving follow-ups such as
+    /// "now filter by date"; `None` until a question has produced SQL
+    pub fn follow_up_context(&self) -> Option<String> {
+        let sql = self.last_sql.as_ref()?;
+
+        let mut lines = Vec::new();
+        if let Some(question) = &self.last_question {
+            lines.push(format!("Previous question: {}", question));
+        }
+        lines.push(format!("Previous SQL:\n{}", sql));
+        if let Some(summary) = &self.last_result_summary {
+            lines.push(format!("Previous result: {}", summary));
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+/// Agent conversations keyed by session id
+///
+/// Cloning is cheap and every clone shares the same sessions, so commands can keep a handle
+/// without holding the `EmbeddingState` lock while the model answers.
+#[derive(Clone, Default)]
+pub struct AgentSessions {
+    sessions: Arc<Mutex<HashMap<String, AgentSession>>>,
+}
+
+impl AgentSessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A snapshot of the session; empty for a new session
+    pub fn get(&self, session_id: &str) -> AgentSession {
+        let sessions = self.sessions.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        sessions.get(session_id).cloned().unwrap_or_default()
+    }
+
+    /// Add messages to a session, forgetting the oldest ones beyond the history limit
+    pub fn append(&self, session_id: &str, messages: impl IntoIterator<Item = AgentChatMessage>) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let history = &mut sessions.entry(session_id.to_string()).or_default().messages;
+        history.extend(messages);
+        if history.len() > MAX_SESSION_MESSAGES {
+            let excess = history.len() - MAX_SESSION_MESSAGES;
+            history.drain(..excess);
+        }
+    }
+
+    /// Remember the SQL the last data question produced and a summary of its result, along with
+    /// the question itself, the session's latest user message
+    pub fn record_result(
+        &self,
+        session_id: &str,
+        sql: Option<String>,
+        result_summary: Option<String>,
+    ) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let session = sessions.entry(session_id.to_string()).or_default();
+        session.last_question = session
+            .messages
+            .iter()
+            .rev()
+            .find(|message| message.role == "user")
+            .map(|message| message.content.clone());
+        session.last_sql = sql;
+        session.last_result_summary = result_summary;
+    }
+
+    /// Forget a session; returns false when there was nothing to forget
+    pub fn clear(&self, session_id: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        sessions.remove(session_id).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AgentSessions, MAX_SESSION_MESSAGES};
+    use crate::types::AgentChatMessage;
+
+    fn message(content: usize) -> AgentChatMessage {
+        AgentChatMessage { role: "user".to_string(), content: content.to_string() }
+    }
+
+    #[test]
+    fn sessions_keep_recent_messages_and_the_last_result() {
+        let sessions = AgentSessions::new();
+        sessions.append("a", (0..MAX_SESSION_MESSAGES + 5).map(message));
+        sessions.append("b", [message(1)]);
+        assert!(sessions.get("b").follow_up_context().is_none());
+        assert!(sessions.get("c").follow_up_context().is_none());
+
+        let session = sessions.get("a");
+        assert_eq!(session.messages.len(), MAX_SESSION_MESSAGES);
+        assert_eq!(session.messages[0].content, "5");
+
+        sessions.record_result("a", Some("SELECT 1".to_string()), None);
+        let context = sessions.get("a").follow_up_context().unwrap();
+        assert!(context.contains("SELECT 1"));
+        assert!(context.contains(&(MAX_SESSION_MESSAGES + 4).to_string()));
+
+        // Later small talk doesn't replace the question the SQL answered
+        sessions.append("a", [message(99)]);
+        let context = sessions.get("a").follow_up_context().unwrap();
+        assert!(!context.contains("99"));
+
+        sessions.record_result("a", None, Some("no rows".to_string()));
+        assert!(sessions.get("a").follow_up_context().is_none());
+
+        assert!(sessions.clear("a"));
+        assert!(sessions.get("a").messages.is_empty());
+        assert!(!sessions.clear("a"));
+    }
+}
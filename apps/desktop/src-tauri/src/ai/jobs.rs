@@ -0,0 +1,167 @@
+use crate::error::{Result, RowFlowError};
+use crate::types::AiJobInfo;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+struct JobEntry {
+    info: AiJobInfo,
+    token: CancellationToken,
+}
+
+/// In-flight AI jobs, each with a token that cancels it
+///
+/// Cloning is cheap and every clone shares the same jobs, so long-running commands can keep a
+/// handle without holding the `EmbeddingState` lock.
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    jobs: Arc<Mutex<HashMap<String, JobEntry>>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a job under the caller's id, or a fresh one when none is given
+    ///
+    /// The job is removed from the registry when the returned handle is dropped.
+    pub fn start(
+        &self,
+        job_id: Option<String>,
+        kind: &str,
+        description: String,
+        total: usize,
+    ) -> Result<JobHandle> {
+        let job_id = job_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+        let token = CancellationToken::new();
+
+        let mut jobs = self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if jobs.contains_key(&job_id) {
+            return Err(RowFlowError::InvalidInput(format!(
+                "An AI job with id '{}' is already running",
+                job_id
+            )));
+        }
+        jobs.insert(
+            job_id.clone(),
+            JobEntry {
+                info: AiJobInfo {
+                    job_id: job_id.clone(),
+                    kind: kind.to_string(),
+                    description,
+                    completed: 0,
+                    total,
+                    started_at: chrono::Utc::now().to_rfc3339(),
+                },
+                token: token.clone(),
+            },
+        );
+
+        Ok(JobHandle { registry: self.clone(), job_id, token })
+    }
+
+    /// Signal a job to stop; returns false when no such job is running
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let jobs = self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match jobs.get(job_id) {
+            Some(entry) => {
+                entry.token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Running jobs, oldest first
+    pub fn list(&self) -> Vec<AiJobInfo> {
+        let jobs = self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut infos: Vec<AiJobInfo> = jobs.values().map(|entry| entry.info.clone()).collect();
+        infos.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        infos
+    }
+
+    fn update(&self, job_id: &str, apply: impl FnOnce(&mut AiJobInfo)) {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(entry) = jobs.get_mut(job_id) {
+            apply(&mut entry.info);
+        }
+    }
+
+    fn remove(&self, job_id: &str) {
+        let mut jobs = self.jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        jobs.remove(job_id);
+    }
+}
+
+/// A registered job, kept alive for as long as the job runs
+pub struct JobHandle {
+    registry: JobRegistry,
+    job_id: String,
+    token: CancellationToken,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> &str {
+        &self.job_id
+    }
+
+    /// Fail with `JobCancelled` if the job has been cancelled
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.token.is_cancelled() {
+            Err(RowFlowError::JobCancelled(self.job_id.clone()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Run a step of the job, abandoning it as soon as the job is cancelled
+    pub async fn run<T>(&self, step: impl Future<Output = Result<T>>) -> Result<T> {
+        tokio::select! {
+            result = step => result,
+            _ = self.token.cancelled() => Err(RowFlowError::JobCancelled(self.job_id.clone())),
+        }
+    }
+
+    pub fn set_progress(&self, completed: usize, total: usize) {
+        self.registry.update(&self.job_id, |info| {
+            info.completed = completed;
+            info.total = total;
+        });
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        self.registry.remove(&self.job_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JobRegistry;
+    use crate::error::RowFlowError;
+
+    #[tokio::test]
+    async fn cancelled_jobs_stop_and_leave_the_registry() {
+        let registry = JobRegistry::new();
+        let handle =
+            registry.start(Some("job-1".to_string()), "embed_table", "t".into(), 10).unwrap();
+        assert!(registry.start(Some("job-1".to_string()), "embed_table", "t".into(), 10).is_err());
+
+        handle.set_progress(4, 10);
+        assert_eq!(registry.list()[0].completed, 4);
+
+        assert!(registry.cancel("job-1"));
+        assert!(matches!(handle.check_cancelled(), Err(RowFlowError::JobCancelled(_))));
+        let pending = handle.run(std::future::pending::<crate::error::Result<()>>()).await;
+        assert!(matches!(pending, Err(RowFlowError::JobCancelled(_))));
+
+        drop(handle);
+        assert!(registry.list().is_empty());
+        assert!(!registry.cancel("job-1"));
+    }
+}
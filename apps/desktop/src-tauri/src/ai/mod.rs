@@ -1,5 +1,7 @@
 pub mod agent;
 pub mod bundler;
+pub mod chat;
+pub mod jobs;
 pub mod ollama;
 pub mod state;
 pub mod supervisor;
@@ -10,7 +12,9 @@ pub use crate::types::{
 };
 pub use agent::Agent;
 pub use bundler::{detect_system_ollama, format_bytes, OllamaBundler};
-pub use ollama::OllamaClient;
+pub use chat::{AgentSession, AgentSessions};
+pub use jobs::{JobHandle, JobRegistry};
+pub use ollama::{OllamaClient, RequestLimiter};
 pub use state::EmbeddingState;
-pub use supervisor::{OllamaSupervisor, SupervisorConfig};
-pub use vector_store::{EmbeddingRecord, VectorStore};
+pub use supervisor::{OllamaProcessStatus, OllamaSupervisor, SupervisorConfig};
+pub use vector_store::{EmbeddingRecord, SearchFilter, VectorStore};
@@ -1,17 +1,84 @@
 use crate::error::{Result, RowFlowError};
-use crate::types::{OllamaModelInfo, OllamaStatus};
+use crate::types::{AgentChatMessage, OllamaModelInfo, OllamaStatus};
 
-use reqwest::Client;
+use futures_util::StreamExt;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:11434";
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 2;
+
+/// Bounds how many embedding and generation requests are in flight to Ollama at once
+///
+/// Clones share the same bound, so it holds across every copy of the client and survives the
+/// client being rebuilt for a new endpoint.
+#[derive(Clone)]
+pub struct RequestLimiter {
+    semaphore: Arc<Semaphore>,
+    limit: Arc<Mutex<usize>>,
+}
+
+impl RequestLimiter {
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self { semaphore: Arc::new(Semaphore::new(limit)), limit: Arc::new(Mutex::new(limit)) }
+    }
+
+    pub fn limit(&self) -> usize {
+        *self.limit.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Change the bound; lowering it takes effect as in-flight requests finish
+    pub fn set_limit(&self, limit: usize) {
+        let limit = limit.max(1);
+        let mut current = self.limit.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if limit > *current {
+            self.semaphore.add_permits(limit - *current);
+        } else if limit < *current {
+            let excess = *current - limit;
+            let forgotten = self.semaphore.forget_permits(excess);
+            if forgotten < excess {
+                // The rest are held by running requests; retire them once released
+                let semaphore = self.semaphore.clone();
+                let remaining = (excess - forgotten) as u32;
+                tokio::spawn(async move {
+                    if let Ok(permits) = semaphore.acquire_many_owned(remaining).await {
+                        permits.forget();
+                    }
+                });
+            }
+        }
+
+        *current = limit;
+    }
+
+    async fn acquire(&self) -> Result<OwnedSemaphorePermit> {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|error| RowFlowError::InternalError(error.to_string()))
+    }
+}
+
+impl Default for RequestLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_REQUESTS)
+    }
+}
 
 #[derive(Clone)]
 pub struct OllamaClient {
     endpoint: String,
     http: Client,
+    limiter: RequestLimiter,
+    auto_pull: bool,
 }
 
 impl OllamaClient {
@@ -26,7 +93,19 @@ impl OllamaClient {
             .build()
             .expect("failed to build reqwest client");
 
-        Self { endpoint, http }
+        Self { endpoint, http, limiter: RequestLimiter::default(), auto_pull: false }
+    }
+
+    /// Pull a missing model automatically instead of failing the request
+    pub fn with_auto_pull(mut self, auto_pull: bool) -> Self {
+        self.auto_pull = auto_pull;
+        self
+    }
+
+    /// Share a request limiter with other clients instead of using one of its own
+    pub fn with_limiter(mut self, limiter: RequestLimiter) -> Self {
+        self.limiter = limiter;
+        self
     }
 
     pub fn endpoint(&self) -> &str {
@@ -132,6 +211,15 @@ impl OllamaClient {
             return Ok(Vec::new());
         }
 
+        self.with_model(model, || self.send_embed(model, inputs)).await
+    }
+
+    async fn send_embed(
+        &self,
+        model: &str,
+        inputs: &[String],
+    ) -> Result<ModelResponse<Vec<Vec<f32>>>> {
+        let _permit = self.limiter.acquire().await?;
         let url = format!("{}/api/embed", self.endpoint);
         let response = self
             .http
@@ -142,14 +230,18 @@ impl OllamaClient {
             .map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            if is_model_not_found(status, &body) {
+                return Ok(ModelResponse::ModelMissing);
+            }
             return Err(RowFlowError::OllamaError(format!("Embedding request failed: {}", body)));
         }
 
         let payload: EmbedResponse =
             response.json().await.map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
 
-        Ok(payload.embeddings)
+        Ok(ModelResponse::Ready(payload.embeddings))
     }
 
     pub async fn generate(
@@ -166,7 +258,23 @@ impl OllamaClient {
             model: model.to_string(),
             prompt: prompt.to_string(),
             stream: false,
-            format: Some("json".to_string()),
+            format: Some(serde_json::Value::String("json".to_string())),
+        };
+        self.send_generate(request).await
+    }
+
+    /// Generate output constrained to a JSON schema, which Ollama 0.5 and later support
+    pub async fn generate_with_schema(
+        &self,
+        model: &str,
+        prompt: &str,
+        schema: &serde_json::Value,
+    ) -> Result<String> {
+        let request = GenerateRequest {
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            stream: false,
+            format: Some(schema.clone()),
         };
         self.send_generate(request).await
     }
@@ -218,7 +326,7 @@ Instructions:
             model: model.to_string(),
             prompt: full_prompt,
             stream: false,
-            format: format.map(|f| f.to_string()),
+            format: format.map(|f| serde_json::Value::String(f.to_string())),
         };
 
         self.send_generate(request).await
@@ -236,28 +344,173 @@ Instructions:
     }
 
     async fn send_generate(&self, request: GenerateRequest) -> Result<String> {
+        self.with_model(&request.model, || self.send_generate_once(&request)).await
+    }
+
+    async fn send_generate_once(&self, request: &GenerateRequest) -> Result<ModelResponse<String>> {
+        let _permit = self.limiter.acquire().await?;
         let url = format!("{}/api/generate", self.endpoint);
 
         let response = self
             .http
             .post(&url)
-            .json(&request)
+            .json(request)
             .send()
             .await
             .map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            if is_model_not_found(status, &body) {
+                return Ok(ModelResponse::ModelMissing);
+            }
             return Err(RowFlowError::OllamaError(format!("Generate request failed: {}", body)));
         }
 
         let payload: GenerateResponse =
             response.json().await.map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
 
-        Ok(payload.response.trim().to_string())
+        Ok(ModelResponse::Ready(payload.response.trim().to_string()))
+    }
+
+    /// Send a conversation to `/api/chat` and return the assistant's reply
+    pub async fn chat(
+        &self,
+        model: &str,
+        messages: &[AgentChatMessage],
+    ) -> Result<AgentChatMessage> {
+        let request = ChatRequest { model: model.to_string(), messages, stream: false };
+        let ignore_content = |_: &str| {};
+        self.with_model(model, || self.send_chat_once(&request, &ignore_content)).await
+    }
+
+    /// Like `chat`, but streams the reply and passes each piece of it to `on_content` as it
+    /// arrives
+    pub async fn chat_stream(
+        &self,
+        model: &str,
+        messages: &[AgentChatMessage],
+        on_content: &(dyn Fn(&str) + Sync),
+    ) -> Result<AgentChatMessage> {
+        let request = ChatRequest { model: model.to_string(), messages, stream: true };
+        self.with_model(model, || self.send_chat_once(&request, on_content)).await
+    }
+
+    async fn send_chat_once(
+        &self,
+        request: &ChatRequest<'_>,
+        on_content: &(dyn Fn(&str) + Sync),
+    ) -> Result<ModelResponse<AgentChatMessage>> {
+        let _permit = self.limiter.acquire().await?;
+        let url = format!("{}/api/chat", self.endpoint);
+
+        let response = self
+            .http
+            .post(&url)
+            .json(request)
+            .send()
+            .await
+            .map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            if is_model_not_found(status, &body) {
+                return Ok(ModelResponse::ModelMissing);
+            }
+            return Err(RowFlowError::OllamaError(format!("Chat request failed: {}", body)));
+        }
+
+        // Streamed replies arrive as newline-delimited JSON chunks, others as a single object
+        let mut reply = AgentChatMessage { role: "assistant".to_string(), content: String::new() };
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(newline_pos) = buffer.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=newline_pos).collect();
+                apply_chat_chunk(&line, &mut reply, on_content)?;
+            }
+        }
+        apply_chat_chunk(&buffer, &mut reply, on_content)?;
+
+        reply.content = reply.content.trim().to_string();
+        Ok(ModelResponse::Ready(reply))
+    }
+
+    /// Run a request that needs `model`, pulling the model and retrying once when it is
+    /// missing and auto-pull is on
+    async fn with_model<T, F, Fut>(&self, model: &str, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<ModelResponse<T>>>,
+    {
+        match request().await? {
+            ModelResponse::Ready(value) => return Ok(value),
+            ModelResponse::ModelMissing if self.auto_pull => {
+                log::info!("Model {} is not installed; pulling it before retrying", model);
+                self.pull_model(model).await?;
+            }
+            ModelResponse::ModelMissing => return Err(model_not_found_error(model)),
+        }
+
+        match request().await? {
+            ModelResponse::Ready(value) => Ok(value),
+            ModelResponse::ModelMissing => Err(model_not_found_error(model)),
+        }
     }
 }
 
+/// Response to a request naming a model that Ollama may not have
+enum ModelResponse<T> {
+    Ready(T),
+    ModelMissing,
+}
+
+/// Add one line of a chat response to the reply being assembled
+fn apply_chat_chunk(
+    line: &[u8],
+    reply: &mut AgentChatMessage,
+    on_content: &(dyn Fn(&str) + Sync),
+) -> Result<()> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(());
+    }
+
+    let chunk: ChatChunk = serde_json::from_slice(line)
+        .map_err(|error| RowFlowError::OllamaError(error.to_string()))?;
+    if let Some(error) = chunk.error {
+        return Err(RowFlowError::OllamaError(format!("Chat request failed: {}", error)));
+    }
+
+    if let Some(message) = chunk.message {
+        if !message.content.is_empty() {
+            on_content(&message.content);
+            reply.content.push_str(&message.content);
+        }
+        reply.role = message.role;
+    }
+
+    Ok(())
+}
+
+/// Ollama answers 404 with `{"error":"model \"x\" not found, try pulling it first"}`
+fn is_model_not_found(status: StatusCode, body: &str) -> bool {
+    let body = body.to_lowercase();
+    status == StatusCode::NOT_FOUND && body.contains("model") && body.contains("not found")
+}
+
+fn model_not_found_error(model: &str) -> RowFlowError {
+    RowFlowError::OllamaError(format!(
+        "Model '{}' is not installed. Install it in Settings > AI Models (pull_ollama_model) and try again.",
+        model
+    ))
+}
+
 #[derive(Debug, Deserialize)]
 struct VersionResponse {
     version: String,
@@ -290,10 +543,80 @@ struct GenerateRequest {
     prompt: String,
     stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
-    format: Option<String>,
+    format: Option<serde_json::Value>, // "json" or a JSON schema
 }
 
 #[derive(Debug, Deserialize)]
 struct GenerateResponse {
     response: String,
 }
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: String,
+    messages: &'a [AgentChatMessage],
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    message: Option<AgentChatMessage>,
+    error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_chat_chunk, is_model_not_found, RequestLimiter};
+    use crate::types::AgentChatMessage;
+    use reqwest::StatusCode;
+    use std::sync::Mutex;
+
+    #[test]
+    fn detects_missing_models() {
+        let body = r#"{"error":"model \"nomic-embed-text\" not found, try pulling it first"}"#;
+        assert!(is_model_not_found(StatusCode::NOT_FOUND, body));
+        assert!(!is_model_not_found(StatusCode::INTERNAL_SERVER_ERROR, body));
+        assert!(!is_model_not_found(StatusCode::NOT_FOUND, "404 page not found"));
+    }
+
+    #[test]
+    fn assembles_streamed_chat_replies() {
+        let mut reply = AgentChatMessage { role: "assistant".to_string(), content: String::new() };
+        let pieces = Mutex::new(Vec::new());
+        let on_content = |content: &str| pieces.lock().unwrap().push(content.to_string());
+
+        for line in [
+            r#"{"message":{"role":"assistant","content":"Hel"},"done":false}"#,
+            "",
+            r#"{"message":{"role":"assistant","content":"lo"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":""},"done":true}"#,
+        ] {
+            apply_chat_chunk(line.as_bytes(), &mut reply, &on_content).unwrap();
+        }
+
+        assert_eq!(reply.content, "Hello");
+        assert_eq!(*pieces.lock().unwrap(), vec!["Hel", "lo"]);
+        assert!(apply_chat_chunk(br#"{"error":"boom"}"#, &mut reply, &on_content).is_err());
+    }
+
+    #[tokio::test]
+    async fn request_limit_can_be_raised_and_lowered() {
+        let limiter = RequestLimiter::new(2);
+        let first = limiter.acquire().await.unwrap();
+        let second = limiter.acquire().await.unwrap();
+
+        limiter.set_limit(3);
+        assert_eq!(limiter.semaphore.available_permits(), 1);
+
+        // Both original permits are in use, so one of them is retired once released
+        limiter.set_limit(1);
+        assert_eq!(limiter.limit(), 1);
+        assert_eq!(limiter.semaphore.available_permits(), 0);
+
+        drop(first);
+        tokio::task::yield_now().await;
+        drop(second);
+        tokio::task::yield_now().await;
+        assert_eq!(limiter.semaphore.available_permits(), 1);
+    }
+}
@@ -1,4 +1,7 @@
-use super::{OllamaBundler, OllamaClient, OllamaSupervisor, SupervisorConfig, VectorStore};
+use super::{
+    AgentSessions, JobRegistry, OllamaBundler, OllamaClient, OllamaSupervisor, RequestLimiter,
+    SupervisorConfig, VectorStore,
+};
 use crate::error::Result;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -8,6 +11,10 @@ pub struct EmbeddingState {
     ollama_client: OllamaClient,
     supervisor: Option<Arc<OllamaSupervisor>>,
     bundler: OllamaBundler,
+    jobs: JobRegistry,
+    agent_sessions: AgentSessions,
+    request_limiter: RequestLimiter,
+    auto_pull_models: bool,
 }
 
 impl EmbeddingState {
@@ -20,9 +27,19 @@ impl EmbeddingState {
 
         // Initialize Ollama client with default endpoint for now
         // This will be updated if we start our own supervised instance
-        let ollama_client = OllamaClient::new(None);
+        let request_limiter = RequestLimiter::default();
+        let ollama_client = OllamaClient::new(None).with_limiter(request_limiter.clone());
 
-        Ok(Self { vector_store, ollama_client, supervisor: None, bundler })
+        Ok(Self {
+            vector_store,
+            ollama_client,
+            supervisor: None,
+            bundler,
+            jobs: JobRegistry::new(),
+            agent_sessions: AgentSessions::new(),
+            request_limiter,
+            auto_pull_models: false,
+        })
     }
 
     /// Initialize and start supervised Ollama instance
@@ -31,7 +48,7 @@ impl EmbeddingState {
         if let Some(system_path) = super::detect_system_ollama() {
             log::info!("Using system Ollama at: {}", system_path.display());
             // System Ollama typically runs on default port 11434
-            self.ollama_client = OllamaClient::new(Some("http://127.0.0.1:11434".to_string()));
+            self.ollama_client = self.build_client("http://127.0.0.1:11434".to_string());
             return Ok(());
         }
 
@@ -68,13 +85,25 @@ impl EmbeddingState {
 
         // Update Ollama client to use supervised endpoint
         let endpoint = supervisor.endpoint();
-        self.ollama_client = OllamaClient::new(Some(endpoint));
+        self.ollama_client = self.build_client(endpoint);
         self.supervisor = Some(Arc::new(supervisor));
 
         log::info!("Supervised Ollama instance started");
         Ok(())
     }
 
+    fn build_client(&self, endpoint: String) -> OllamaClient {
+        OllamaClient::new(Some(endpoint))
+            .with_limiter(self.request_limiter.clone())
+            .with_auto_pull(self.auto_pull_models)
+    }
+
+    /// Whether embed and generate requests pull a missing model instead of failing
+    pub fn set_auto_pull_models(&mut self, enabled: bool) {
+        self.auto_pull_models = enabled;
+        self.ollama_client = self.ollama_client.clone().with_auto_pull(enabled);
+    }
+
     pub fn vector_store(&self) -> &VectorStore {
         &self.vector_store
     }
@@ -90,4 +119,16 @@ impl EmbeddingState {
     pub fn bundler(&self) -> &OllamaBundler {
         &self.bundler
     }
+
+    pub fn jobs(&self) -> &JobRegistry {
+        &self.jobs
+    }
+
+    pub fn agent_sessions(&self) -> &AgentSessions {
+        &self.agent_sessions
+    }
+
+    pub fn request_limiter(&self) -> &RequestLimiter {
+        &self.request_limiter
+    }
 }
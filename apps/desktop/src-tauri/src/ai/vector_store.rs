@@ -1,12 +1,25 @@
 use crate::error::{Result, RowFlowError};
-use crate::types::{EmbeddingSearchMatch, EmbeddingTableMetadata};
+use crate::types::{EmbeddingImportResult, EmbeddingSearchMatch, EmbeddingTableMetadata};
 
-use rusqlite::{params, params_from_iter, Connection};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::task;
 
+/// Written to `user_version` of exported files so imports can recognise them
+const EXPORT_FORMAT_VERSION: i64 = 1;
+
+/// Which stored embeddings a search considers; unset fields don't restrict it
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    pub schema: Option<String>,
+    pub table: Option<String>,
+    /// `(schema, table)` pairs, any of which may match
+    pub tables: Vec<(String, String)>,
+    pub exclude_chunk_hash: Option<String>,
+}
+
 pub struct VectorStore {
     db_path: PathBuf,
 }
@@ -98,17 +111,17 @@ impl VectorStore {
         Ok(inserted)
     }
 
+    /// Rank the embeddings of a connection that pass `filter` by similarity to a query,
+    /// across every table they come from
     pub async fn search(
         &self,
         connection_id: &str,
-        schema: Option<&str>,
-        table: Option<&str>,
+        filter: SearchFilter,
         query_embedding: &[f32],
         top_k: usize,
     ) -> Result<Vec<EmbeddingSearchMatch>> {
         let db_path = self.db_path.clone();
-        let schema = schema.map(|s| s.to_string());
-        let table = table.map(|t| t.to_string());
+        let SearchFilter { schema, table, tables, exclude_chunk_hash } = filter;
         let query_embedding = query_embedding.to_vec();
         let connection_id = connection_id.to_string();
 
@@ -129,6 +142,21 @@ impl VectorStore {
                 sql.push_str(" AND table_name = ?");
                 bindings.push(table);
             }
+            if !tables.is_empty() {
+                let placeholders = vec!["(?, ?)"; tables.len()].join(", ");
+                sql.push_str(&format!(
+                    " AND (schema_name, table_name) IN (VALUES {})",
+                    placeholders
+                ));
+                for (schema, table) in tables {
+                    bindings.push(schema);
+                    bindings.push(table);
+                }
+            }
+            if let Some(chunk_hash) = exclude_chunk_hash {
+                sql.push_str(" AND chunk_hash <> ?");
+                bindings.push(chunk_hash);
+            }
 
             let mut stmt = conn.prepare(&sql)?;
             let params = params_from_iter(bindings.iter());
@@ -215,6 +243,49 @@ impl VectorStore {
         Ok(metadata)
     }
 
+    /// Columns embedded for a table, in the order the embedding job serialized them
+    ///
+    /// The stored metadata holds the columns but not their order, so the order is read back
+    /// from the record's content, where each column starts a `name: value` line.
+    pub async fn embedded_columns(
+        &self,
+        connection_id: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<Vec<String>>> {
+        let db_path = self.db_path.clone();
+        let connection_id = connection_id.to_string();
+        let schema = schema.to_string();
+        let table = table.to_string();
+
+        let columns = task::spawn_blocking(move || -> Result<Option<Vec<String>>> {
+            let conn = Connection::open(db_path)?;
+            let record: Option<(String, String)> = conn
+                .query_row(
+                    "SELECT content, metadata FROM embeddings \
+                     WHERE connection_id = ?1 AND schema_name = ?2 AND table_name = ?3 \
+                     ORDER BY created_at DESC LIMIT 1",
+                    params![connection_id, schema, table],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+
+            match record {
+                Some((content, metadata)) => match serde_json::from_str::<Value>(&metadata)? {
+                    Value::Object(map) => {
+                        Ok(Some(columns_in_content_order(&content, map.keys().cloned().collect())))
+                    }
+                    _ => Ok(None),
+                },
+                None => Ok(None),
+            }
+        })
+        .await
+        .map_err(|err| RowFlowError::InternalError(err.to_string()))??;
+
+        Ok(columns)
+    }
+
     /// Delete all embeddings for a specific table
     pub async fn delete_table_embeddings(
         &self,
@@ -241,6 +312,208 @@ impl VectorStore {
         Ok(deleted)
     }
 
+    /// Copy embeddings into a standalone SQLite file, optionally only those of one connection,
+    /// schema or table
+    ///
+    /// Rows are copied by SQLite itself, so the export never loads them into memory.
+    pub async fn export_embeddings(
+        &self,
+        path: PathBuf,
+        connection_id: Option<String>,
+        schema: Option<String>,
+        table: Option<String>,
+    ) -> Result<usize> {
+        let db_path = self.db_path.clone();
+
+        let exported = task::spawn_blocking(move || -> Result<usize> {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+
+            let conn = Connection::open(db_path)?;
+            conn.execute("ATTACH DATABASE ?1 AS export", params![path.to_string_lossy()])?;
+            conn.execute_batch(&format!(
+                r#"
+                PRAGMA export.user_version = {};
+                CREATE TABLE export.embeddings (
+                    connection_id TEXT NOT NULL,
+                    schema_name TEXT NOT NULL,
+                    table_name TEXT NOT NULL,
+                    row_reference TEXT NOT NULL,
+                    chunk_hash TEXT NOT NULL,
+                    content TEXT NOT NULL,
+                    metadata TEXT NOT NULL,
+                    embedding TEXT NOT NULL,
+                    created_at INTEGER NOT NULL
+                );
+                "#,
+                EXPORT_FORMAT_VERSION
+            ))?;
+
+            let exported = conn.execute(
+                r#"
+                INSERT INTO export.embeddings
+                SELECT
+                    connection_id,
+                    schema_name,
+                    table_name,
+                    row_reference,
+                    chunk_hash,
+                    content,
+                    metadata,
+                    embedding,
+                    created_at
+                FROM main.embeddings
+                WHERE (?1 IS NULL OR connection_id = ?1)
+                    AND (?2 IS NULL OR schema_name = ?2)
+                    AND (?3 IS NULL OR table_name = ?3)
+                ORDER BY id
+                "#,
+                params![connection_id, schema, table],
+            )?;
+
+            conn.execute("DETACH DATABASE export", [])?;
+            Ok(exported)
+        })
+        .await
+        .map_err(|err| RowFlowError::InternalError(err.to_string()))??;
+
+        Ok(exported)
+    }
+
+    /// Load embeddings from a file written by `export_embeddings`
+    ///
+    /// Rows whose chunk hash is already stored for the same table are skipped. Every table in
+    /// the file must use a single embedding dimension that matches what is already stored for
+    /// it. `connection_id` reassigns the imported rows to another connection.
+    pub async fn import_embeddings(
+        &self,
+        path: PathBuf,
+        connection_id: Option<String>,
+    ) -> Result<EmbeddingImportResult> {
+        let db_path = self.db_path.clone();
+
+        let result = task::spawn_blocking(move || -> Result<EmbeddingImportResult> {
+            if !path.is_file() {
+                return Err(RowFlowError::InvalidInput(format!(
+                    "Embeddings file not found: {}",
+                    path.display()
+                )));
+            }
+
+            let mut conn = Connection::open(db_path)?;
+            conn.execute("ATTACH DATABASE ?1 AS import", params![path.to_string_lossy()])?;
+
+            let version: i64 =
+                conn.query_row("PRAGMA import.user_version", [], |row| row.get(0))?;
+            if version != EXPORT_FORMAT_VERSION {
+                return Err(RowFlowError::InvalidInput(format!(
+                    "{} is not a RowFlow embeddings export",
+                    path.display()
+                )));
+            }
+
+            // Every table needs one dimension, and the same one as its stored embeddings
+            let mut groups = conn.prepare(
+                r#"
+                SELECT
+                    COALESCE(?1, connection_id),
+                    schema_name,
+                    table_name,
+                    MIN(json_array_length(embedding)),
+                    MAX(json_array_length(embedding))
+                FROM import.embeddings
+                GROUP BY 1, 2, 3
+                "#,
+            )?;
+            let mut existing = conn.prepare(
+                "SELECT json_array_length(embedding) FROM main.embeddings \
+                 WHERE connection_id = ?1 AND schema_name = ?2 AND table_name = ?3 LIMIT 1",
+            )?;
+            let mut rows = groups.query(params![connection_id])?;
+            while let Some(row) = rows.next()? {
+                let group_connection: String = row.get(0)?;
+                let schema: String = row.get(1)?;
+                let table: String = row.get(2)?;
+                let min_dimension: i64 = row.get(3)?;
+                let max_dimension: i64 = row.get(4)?;
+
+                if min_dimension != max_dimension {
+                    return Err(RowFlowError::InvalidInput(format!(
+                        "Embeddings for {}.{} mix dimensions {} and {}",
+                        schema, table, min_dimension, max_dimension
+                    )));
+                }
+
+                let stored: Option<i64> = existing
+                    .query_row(params![group_connection, schema, table], |row| row.get(0))
+                    .optional()?;
+                if let Some(stored) = stored.filter(|stored| *stored != min_dimension) {
+                    return Err(RowFlowError::InvalidInput(format!(
+                        "Embeddings for {}.{} have {} dimensions but {} are already stored",
+                        schema, table, min_dimension, stored
+                    )));
+                }
+            }
+            drop(rows);
+            drop(groups);
+            drop(existing);
+
+            let total: i64 =
+                conn.query_row("SELECT COUNT(*) FROM import.embeddings", [], |row| row.get(0))?;
+
+            let tx = conn.transaction()?;
+            let imported = tx.execute(
+                r#"
+                INSERT INTO main.embeddings (
+                    connection_id,
+                    schema_name,
+                    table_name,
+                    row_reference,
+                    chunk_hash,
+                    content,
+                    metadata,
+                    embedding,
+                    created_at
+                )
+                SELECT
+                    COALESCE(?1, i.connection_id),
+                    i.schema_name,
+                    i.table_name,
+                    i.row_reference,
+                    i.chunk_hash,
+                    i.content,
+                    i.metadata,
+                    i.embedding,
+                    i.created_at
+                FROM import.embeddings i
+                WHERE NOT EXISTS (
+                    SELECT 1
+                    FROM main.embeddings e
+                    WHERE e.connection_id = COALESCE(?1, i.connection_id)
+                        AND e.schema_name = i.schema_name
+                        AND e.table_name = i.table_name
+                        AND e.chunk_hash = i.chunk_hash
+                )
+                ON CONFLICT DO NOTHING
+                "#,
+                params![connection_id],
+            )?;
+            tx.commit()?;
+
+            conn.execute("DETACH DATABASE import", [])?;
+
+            Ok(EmbeddingImportResult {
+                imported_rows: imported,
+                skipped_rows: (total as usize).saturating_sub(imported),
+            })
+        })
+        .await
+        .map_err(|err| RowFlowError::InternalError(err.to_string()))??;
+
+        Ok(result)
+    }
+
     fn initialize(&self) -> Result<()> {
         let conn = Connection::open(&self.db_path)?;
         conn.execute_batch(
@@ -274,6 +547,22 @@ impl VectorStore {
     }
 }
 
+/// Order `columns` by the `name: value` lines of a serialized row, after its table and row
+/// header lines; columns without a line keep their relative order at the end
+fn columns_in_content_order(content: &str, mut columns: Vec<String>) -> Vec<String> {
+    let mut ordered = Vec::with_capacity(columns.len());
+    for line in content.lines().skip(2) {
+        let position = columns.iter().position(|column| {
+            line.strip_prefix(column.as_str()).is_some_and(|rest| rest.starts_with(": "))
+        });
+        if let Some(position) = position {
+            ordered.push(columns.remove(position));
+        }
+    }
+    ordered.extend(columns);
+    ordered
+}
+
 fn current_timestamp() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -292,3 +581,65 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         dot / (norm_a * norm_b)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{columns_in_content_order, EmbeddingRecord, VectorStore};
+    use serde_json::json;
+
+    fn record(table: &str, chunk_hash: &str, embedding: Vec<f32>) -> EmbeddingRecord {
+        EmbeddingRecord {
+            connection_id: "source".to_string(),
+            schema_name: "public".to_string(),
+            table_name: table.to_string(),
+            row_reference: format!("row-{}", chunk_hash),
+            chunk_hash: chunk_hash.to_string(),
+            content: chunk_hash.to_string(),
+            metadata: json!({}),
+            embedding,
+        }
+    }
+
+    #[tokio::test]
+    async fn export_and_import_round_trip() {
+        let dir = std::env::temp_dir().join(format!("rowflow-embeddings-{}", uuid::Uuid::new_v4()));
+        let source = VectorStore::new(dir.join("source.db")).unwrap();
+        let target = VectorStore::new(dir.join("target.db")).unwrap();
+        let export_path = dir.join("export.db");
+
+        source
+            .insert_embeddings(vec![
+                record("users", "a", vec![1.0, 0.0]),
+                record("users", "b", vec![0.0, 1.0]),
+                record("orders", "c", vec![1.0, 1.0, 1.0]),
+            ])
+            .await
+            .unwrap();
+        target.insert_embeddings(vec![record("users", "a", vec![1.0, 0.0])]).await.unwrap();
+
+        let exported = source
+            .export_embeddings(export_path.clone(), None, None, Some("users".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(exported, 2);
+
+        let result = target.import_embeddings(export_path.clone(), None).await.unwrap();
+        assert_eq!((result.imported_rows, result.skipped_rows), (1, 1));
+
+        // Stored embeddings with a different dimension than the file reject the import
+        target.delete_table_embeddings("source", "public", "users").await.unwrap();
+        target.insert_embeddings(vec![record("users", "z", vec![1.0, 0.0, 0.0])]).await.unwrap();
+        assert!(target.import_embeddings(export_path, None).await.is_err());
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn reads_column_order_from_content() {
+        // The second line of the multi-line bio names a column that was already read
+        let content =
+            "Table: public.users\nRow: 1\nname: Ada\nbio: knows\nname: Bob\nemail: x\nage: 36";
+        let columns = ["age", "bio", "email", "name"].map(String::from).to_vec();
+        assert_eq!(columns_in_content_order(content, columns), ["name", "bio", "email", "age"]);
+    }
+}
@@ -1,21 +1,85 @@
 use super::schema::{
-    get_table_columns, qualified_table_name, quote_identifier, validate_identifier,
+    fetch_extensions, fetch_inbound_foreign_keys, get_foreign_keys, get_primary_keys,
+    get_table_columns, normalize_type_name, qualified_table_name, quote_identifier,
+    resolve_table_schema, validate_identifier,
 };
+use crate::ai::{EmbeddingState, OllamaProcessStatus};
 use crate::error::{Result, RowFlowError};
-use crate::state::AppState;
+use crate::state::{
+    build_pg_config, build_tls_connector, release_session, AppState, CertVerification,
+};
 use crate::types::{
-    Column, ConnectionInfo, ConnectionProfile, DeleteRowRequest, FieldInfo,
-    ForeignKeySearchRequest, ForeignKeySearchResult, InsertRowRequest, QueryResult,
+    AggregateQueryRequest, BatchDeleteRequest, BatchDeleteResult, BlockingLock,
+    CallFunctionRequest, CascadeDeleteNode, CellBytes, CellBytesRequest, Column,
+    ColumnValueSuggestion, ColumnValueSuggestionRequest, ConnectionDiagnosis,
+    ConnectionFailureCategory, ConnectionInfo, ConnectionOverrides, ConnectionProfile,
+    ConnectionStatusUpdate, DeleteCascadePreview, DeleteCascadePreviewRequest, DeleteRowRequest,
+    FieldInfo, FilterOptions, FilterOptionsRequest, ForeignKeySearchRequest,
+    ForeignKeySearchResult, InboundForeignKey, InsertRowRequest, OrphanedRows, OrphanedRowsRequest,
+    QueryMetrics, QueryResult, QueryStreamEvent, ReferencedRow, ReferencedRowRequest,
+    ReferencingRowsRequest, ReferencingTableRows, ReplicaStatus, ReplicationStatus, ResetSummary,
+    RowByKeyRequest, ServerSetting, StatementStats, TableRowData, TlsModeDiagnosis,
 };
+use bytes::BytesMut;
+use rust_decimal::Decimal;
 use serde_json::{Number, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
+use std::future::Future;
+use std::pin::Pin;
 use std::str::FromStr;
 use std::time::Instant;
-use tauri::State;
-use tokio_postgres::types::{FromSqlOwned, Json, ToSql, Type};
+use tauri::ipc::Channel;
+use tauri::{Emitter, State};
+use tokio::sync::{Mutex, Semaphore};
+use tokio_postgres::config::SslMode;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::{
+    to_sql_checked, Format, FromSql, FromSqlOwned, IsNull, Json, Kind, ToSql, Type,
+};
+use tokio_postgres::{NoTls, Statement, Transaction};
 use uuid::Uuid;
 
+const MAX_VALUE_SUGGESTIONS: i64 = 50;
+const SUGGESTION_SCAN_LIMIT: i64 = 10_000;
+const DEFAULT_FILTER_OPTIONS: i64 = 100;
+const MAX_FILTER_OPTIONS: i64 = 1_000;
+const MAX_REFERENCING_SAMPLES: i64 = 50;
+const MAX_CASCADE_DEPTH: usize = 8;
+const MAX_SERIALIZATION_RETRIES: u32 = 5;
+const PG_STAT_STATEMENTS_INSTALL_HINT: &str =
+    "Add pg_stat_statements to shared_preload_libraries, \
+     restart the server and run CREATE EXTENSION pg_stat_statements;";
+const DEFAULT_STREAM_BATCH_SIZE: usize = 500;
+const STREAM_WINDOW_BATCHES: usize = 4;
+const STREAM_ACK_TIMEOUT_SECS: u64 = 120;
+const DEFAULT_DELETE_BATCH_SIZE: u32 = 10_000;
+const DEFAULT_DELETE_BATCH_PAUSE_MS: u64 = 100;
+const REPLICATION_PRIVILEGE_NOTE: &str =
+    "Replication details require superuser or the pg_read_all_stats role";
+const TLS_DIAGNOSIS_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 500;
+
+/// Settings shown by `get_server_settings` when no name filter is given
+const DEFAULT_SERVER_SETTINGS: &[&str] = &[
+    "server_version",
+    "max_connections",
+    "shared_buffers",
+    "work_mem",
+    "maintenance_work_mem",
+    "effective_cache_size",
+    "default_statistics_target",
+    "random_page_cost",
+    "TimeZone",
+    "statement_timeout",
+    "lock_timeout",
+    "idle_in_transaction_session_timeout",
+    "search_path",
+    "default_transaction_isolation",
+    "wal_level",
+    "max_wal_size",
+];
+
 /// Connect to a PostgreSQL database
 #[tauri::command]
 pub async fn connect_database(
@@ -33,6 +97,81 @@ pub async fn disconnect_database(state: State<'_, AppState>, connection_id: Stri
     state.remove_connection(&connection_id).await
 }
 
+/// Get the last status recorded for a connection
+///
+/// Changes are also pushed as `connection-status` events, so this is only needed to catch up,
+/// e.g. after the window reloads.
+#[tauri::command]
+pub async fn get_connection_status(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<ConnectionStatusUpdate> {
+    state.connection_status(&connection_id).await
+}
+
+/// Disconnect every database and S3 connection and stop the supervised Ollama instance
+///
+/// Recovers from a bad state without restarting the app. The schema tree cache lives in the
+/// frontend; with `clear_schema_cache` a `schema-cache-cleared` event tells it to drop the cache.
+#[tauri::command]
+pub async fn reset_all(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    embedding_state: State<'_, Mutex<EmbeddingState>>,
+    clear_schema_cache: Option<bool>,
+) -> Result<ResetSummary> {
+    log::info!("Resetting all connections");
+
+    let database_connections = state.remove_all_connections().await;
+    let s3_connections = state.remove_all_s3_connections().await;
+
+    let supervisor = embedding_state.lock().await.supervisor();
+    let ollama_stopped = match supervisor {
+        Some(supervisor) if supervisor.status().status != OllamaProcessStatus::Stopped => {
+            supervisor.stop().await?;
+            true
+        }
+        _ => false,
+    };
+
+    let schema_cache_cleared = clear_schema_cache.unwrap_or(false);
+    if schema_cache_cleared {
+        let _ = app.emit("schema-cache-cleared", ());
+    }
+
+    Ok(ResetSummary { database_connections, s3_connections, ollama_stopped, schema_cache_cleared })
+}
+
+/// Open a new connection from an existing connection's profile with overrides applied
+#[tauri::command]
+pub async fn clone_connection(
+    state: State<'_, AppState>,
+    connection_id: String,
+    overrides: ConnectionOverrides,
+) -> Result<String> {
+    log::info!("Cloning connection: {}", connection_id);
+
+    let mut profile = state.get_profile(&connection_id).await?;
+
+    if let Some(database) = overrides.database {
+        let database = database.trim();
+        if database.is_empty() {
+            return Err(RowFlowError::InvalidInput("Database name cannot be empty".to_string()));
+        }
+        profile.database = database.to_string();
+    }
+
+    if let Some(read_only) = overrides.read_only {
+        profile.read_only = read_only;
+    }
+
+    if let Some(search_path) = overrides.search_path {
+        profile.search_path = Some(search_path);
+    }
+
+    state.create_connection(profile).await
+}
+
 /// Test a database connection
 #[tauri::command]
 pub async fn test_connection(profile: ConnectionProfile) -> Result<ConnectionInfo> {
@@ -82,270 +221,1164 @@ pub async fn test_connection(profile: ConnectionProfile) -> Result<ConnectionInf
     Ok(connection_info)
 }
 
-/// Execute a SQL query
+/// Try connecting under each sslmode with the profile's certificates and report which work
+///
+/// Each attempt opens a single connection outside any pool and closes it straight away, so
+/// nothing is stored. The profile's own TLS settings are ignored apart from its certificates.
 #[tauri::command]
-pub async fn execute_query(
+pub async fn diagnose_tls(profile: ConnectionProfile) -> Result<Vec<TlsModeDiagnosis>> {
+    log::info!("Diagnosing TLS for: {}", profile.name);
+
+    let modes = [
+        ("disable", SslMode::Disable, None),
+        ("prefer", SslMode::Prefer, Some(CertVerification::None)),
+        ("require", SslMode::Require, Some(CertVerification::None)),
+        ("verify-ca", SslMode::Require, Some(CertVerification::Ca)),
+        ("verify-full", SslMode::Require, Some(CertVerification::Full)),
+    ];
+
+    let mut diagnoses = Vec::with_capacity(modes.len());
+    for (ssl_mode, mode, verification) in modes {
+        let outcome = try_tls_mode(&profile, mode, verification).await;
+        log::debug!("sslmode {} for {}: {:?}", ssl_mode, profile.name, outcome);
+        diagnoses.push(match outcome {
+            Ok(encrypted) => TlsModeDiagnosis {
+                ssl_mode: ssl_mode.to_string(),
+                ok: true,
+                encrypted: Some(encrypted),
+                error: None,
+            },
+            Err(err) => TlsModeDiagnosis {
+                ssl_mode: ssl_mode.to_string(),
+                ok: false,
+                encrypted: None,
+                error: Some(err.to_string()),
+            },
+        });
+    }
+
+    Ok(diagnoses)
+}
+
+/// Connect once with the given sslmode and return whether the connection is encrypted
+async fn try_tls_mode(
+    profile: &ConnectionProfile,
+    mode: SslMode,
+    verification: Option<CertVerification>,
+) -> Result<bool> {
+    let mut config = build_pg_config(profile);
+    config.ssl_mode(mode);
+    if profile.connection_timeout.is_none() {
+        config.connect_timeout(std::time::Duration::from_secs(TLS_DIAGNOSIS_TIMEOUT_SECS));
+    }
+
+    let client = match verification {
+        Some(verification) => {
+            let tls = build_tls_connector(profile, profile.tls_config.as_ref(), verification)?;
+            let (client, connection) = config.connect(tls).await?;
+            tokio::spawn(connection);
+            client
+        }
+        None => {
+            let (client, connection) = config.connect(NoTls).await?;
+            tokio::spawn(connection);
+            client
+        }
+    };
+
+    let row = client
+        .query_opt("SELECT ssl FROM pg_catalog.pg_stat_ssl WHERE pid = pg_backend_pid()", &[])
+        .await?;
+    Ok(row.is_some_and(|row| row.get::<_, bool>(0)))
+}
+
+/// Try connecting with the profile and, when that fails, say why and how to fix it
+///
+/// The host is resolved first so DNS problems are told apart from an unreachable server, then
+/// a single connection is opened outside any pool with the profile's TLS settings and closed.
+#[tauri::command]
+pub async fn diagnose_connection(profile: ConnectionProfile) -> Result<ConnectionDiagnosis> {
+    log::info!("Diagnosing connection to: {}", profile.name);
+
+    // Unix socket directories have nothing to resolve
+    if !profile.host.starts_with('/') {
+        if let Err(err) = tokio::net::lookup_host((profile.host.as_str(), profile.port)).await {
+            return Ok(connection_failure(ConnectionFailureCategory::Dns, None, err.to_string()));
+        }
+    }
+
+    let mut config = build_pg_config(&profile);
+    if profile.connection_timeout.is_none() {
+        config.connect_timeout(std::time::Duration::from_secs(TLS_DIAGNOSIS_TIMEOUT_SECS));
+    }
+
+    let connected = match profile.tls_config.as_ref().filter(|tls| tls.enabled) {
+        Some(tls_config) => {
+            let verification =
+                if tls_config.verify_ca { CertVerification::Full } else { CertVerification::None };
+            let tls = match build_tls_connector(&profile, Some(tls_config), verification) {
+                Ok(tls) => tls,
+                Err(err) => {
+                    return Ok(connection_failure(
+                        ConnectionFailureCategory::Tls,
+                        None,
+                        err.to_string(),
+                    ))
+                }
+            };
+            config.connect(tls).await.map(|(client, connection)| {
+                tokio::spawn(connection);
+                client
+            })
+        }
+        None => config.connect(NoTls).await.map(|(client, connection)| {
+            tokio::spawn(connection);
+            client
+        }),
+    };
+
+    // Dropping the client closes the connection
+    Ok(match connected {
+        Ok(_client) => ConnectionDiagnosis {
+            ok: true,
+            category: None,
+            sql_state: None,
+            error: None,
+            hint: None,
+        },
+        Err(err) => {
+            log::debug!("Connection diagnosis for {} failed: {:?}", profile.name, err);
+            let message =
+                err.as_db_error().map_or_else(|| err.to_string(), |db| db.message().to_string());
+            connection_failure(classify_connect_error(&err), err.code(), message)
+        }
+    })
+}
+
+/// Work out which stage of connecting an error came from
+fn classify_connect_error(err: &tokio_postgres::Error) -> ConnectionFailureCategory {
+    let mut io_kind = None;
+    let mut tls_source = false;
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            io_kind = io_kind.or(Some(io_error.kind()));
+        }
+        tls_source |= cause.is::<native_tls::Error>();
+        source = cause.source();
+    }
+
+    classify_connect_failure(err.code(), io_kind, tls_source, &err.to_string())
+}
+
+/// Classify a failed connection from what its error carries: the server's SQLSTATE, the kind of
+/// the first I/O error among its causes, whether a TLS error is among them, and its message
+fn classify_connect_failure(
+    code: Option<&SqlState>,
+    io_kind: Option<std::io::ErrorKind>,
+    tls_source: bool,
+    message: &str,
+) -> ConnectionFailureCategory {
+    if let Some(code) = code {
+        return if code == &SqlState::INVALID_PASSWORD
+            || code == &SqlState::INVALID_AUTHORIZATION_SPECIFICATION
+        {
+            ConnectionFailureCategory::Authentication
+        } else if code == &SqlState::INVALID_CATALOG_NAME {
+            ConnectionFailureCategory::DatabaseNotFound
+        } else if code == &SqlState::TOO_MANY_CONNECTIONS {
+            ConnectionFailureCategory::TooManyConnections
+        } else {
+            ConnectionFailureCategory::Server
+        };
+    }
+
+    match io_kind {
+        Some(std::io::ErrorKind::ConnectionRefused) => {
+            return ConnectionFailureCategory::ConnectionRefused
+        }
+        Some(std::io::ErrorKind::TimedOut) => return ConnectionFailureCategory::Timeout,
+        _ => {}
+    }
+    if tls_source {
+        return ConnectionFailureCategory::Tls;
+    }
+
+    // Some TLS failures, such as a server without TLS support, carry no typed source
+    let message = message.to_lowercase();
+    if message.contains("tls") || message.contains("ssl") || message.contains("certificate") {
+        ConnectionFailureCategory::Tls
+    } else if message.contains("timed out") || message.contains("timeout") {
+        ConnectionFailureCategory::Timeout
+    } else {
+        ConnectionFailureCategory::Unknown
+    }
+}
+
+fn connection_failure(
+    category: ConnectionFailureCategory,
+    sql_state: Option<&SqlState>,
+    error: String,
+) -> ConnectionDiagnosis {
+    let hint = match category {
+        ConnectionFailureCategory::Dns => {
+            "The host name could not be resolved; check the host for typos and that you are on \
+             the right network or VPN"
+        }
+        ConnectionFailureCategory::ConnectionRefused => {
+            "Nothing is listening on that host and port; check the port and that the server is \
+             running and accepts TCP connections (listen_addresses)"
+        }
+        ConnectionFailureCategory::Timeout => {
+            "The server did not answer in time; check the host and port and whether a firewall \
+             or security group blocks the connection"
+        }
+        ConnectionFailureCategory::Tls => {
+            "The TLS handshake failed; check that the server has SSL enabled and that the CA \
+             and client certificates match it"
+        }
+        ConnectionFailureCategory::Authentication
+            if sql_state == Some(&SqlState::INVALID_AUTHORIZATION_SPECIFICATION) =>
+        {
+            "The server refused this user; check that the role exists and that pg_hba.conf \
+             allows it to connect from this host to this database"
+        }
+        ConnectionFailureCategory::Authentication => {
+            "Wrong password for this user; check the username and password"
+        }
+        ConnectionFailureCategory::DatabaseNotFound => {
+            "The database doesn't exist on this server; check the database name"
+        }
+        ConnectionFailureCategory::TooManyConnections => {
+            "The server has no free connection slots; close idle sessions or raise \
+             max_connections"
+        }
+        ConnectionFailureCategory::Server => "The server rejected the connection; see the error",
+        ConnectionFailureCategory::Unknown => "The connection failed; see the error",
+    };
+
+    ConnectionDiagnosis {
+        ok: false,
+        category: Some(category),
+        sql_state: sql_state.map(|code| code.code().to_string()),
+        error: Some(error),
+        hint: Some(hint.to_string()),
+    }
+}
+
+/// Get server configuration settings, either a curated default set or those matching a filter
+#[tauri::command]
+pub async fn get_server_settings(
     state: State<'_, AppState>,
     connection_id: String,
-    sql: String,
-    params: Vec<Value>,
-) -> Result<QueryResult> {
-    log::info!("Executing query on connection: {}", connection_id);
+    name_filter: Option<String>,
+) -> Result<Vec<ServerSetting>> {
+    log::info!("Getting server settings on connection: {}", connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    let start = Instant::now();
+    let pattern = name_filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+        .map(|filter| format!("%{}%", escape_like_pattern(filter)));
 
-    // Execute the query
-    let statement = client.prepare(&sql).await?;
-    let converted_params = convert_params(&params, statement.params())?;
-    let param_refs: Vec<&(dyn ToSql + Sync)> =
-        converted_params.iter().map(ConvertedParam::as_sql).collect();
-    let rows = client.query(&statement, &param_refs).await?;
+    let default_names: Vec<&str> = DEFAULT_SERVER_SETTINGS.to_vec();
 
-    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    let query = r#"
+        SELECT name, setting, unit, category, short_desc
+        FROM pg_catalog.pg_settings
+        WHERE ($1::text IS NULL AND name = ANY($2))
+            OR ($1::text IS NOT NULL AND name ILIKE $1)
+        ORDER BY category, name
+    "#;
 
-    // Extract field information
-    let fields: Vec<FieldInfo> = statement
-        .columns()
-        .iter()
-        .map(|col| FieldInfo {
-            name: col.name().to_string(),
-            type_oid: col.type_().oid(),
-            type_name: pg_type_to_name(col.type_()).to_string(),
-            nullable: true, // PostgreSQL doesn't provide this info easily
-        })
-        .collect();
+    let rows = client.query(query, &[&pattern, &default_names]).await?;
 
-    // Convert rows to JSON values
-    let row_values: Vec<Value> = rows
+    let settings = rows
         .iter()
-        .map(|row| {
-            let mut obj = serde_json::Map::new();
-            for (idx, col) in statement.columns().iter().enumerate() {
-                let value = row_to_json_value(row, idx, col.type_());
-                obj.insert(col.name().to_string(), value);
-            }
-            Value::Object(obj)
+        .map(|row| ServerSetting {
+            name: row.get(0),
+            value: row.get(1),
+            unit: row.get(2),
+            category: row.get(3),
+            description: row.get(4),
         })
         .collect();
 
-    let row_count = row_values.len();
-
-    Ok(QueryResult { fields, rows: row_values, row_count, execution_time, has_more: false })
+    Ok(settings)
 }
 
-/// Execute a SQL statement that modifies data and returns the affected row count.
+/// Get the most expensive statements recorded by `pg_stat_statements` for the current database
+///
+/// `order_by` is `total` (default), `mean` or `calls`. Fails with an installation hint when the
+/// extension is missing or not preloaded.
 #[tauri::command]
-pub async fn execute_update(
+pub async fn get_top_statements(
     state: State<'_, AppState>,
     connection_id: String,
-    sql: String,
-) -> Result<u64> {
-    log::info!("Executing update on connection: {}", connection_id);
+    order_by: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<StatementStats>> {
+    log::info!("Getting top statements on connection: {}", connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    let sanitized_sql = sanitize_sql_for_wrapping(&sql);
+    let extension = fetch_extensions(&client)
+        .await?
+        .into_iter()
+        .find(|extension| extension.name == "pg_stat_statements")
+        .ok_or_else(|| {
+            RowFlowError::PreconditionFailed(format!(
+                "The pg_stat_statements extension is not installed. {}",
+                PG_STAT_STATEMENTS_INSTALL_HINT
+            ))
+        })?;
 
-    let start = Instant::now();
+    // PostgreSQL 13 renamed the timing columns to *_exec_time
+    let version_num: i32 =
+        client.query_one("SELECT current_setting('server_version_num')::int", &[]).await?.get(0);
+    let (total_column, mean_column, max_column) = if version_num >= 130000 {
+        ("total_exec_time", "mean_exec_time", "max_exec_time")
+    } else {
+        ("total_time", "mean_time", "max_time")
+    };
 
-    let statement = client.prepare(&sanitized_sql).await?;
-    let affected = client.execute(&statement, &[]).await?;
+    let order_column = match order_by.as_deref().unwrap_or("total") {
+        "total" => total_column,
+        "mean" => mean_column,
+        "calls" => "calls",
+        other => {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Unsupported order '{}'; expected total, mean or calls",
+                other
+            )))
+        }
+    };
 
-    let duration = start.elapsed().as_secs_f64() * 1000.0;
-    log::info!("Update completed: {} rows affected in {:.2}ms", affected, duration);
+    let query = format!(
+        r#"
+        SELECT
+            queryid,
+            query,
+            calls,
+            rows,
+            {total}::float8,
+            {mean}::float8,
+            {max}::float8
+        FROM {schema}.pg_stat_statements
+        WHERE dbid = (SELECT oid FROM pg_catalog.pg_database WHERE datname = current_database())
+        ORDER BY {order} DESC
+        LIMIT $1
+    "#,
+        total = total_column,
+        mean = mean_column,
+        max = max_column,
+        schema = quote_identifier(&extension.schema),
+        order = order_column
+    );
 
-    Ok(affected)
+    let limit = limit.unwrap_or(20).clamp(1, 500);
+    let rows = client.query(query.as_str(), &[&limit]).await.map_err(|err| {
+        if err.code() == Some(&SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE) {
+            RowFlowError::PreconditionFailed(format!(
+                "pg_stat_statements is installed but not loaded. {}",
+                PG_STAT_STATEMENTS_INSTALL_HINT
+            ))
+        } else {
+            err.into()
+        }
+    })?;
+
+    let statements = rows
+        .iter()
+        .map(|row| StatementStats {
+            query_id: row.get(0),
+            query: row.get(1),
+            calls: row.get(2),
+            rows: row.get(3),
+            total_time: row.get(4),
+            mean_time: row.get(5),
+            max_time: row.get(6),
+        })
+        .collect();
+
+    Ok(statements)
 }
 
-/// Execute a query with streaming support for large result sets
+/// Get a replication overview: connected standbys on a primary, or WAL receive/replay progress on a standby
 #[tauri::command]
-pub async fn execute_query_stream(
+pub async fn get_replication_status(
     state: State<'_, AppState>,
     connection_id: String,
-    sql: String,
-    chunk_size: usize,
-    offset: usize,
-) -> Result<QueryResult> {
-    log::info!(
-        "Executing query with pagination on connection: {} (offset: {}, limit: {})",
-        connection_id,
-        offset,
-        chunk_size
-    );
+) -> Result<ReplicationStatus> {
+    log::info!("Getting replication status on connection: {}", connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    // Wrap the query with LIMIT and OFFSET
-    let paginated_sql = format!(
-        "SELECT * FROM ({}) AS subquery LIMIT {} OFFSET {}",
-        sanitize_sql_for_wrapping(&sql),
-        chunk_size + 1,
-        offset
-    );
+    let is_in_recovery: bool = client.query_one("SELECT pg_is_in_recovery()", &[]).await?.get(0);
 
-    let start = Instant::now();
+    let mut status = ReplicationStatus {
+        is_in_recovery,
+        replicas: Vec::new(),
+        upstream_host: None,
+        receive_lsn: None,
+        replay_lsn: None,
+        replay_lag_seconds: None,
+        note: None,
+    };
 
-    // Execute the query
-    let statement = client.prepare(&paginated_sql).await?;
-    let rows = client.query(&statement, &[]).await?;
+    if is_in_recovery {
+        let query = r#"
+            SELECT
+                pg_last_wal_receive_lsn()::text,
+                pg_last_wal_replay_lsn()::text,
+                EXTRACT(EPOCH FROM (now() - pg_last_xact_replay_timestamp()))::float8,
+                (SELECT sender_host FROM pg_stat_wal_receiver LIMIT 1)
+        "#;
+
+        match client.query_one(query, &[]).await {
+            Ok(row) => {
+                status.receive_lsn = row.get(0);
+                status.replay_lsn = row.get(1);
+                status.replay_lag_seconds = row.get(2);
+                status.upstream_host = row.get(3);
+                if status.upstream_host.is_none() {
+                    status.note = Some(
+                        "No active WAL receiver; the standby may be replaying from archive or lacks privileges to view it"
+                            .to_string(),
+                    );
+                }
+            }
+            Err(err) if is_insufficient_privilege(&err) => {
+                status.note = Some(REPLICATION_PRIVILEGE_NOTE.to_string());
+            }
+            Err(err) => return Err(err.into()),
+        }
 
-    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+        return Ok(status);
+    }
 
-    let has_more = rows.len() > chunk_size;
-    let rows_to_return = if has_more { &rows[..chunk_size] } else { &rows[..] };
+    let query = r#"
+        SELECT
+            pid,
+            COALESCE(application_name, ''),
+            client_addr::text,
+            state,
+            sync_state,
+            sent_lsn::text,
+            write_lsn::text,
+            flush_lsn::text,
+            replay_lsn::text,
+            pg_wal_lsn_diff(sent_lsn, replay_lsn)::bigint,
+            EXTRACT(EPOCH FROM write_lag)::float8,
+            EXTRACT(EPOCH FROM flush_lag)::float8,
+            EXTRACT(EPOCH FROM replay_lag)::float8
+        FROM pg_catalog.pg_stat_replication
+        ORDER BY application_name, pid
+    "#;
 
-    // Extract field information
-    let fields: Vec<FieldInfo> = statement
-        .columns()
-        .iter()
-        .map(|col| FieldInfo {
-            name: col.name().to_string(),
-            type_oid: col.type_().oid(),
-            type_name: pg_type_to_name(col.type_()).to_string(),
-            nullable: true,
-        })
-        .collect();
+    let rows = match client.query(query, &[]).await {
+        Ok(rows) => rows,
+        Err(err) if is_insufficient_privilege(&err) => {
+            status.note = Some(REPLICATION_PRIVILEGE_NOTE.to_string());
+            return Ok(status);
+        }
+        Err(err) => return Err(err.into()),
+    };
 
-    // Convert rows to JSON values
-    let row_values: Vec<Value> = rows_to_return
+    status.replicas = rows
         .iter()
-        .map(|row| {
-            let mut obj = serde_json::Map::new();
-            for (idx, col) in statement.columns().iter().enumerate() {
-                let value = row_to_json_value(row, idx, col.type_());
-                obj.insert(col.name().to_string(), value);
-            }
-            Value::Object(obj)
+        .map(|row| ReplicaStatus {
+            pid: row.get(0),
+            application_name: row.get(1),
+            client_address: row.get(2),
+            state: row.get(3),
+            sync_state: row.get(4),
+            sent_lsn: row.get(5),
+            write_lsn: row.get(6),
+            flush_lsn: row.get(7),
+            replay_lsn: row.get(8),
+            replay_lag_bytes: row.get(9),
+            write_lag_seconds: row.get(10),
+            flush_lag_seconds: row.get(11),
+            replay_lag_seconds: row.get(12),
         })
         .collect();
 
-    let row_count = row_values.len();
-
-    Ok(QueryResult { fields, rows: row_values, row_count, execution_time, has_more })
-}
-
-/// Map PostgreSQL type to a simplified type name string
-fn pg_type_to_name(pg_type: &Type) -> &str {
-    match pg_type {
-        &Type::BOOL => "boolean",
-        &Type::INT2 | &Type::INT4 | &Type::INT8 => "integer",
-        &Type::FLOAT4 | &Type::FLOAT8 => "float",
-        &Type::NUMERIC => "numeric",
-        &Type::TEXT | &Type::VARCHAR | &Type::BPCHAR => "text",
-        &Type::BYTEA => "bytea",
-        &Type::TIMESTAMP | &Type::TIMESTAMPTZ => "timestamp",
-        &Type::DATE => "date",
-        &Type::TIME | &Type::TIMETZ => "time",
-        &Type::UUID => "uuid",
-        &Type::JSON | &Type::JSONB => "json",
-        _ => pg_type.name(),
+    // Without pg_read_all_stats the view still lists standbys but hides their details
+    if status.replicas.iter().any(|replica| replica.state.is_none()) {
+        status.replicas.clear();
+        status.note = Some(REPLICATION_PRIVILEGE_NOTE.to_string());
+    } else if status.replicas.is_empty() {
+        status.note = Some("No standbys are currently connected".to_string());
     }
+
+    Ok(status)
 }
 
-/// Normalize SQL so it can be wrapped inside a subquery without syntax errors.
-fn sanitize_sql_for_wrapping(sql: &str) -> String {
-    let trimmed = sql.trim();
-    let sanitized = trimmed.trim_end_matches(&[';', ' ', '\t', '\n', '\r']);
-    sanitized.to_string()
+fn is_insufficient_privilege(err: &tokio_postgres::Error) -> bool {
+    err.code() == Some(&SqlState::INSUFFICIENT_PRIVILEGE)
 }
 
-fn escape_sql_string(value: &str) -> String {
-    value.replace('\'', "''")
+/// Latency percentiles and error rate of recent `execute_query` calls on a connection
+///
+/// `window_seconds` limits the summary to queries that finished that recently; without it every
+/// kept sample counts.
+#[tauri::command]
+pub async fn get_query_metrics(
+    state: State<'_, AppState>,
+    connection_id: String,
+    window_seconds: Option<u64>,
+) -> Result<QueryMetrics> {
+    state.get_profile(&connection_id).await?;
+    let window = window_seconds.map(std::time::Duration::from_secs);
+    Ok(state.query_metrics().summary(&connection_id, window))
 }
 
-fn value_to_sql_literal(value: &Value, column: &Column) -> Result<String> {
-    if is_array_column(column) {
-        return Ok(value_to_array_literal(value));
+/// Execute a SQL query
+///
+/// With `session_id` the query runs on that session's pinned client, so temp tables, `SET`
+/// values and prepared statements from earlier queries in the session are visible. With
+/// `read_only` it runs inside a `READ ONLY` transaction that is always rolled back, so SQL run
+/// without the user's review can't change anything even if it slipped past classification.
+#[tauri::command]
+pub async fn execute_query(
+    state: State<'_, AppState>,
+    connection_id: String,
+    sql: String,
+    params: Vec<Value>,
+    session_id: Option<String>,
+    read_only: Option<bool>,
+) -> Result<QueryResult> {
+    let read_only = read_only.unwrap_or(false);
+    if read_only && session_id.is_some() {
+        return Err(RowFlowError::InvalidInput(
+            "Read-only queries can't run on a session".to_string(),
+        ));
     }
 
-    if is_json_column(column) {
-        let json_text = serde_json::to_string(value).unwrap_or_else(|_| "null".to_string());
-        let cast = json_cast_for_column(column).unwrap_or("::jsonb");
-        return Ok(format!("'{}'{}", escape_sql_string(&json_text), cast));
-    }
+    let start = Instant::now();
 
-    if is_numeric_column(column) {
-        let literal = match value {
-            Value::Null => "NULL".to_string(),
-            Value::Number(num) => num.to_string(),
-            Value::String(text) => text.clone(),
-            Value::Bool(flag) => {
-                if *flag {
-                    "1".to_string()
-                } else {
-                    "0".to_string()
+    // Execute the query
+    let result = async {
+        match session_id {
+            _ if read_only => {
+                let client = state.get_client(&connection_id).await?;
+                client.batch_execute("BEGIN READ ONLY").await?;
+                let result = query_prepared(&state, &connection_id, &client, &sql, &params).await;
+                if client.batch_execute("ROLLBACK").await.is_err() {
+                    // The connection's state is unknown, so keep it out of the pool
+                    drop(deadpool_postgres::Object::take(client));
                 }
+                result
             }
-            _ => value.to_string(),
-        };
-        return Ok(literal);
-    }
-
-    let literal = match value {
-        Value::Null => "NULL".to_string(),
-        Value::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
-        Value::Number(num) => {
-            if let Some(i) = num.as_i64() {
-                i.to_string()
-            } else if let Some(u) = num.as_u64() {
-                u.to_string()
-            } else if let Some(f) = num.as_f64() {
-                if f.is_finite() {
-                    f.to_string()
-                } else {
-                    "NULL".to_string()
-                }
-            } else {
-                "NULL".to_string()
+            Some(session_id) => {
+                let session = state.get_session(&session_id, &connection_id).await?;
+                let client = session.lock().await;
+                query_prepared(&state, &connection_id, &client, &sql, &params).await
+            }
+            None => {
+                let client = state.get_client(&connection_id).await?;
+                query_prepared(&state, &connection_id, &client, &sql, &params).await
             }
         }
-        Value::String(text) => format!("'{}'", escape_sql_string(text)),
-        Value::Array(_) | Value::Object(_) => match serde_json::to_string(value) {
-            Ok(json) => format!("'{}'", escape_sql_string(&json)),
-            Err(_) => "NULL".to_string(),
-        },
-    };
+    }
+    .await;
 
-    Ok(literal)
-}
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    if !matches!(result, Err(RowFlowError::ConnectionNotFound(_))) {
+        state.query_metrics().record(&connection_id, execution_time, result.is_ok());
+        log_query_timing(&state, &connection_id, &sql, execution_time).await;
+    }
+    let (statement, rows) = result?;
 
-fn is_array_column(column: &Column) -> bool {
-    let data_type = column.data_type.to_ascii_lowercase();
-    data_type.contains("array") || data_type.ends_with("[]")
+    Ok(build_query_result(statement.columns(), &rows, execution_time, false))
 }
 
-fn is_numeric_column(column: &Column) -> bool {
-    let data_type = column.data_type.to_ascii_lowercase();
-    data_type.contains("numeric") || data_type.contains("decimal")
+/// Log queries slower than the profile's threshold at warn with their SQL, the rest at debug
+async fn log_query_timing(state: &AppState, connection_id: &str, sql: &str, execution_time: f64) {
+    let threshold = state
+        .get_profile(connection_id)
+        .await
+        .ok()
+        .and_then(|profile| profile.slow_query_threshold)
+        .unwrap_or(DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+
+    if execution_time >= threshold as f64 {
+        log::warn!(
+            "Slow query on connection {} took {:.1} ms: {}",
+            connection_id,
+            execution_time,
+            sql.trim()
+        );
+    } else {
+        log::debug!("Query on connection {} took {:.1} ms", connection_id, execution_time);
+    }
 }
 
-fn json_cast_for_column(column: &Column) -> Option<&'static str> {
-    let data_type = column.data_type.to_ascii_lowercase();
-    match data_type.as_str() {
-        "json" => Some("::json"),
-        "jsonb" => Some("::jsonb"),
-        _ => None,
+/// Run a query using the connection's prepared statement cache
+///
+/// A cached statement fails with "cached plan must not change result type" once DDL alters the
+/// tables it reads, so it is invalidated and prepared again once. `DISCARD` and `DEALLOCATE`
+/// drop the server side of the client's cached statements, so its cache is cleared after them.
+async fn query_prepared(
+    state: &AppState,
+    connection_id: &str,
+    client: &deadpool_postgres::Client,
+    sql: &str,
+    params: &[Value],
+) -> Result<(Statement, Vec<tokio_postgres::Row>)> {
+    let mut invalidated = false;
+    loop {
+        let statement = state.prepare_cached(connection_id, client, sql).await?;
+        let converted_params = convert_params(params, statement.params())?;
+        let param_refs: Vec<&(dyn ToSql + Sync)> =
+            converted_params.iter().map(ConvertedParam::as_sql).collect();
+
+        match client.query(&statement, &param_refs).await {
+            Ok(rows) => {
+                if deallocates_statements(sql) {
+                    client.statement_cache.clear();
+                }
+                return Ok((statement, rows));
+            }
+            Err(err) if !invalidated && is_stale_cached_plan(&err) => {
+                log::debug!("Re-preparing stale cached statement on connection: {}", connection_id);
+                state.invalidate_cached_statement(connection_id, sql).await;
+                invalidated = true;
+            }
+            Err(err) => return Err(err.into()),
+        }
     }
 }
 
-fn is_json_column(column: &Column) -> bool {
-    json_cast_for_column(column).is_some()
+fn deallocates_statements(sql: &str) -> bool {
+    let keyword = sql.split_whitespace().next().unwrap_or("");
+    keyword.eq_ignore_ascii_case("DISCARD") || keyword.eq_ignore_ascii_case("DEALLOCATE")
 }
 
-fn escape_array_element(value: &str) -> String {
-    value.replace('\\', "\\\\").replace('"', "\\\")")
+fn is_stale_cached_plan(err: &tokio_postgres::Error) -> bool {
+    err.as_db_error().is_some_and(|db| {
+        db.code() == &SqlState::FEATURE_NOT_SUPPORTED && db.message().contains("cached plan")
+    })
 }
 
-fn format_array_elements(values: &[Value]) -> String {
-    values
-        .iter()
-        .map(|value| match value {
-            Value::Array(nested) => format!("{{{}}}", format_array_elements(nested)),
-            Value::String(text) => format!("\"{}\"", escape_array_element(text)),
-            Value::Number(num) => num.to_string(),
-            Value::Bool(flag) => if *flag { "TRUE" } else { "FALSE" }.to_string(),
+/// Check out a client and keep it for the session's lifetime
+///
+/// Every other command takes a fresh client from the pool, so temporary tables, `SET` session
+/// variables and prepared statements only persist across commands that pass this session ID.
+#[tauri::command]
+pub async fn create_session(state: State<'_, AppState>, connection_id: String) -> Result<String> {
+    log::info!("Creating session on connection: {}", connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    Ok(state.store_session(&connection_id, client).await)
+}
+
+/// End a session, returning its client to the pool without the session's state
+///
+/// Sessions left open are released the same way once idle for too long.
+#[tauri::command]
+pub async fn close_session(state: State<'_, AppState>, session_id: String) -> Result<()> {
+    log::info!("Closing session: {}", session_id);
+
+    let session = state.take_session(&session_id).await?;
+    release_session(session).await?;
+
+    Ok(())
+}
+
+/// Execute a SQL statement that modifies data and returns the affected row count.
+///
+/// With `max_retries` set, serialization failures and deadlocks are retried. A standalone
+/// statement runs in its own implicit transaction, so re-running it repeats the whole unit.
+#[tauri::command]
+pub async fn execute_update(
+    state: State<'_, AppState>,
+    connection_id: String,
+    sql: String,
+    max_retries: Option<u32>,
+) -> Result<u64> {
+    log::info!("Executing update on connection: {}", connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let sanitized_sql = sanitize_sql_for_wrapping(&sql);
+
+    let start = Instant::now();
+
+    let statement = client.prepare(&sanitized_sql).await?;
+    let retries = retry_limit(max_retries);
+    let mut attempt = 0;
+    let affected = loop {
+        match client.execute(&statement, &[]).await {
+            Ok(affected) => break affected,
+            Err(err) if is_retryable_transaction_error(&err) && attempt < retries => {
+                attempt += 1;
+                log::warn!("Retrying update after {} (attempt {}/{})", err, attempt, retries);
+                retry_backoff(attempt).await;
+            }
+            Err(err) if attempt > 0 => return Err(retries_exhausted_error(attempt, err)),
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    let duration = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!("Update completed: {} rows affected in {:.2}ms", affected, duration);
+
+    Ok(affected)
+}
+
+/// Serialization failures and deadlocks abort the transaction and are safe to retry from the top
+pub(crate) fn is_retryable_transaction_error(err: &tokio_postgres::Error) -> bool {
+    matches!(
+        err.code(),
+        Some(&SqlState::T_R_SERIALIZATION_FAILURE) | Some(&SqlState::T_R_DEADLOCK_DETECTED)
+    )
+}
+
+pub(crate) fn retry_limit(max_retries: Option<u32>) -> u32 {
+    max_retries.unwrap_or(0).min(MAX_SERIALIZATION_RETRIES)
+}
+
+pub(crate) async fn retry_backoff(attempt: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(25 * 2u64.pow(attempt))).await;
+}
+
+pub(crate) fn retries_exhausted_error(attempts: u32, err: tokio_postgres::Error) -> RowFlowError {
+    match RowFlowError::from(err) {
+        RowFlowError::QueryError(message) => RowFlowError::QueryError(format!(
+            "Transaction still failing after {} retries: {}",
+            attempts, message
+        )),
+        other => other,
+    }
+}
+
+/// Execute one statement once per parameter row inside a single transaction
+///
+/// The statement is prepared once and every row is bound against its inferred parameter types.
+/// Any failing row rolls back the whole batch. Returns the total affected row count.
+#[tauri::command]
+pub async fn execute_batch_params(
+    state: State<'_, AppState>,
+    connection_id: String,
+    sql: String,
+    param_rows: Vec<Vec<Value>>,
+) -> Result<u64> {
+    log::info!(
+        "Executing batch of {} parameter rows on connection: {}",
+        param_rows.len(),
+        connection_id
+    );
+
+    if param_rows.is_empty() {
+        return Ok(0);
+    }
+
+    let mut client = state.get_client(&connection_id).await?;
+    let transaction = client.transaction().await?;
+
+    let start = Instant::now();
+
+    let affected = execute_param_rows(&transaction, &sql, &param_rows).await?;
+
+    transaction.commit().await?;
+
+    let duration = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!(
+        "Batch completed: {} rows affected by {} executions in {:.2}ms",
+        affected,
+        param_rows.len(),
+        duration
+    );
+
+    Ok(affected)
+}
+
+/// Prepare `sql` once and execute it for every parameter row within the caller's transaction
+pub(crate) async fn execute_param_rows(
+    transaction: &Transaction<'_>,
+    sql: &str,
+    param_rows: &[Vec<Value>],
+) -> Result<u64> {
+    let statement = transaction.prepare(sql).await?;
+    let mut affected = 0;
+    for (idx, params) in param_rows.iter().enumerate() {
+        let converted_params = convert_params(params, statement.params())
+            .map_err(|e| with_param_row_context(idx, e))?;
+        let param_refs: Vec<&(dyn ToSql + Sync)> =
+            converted_params.iter().map(ConvertedParam::as_sql).collect();
+        affected += transaction
+            .execute(&statement, &param_refs)
+            .await
+            .map_err(|e| with_param_row_context(idx, e.into()))?;
+    }
+
+    Ok(affected)
+}
+
+fn with_param_row_context(idx: usize, err: RowFlowError) -> RowFlowError {
+    match err {
+        RowFlowError::QueryError(message) => {
+            RowFlowError::QueryError(format!("Parameter row {}: {}", idx + 1, message))
+        }
+        RowFlowError::InvalidInput(message) => {
+            RowFlowError::InvalidInput(format!("Parameter row {}: {}", idx + 1, message))
+        }
+        other => other,
+    }
+}
+
+/// Execute a query with streaming support for large result sets
+///
+/// With `columns` only those columns of the query's result are fetched, in the given order,
+/// which keeps wide-table previews from pulling columns the UI doesn't show.
+#[tauri::command]
+pub async fn execute_query_stream(
+    state: State<'_, AppState>,
+    connection_id: String,
+    sql: String,
+    chunk_size: usize,
+    offset: usize,
+    columns: Option<Vec<String>>,
+) -> Result<QueryResult> {
+    log::info!(
+        "Executing query with pagination on connection: {} (offset: {}, limit: {})",
+        connection_id,
+        offset,
+        chunk_size
+    );
+
+    let client = state.get_client(&connection_id).await?;
+    let inner_sql = sanitize_sql_for_wrapping(&sql);
+
+    let projection = match columns {
+        Some(columns) if !columns.is_empty() => {
+            let statement = client.prepare(&inner_sql).await?;
+            let available: HashSet<&str> =
+                statement.columns().iter().map(|column| column.name()).collect();
+            if let Some(missing) = columns.iter().find(|name| !available.contains(name.as_str())) {
+                return Err(RowFlowError::InvalidInput(format!(
+                    "Column '{}' is not in the query's result",
+                    missing
+                )));
+            }
+            columns.iter().map(|name| quote_identifier(name)).collect::<Vec<_>>().join(", ")
+        }
+        _ => "*".to_string(),
+    };
+
+    // Wrap the query with LIMIT and OFFSET
+    let paginated_sql = format!(
+        "SELECT {} FROM ({}) AS subquery LIMIT {} OFFSET {}",
+        projection,
+        inner_sql,
+        chunk_size + 1,
+        offset
+    );
+
+    // Each page may run on a different pooled client, so register this one for cancel_query
+    let backend_pid: i32 = client.query_one("SELECT pg_backend_pid()", &[]).await?.get(0);
+    state.set_active_backend(&connection_id, backend_pid).await;
+
+    let start = Instant::now();
+
+    // Execute the query
+    let outcome = async {
+        let statement = client.prepare(&paginated_sql).await?;
+        let rows = client.query(&statement, &[]).await?;
+        Ok::<_, tokio_postgres::Error>((statement, rows))
+    }
+    .await;
+
+    state.clear_active_backend(&connection_id, backend_pid).await;
+
+    let (statement, rows) = outcome?;
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+
+    let has_more = rows.len() > chunk_size;
+    let rows_to_return = if has_more { &rows[..chunk_size] } else { &rows[..] };
+
+    let mut result =
+        build_query_result(statement.columns(), rows_to_return, execution_time, has_more);
+    result.backend_pid = Some(backend_pid);
+
+    Ok(result)
+}
+
+/// Stream a query's rows over a channel in batches instead of materializing the whole result
+///
+/// Rows are fetched from a portal one batch at a time. Only a small window of batches may be
+/// unacknowledged, so the frontend must call `ack_query_stream` for each `rows` event it handles.
+#[tauri::command]
+pub async fn stream_query(
+    state: State<'_, AppState>,
+    connection_id: String,
+    sql: String,
+    params: Vec<Value>,
+    batch_size: Option<usize>,
+    channel: Channel<QueryStreamEvent>,
+) -> Result<usize> {
+    log::info!("Streaming query on connection: {}", connection_id);
+
+    let batch_size = batch_size.unwrap_or(DEFAULT_STREAM_BATCH_SIZE).clamp(1, 10_000);
+    let stream_id = Uuid::new_v4().to_string();
+    let credits = state.register_query_stream(&stream_id, STREAM_WINDOW_BATCHES).await;
+
+    let outcome = stream_query_batches(
+        &state,
+        &connection_id,
+        &sql,
+        &params,
+        batch_size,
+        &stream_id,
+        &credits,
+        &channel,
+    )
+    .await;
+
+    state.close_query_stream(&stream_id).await;
+
+    outcome
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn stream_query_batches(
+    state: &AppState,
+    connection_id: &str,
+    sql: &str,
+    params: &[Value],
+    batch_size: usize,
+    stream_id: &str,
+    credits: &Semaphore,
+    channel: &Channel<QueryStreamEvent>,
+) -> Result<usize> {
+    let send = |event: QueryStreamEvent| {
+        channel.send(event).map_err(|e| {
+            RowFlowError::InternalError(format!("Failed to send query stream event: {}", e))
+        })
+    };
+
+    let mut client = state.get_client(connection_id).await?;
+    let transaction = client.transaction().await?;
+
+    let start = Instant::now();
+
+    let statement = transaction.prepare(sql).await?;
+    let converted_params = convert_params(params, statement.params())?;
+    let param_refs: Vec<&(dyn ToSql + Sync)> =
+        converted_params.iter().map(ConvertedParam::as_sql).collect();
+    let portal = transaction.bind(&statement, &param_refs).await?;
+
+    send(QueryStreamEvent::Started {
+        stream_id: stream_id.to_string(),
+        fields: fields_from_columns(statement.columns()),
+    })?;
+
+    let mut row_count = 0;
+    let mut batch_index = 0;
+    loop {
+        let rows = transaction.query_portal(&portal, batch_size as i32).await?;
+        if rows.is_empty() {
+            break;
+        }
+
+        // Wait for the frontend to catch up before reading further from the server
+        let permit = tokio::time::timeout(
+            std::time::Duration::from_secs(STREAM_ACK_TIMEOUT_SECS),
+            credits.acquire(),
+        )
+        .await
+        .map_err(|_| {
+            RowFlowError::TimeoutError("Query stream was not acknowledged in time".to_string())
+        })?
+        .map_err(|_| RowFlowError::QueryCancelled)?;
+        permit.forget();
+
+        let fetched = rows.len();
+        row_count += fetched;
+        send(QueryStreamEvent::Rows {
+            batch_index,
+            rows: rows_to_json(statement.columns(), &rows),
+        })?;
+        batch_index += 1;
+
+        if fetched < batch_size {
+            break;
+        }
+    }
+
+    transaction.commit().await?;
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    send(QueryStreamEvent::Finished { row_count, execution_time })?;
+
+    Ok(row_count)
+}
+
+/// Acknowledge a batch received from `stream_query`
+#[tauri::command]
+pub async fn ack_query_stream(state: State<'_, AppState>, stream_id: String) -> Result<()> {
+    state.ack_query_stream(&stream_id).await
+}
+
+/// Stop a running `stream_query` before it sends its next batch
+#[tauri::command]
+pub async fn cancel_query_stream(state: State<'_, AppState>, stream_id: String) -> Result<()> {
+    log::info!("Cancelling query stream: {}", stream_id);
+    state.close_query_stream(&stream_id).await;
+    Ok(())
+}
+
+/// Group the rows of an arbitrary query and aggregate one column, for charting
+#[tauri::command]
+pub async fn aggregate_query(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: AggregateQueryRequest,
+) -> Result<QueryResult> {
+    log::info!(
+        "Aggregating query by '{}' ({}) on connection: {}",
+        request.group_by,
+        request.aggregate,
+        connection_id
+    );
+
+    validate_identifier(&request.group_by, "column")?;
+
+    let aggregate = parse_aggregate_function(&request.aggregate).ok_or_else(|| {
+        RowFlowError::InvalidInput(format!(
+            "Unsupported aggregate function '{}'. Use count, sum, avg, min, or max",
+            request.aggregate
+        ))
+    })?;
+
+    let value_column = request.value_column.as_deref().filter(|column| !column.trim().is_empty());
+    let aggregate_expression = match value_column {
+        Some(column) => {
+            validate_identifier(column, "column")?;
+            format!("{}(source.{})", aggregate, quote_identifier(column))
+        }
+        None if aggregate == "COUNT" => "COUNT(*)".to_string(),
+        None => {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Aggregate {} requires a value column",
+                aggregate
+            )))
+        }
+    };
+
+    let group_ident = quote_identifier(&request.group_by);
+    let sql = format!(
+        "SELECT source.{group} AS {group}, {aggregate} AS \"value\" \
+         FROM ({query}) AS source \
+         GROUP BY source.{group} \
+         ORDER BY source.{group}",
+        group = group_ident,
+        aggregate = aggregate_expression,
+        query = sanitize_sql_for_wrapping(&request.sql)
+    );
+
+    let client = state.get_client(&connection_id).await?;
+
+    let start = Instant::now();
+    let statement = client.prepare(&sql).await?;
+    let rows = client.query(&statement, &[]).await?;
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(build_query_result(statement.columns(), &rows, execution_time, false))
+}
+
+fn parse_aggregate_function(name: &str) -> Option<&'static str> {
+    match name.trim().to_lowercase().as_str() {
+        "count" => Some("COUNT"),
+        "sum" => Some("SUM"),
+        "avg" => Some("AVG"),
+        "min" => Some("MIN"),
+        "max" => Some("MAX"),
+        _ => None,
+    }
+}
+
+/// Convert prepared statement columns and rows into the JSON result shape sent to the UI
+pub(crate) fn build_query_result(
+    columns: &[tokio_postgres::Column],
+    rows: &[tokio_postgres::Row],
+    execution_time: f64,
+    has_more: bool,
+) -> QueryResult {
+    let fields = fields_from_columns(columns);
+    let row_values = rows_to_json(columns, rows);
+    let row_count = row_values.len();
+
+    QueryResult { fields, rows: row_values, row_count, execution_time, has_more, backend_pid: None }
+}
+
+/// Extract field information
+fn fields_from_columns(columns: &[tokio_postgres::Column]) -> Vec<FieldInfo> {
+    columns
+        .iter()
+        .map(|col| FieldInfo {
+            name: col.name().to_string(),
+            type_oid: col.type_().oid(),
+            type_name: match col.type_().kind() {
+                Kind::Array(element) => format!("{}[]", normalize_type_name(element.name())),
+                _ => normalize_type_name(col.type_().name()),
+            },
+            nullable: true, // PostgreSQL doesn't provide this info easily
+        })
+        .collect()
+}
+
+/// Convert rows to JSON values
+fn rows_to_json(columns: &[tokio_postgres::Column], rows: &[tokio_postgres::Row]) -> Vec<Value> {
+    rows.iter()
+        .map(|row| {
+            let mut obj = serde_json::Map::new();
+            for (idx, col) in columns.iter().enumerate() {
+                let value = row_to_json_value(row, idx, col.type_());
+                obj.insert(col.name().to_string(), value);
+            }
+            Value::Object(obj)
+        })
+        .collect()
+}
+
+/// Normalize SQL so it can be wrapped inside a subquery without syntax errors.
+pub(crate) fn sanitize_sql_for_wrapping(sql: &str) -> String {
+    let trimmed = sql.trim();
+    let sanitized = trimmed.trim_end_matches(&[';', ' ', '\t', '\n', '\r']);
+    sanitized.to_string()
+}
+
+/// Escape LIKE/ILIKE wildcards so user input is matched literally
+fn escape_like_pattern(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn escape_array_element(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\")")
+}
+
+fn format_array_elements(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(|value| match value {
+            Value::Array(nested) => format!("{{{}}}", format_array_elements(nested)),
+            Value::String(text) => format!("\"{}\"", escape_array_element(text)),
+            Value::Number(num) => num.to_string(),
+            Value::Bool(flag) => if *flag { "TRUE" } else { "FALSE" }.to_string(),
             Value::Null => "NULL".to_string(),
             Value::Object(obj) => {
                 let json = serde_json::to_string(obj).unwrap_or_else(|_| "{}".to_string());
@@ -356,188 +1389,1150 @@ fn format_array_elements(values: &[Value]) -> String {
         .join(",")
 }
 
-fn build_array_literal(values: &[Value]) -> String {
-    if values.is_empty() {
-        "'{}'".to_string()
-    } else {
-        format!("'{{{}}}'", format_array_elements(values))
+/// Call a stored function or procedure, binding JSON arguments to its declared parameter types
+#[tauri::command]
+pub async fn call_function(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: CallFunctionRequest,
+) -> Result<QueryResult> {
+    log::info!(
+        "Calling function {}.{} with {} argument(s) on connection: {}",
+        request.schema,
+        request.name,
+        request.arguments.len(),
+        connection_id
+    );
+
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.name, "function")?;
+
+    let client = state.get_client(&connection_id).await?;
+
+    // Match overloads on arity, allowing trailing arguments that have defaults
+    let lookup = r#"
+        SELECT
+            p.prokind = 'p' AS is_procedure,
+            p.prorettype = 'pg_catalog.void'::regtype AS returns_void,
+            p.proretset OR t.typtype = 'c' AS returns_rows,
+            ARRAY(
+                SELECT pg_catalog.format_type(a.type_oid, NULL)
+                FROM unnest(p.proargtypes::oid[]) WITH ORDINALITY AS a(type_oid, ordinality)
+                ORDER BY a.ordinality
+            ) AS argument_types
+        FROM pg_catalog.pg_proc p
+        JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
+        JOIN pg_catalog.pg_type t ON t.oid = p.prorettype
+        WHERE n.nspname = $1
+            AND p.proname = $2
+            AND p.prokind IN ('f', 'p')
+            AND $3::int BETWEEN p.pronargs - p.pronargdefaults AND p.pronargs
+    "#;
+
+    let argument_count = i32::try_from(request.arguments.len())
+        .map_err(|_| RowFlowError::InvalidInput("Too many function arguments".to_string()))?;
+    let candidates =
+        client.query(lookup, &[&request.schema, &request.name, &argument_count]).await?;
+
+    let signature = match candidates.as_slice() {
+        [single] => single,
+        [] => {
+            return Err(RowFlowError::InvalidInput(format!(
+                "No function {}.{} accepts {} argument(s)",
+                request.schema, request.name, argument_count
+            )))
+        }
+        _ => {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Function {}.{} is ambiguous: {} overloads accept {} argument(s)",
+                request.schema,
+                request.name,
+                candidates.len(),
+                argument_count
+            )))
+        }
+    };
+
+    let is_procedure: bool = signature.get(0);
+    let returns_void: bool = signature.get(1);
+    let returns_rows: bool = signature.get(2);
+    let argument_types: Vec<String> = signature.get(3);
+
+    // Explicit casts pin overload resolution to the signature found above
+    let placeholders = argument_types
+        .iter()
+        .take(request.arguments.len())
+        .enumerate()
+        .map(|(idx, type_name)| format!("${}::{}", idx + 1, type_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let function =
+        format!("{}.{}", quote_identifier(&request.schema), quote_identifier(&request.name));
+
+    let sql = if is_procedure {
+        format!("CALL {}({})", function, placeholders)
+    } else if returns_rows {
+        format!("SELECT * FROM {}({})", function, placeholders)
+    } else {
+        format!("SELECT {}({}) AS {}", function, placeholders, quote_identifier(&request.name))
+    };
+
+    let start = Instant::now();
+
+    let statement = client.prepare(&sql).await?;
+    let converted_params = convert_params(&request.arguments, statement.params())?;
+    let param_refs: Vec<&(dyn ToSql + Sync)> =
+        converted_params.iter().map(ConvertedParam::as_sql).collect();
+
+    if returns_void && !is_procedure {
+        client.execute(&statement, &param_refs).await?;
+        let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+        return Ok(build_query_result(&[], &[], execution_time, false));
+    }
+
+    let rows = client.query(&statement, &param_refs).await?;
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(build_query_result(statement.columns(), &rows, execution_time, false))
+}
+
+/// Cancel a running query, defaulting to the backend of the connection's active streamed query
+#[tauri::command]
+pub async fn cancel_query(
+    state: State<'_, AppState>,
+    connection_id: String,
+    backend_pid: Option<i32>,
+) -> Result<()> {
+    let backend_pid = match backend_pid {
+        Some(pid) => pid,
+        None => state.active_backend(&connection_id).await.ok_or_else(|| {
+            RowFlowError::InvalidInput("No running query to cancel on this connection".to_string())
+        })?,
+    };
+
+    log::info!("Cancelling query with PID {} on connection: {}", backend_pid, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    // Use pg_cancel_backend to cancel the query
+    let query = "SELECT pg_cancel_backend($1)";
+    client.execute(query, &[&backend_pid]).await?;
+
+    Ok(())
+}
+
+/// Terminate another backend's session, rolling back its open transaction
+#[tauri::command]
+pub async fn terminate_backend(
+    state: State<'_, AppState>,
+    connection_id: String,
+    backend_pid: i32,
+) -> Result<bool> {
+    log::info!("Terminating backend with PID {} on connection: {}", backend_pid, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let row = client.query_one("SELECT pg_terminate_backend($1)", &[&backend_pid]).await?;
+    let terminated: bool = row.get(0);
+
+    Ok(terminated)
+}
+
+/// List sessions waiting on locks along with the sessions blocking them
+#[tauri::command]
+pub async fn get_blocking_locks(
+    state: State<'_, AppState>,
+    connection_id: String,
+) -> Result<Vec<BlockingLock>> {
+    log::info!("Getting blocking locks on connection: {}", connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let query = r#"
+        SELECT DISTINCT
+            blocked_locks.pid,
+            blocked_activity.usename::text,
+            blocked_activity.query,
+            EXTRACT(EPOCH FROM (now() - blocked_activity.query_start))::float8,
+            blocking_locks.pid,
+            blocking_activity.usename::text,
+            blocking_activity.query,
+            blocking_activity.state,
+            blocked_locks.locktype,
+            blocked_locks.mode,
+            blocked_locks.relation::regclass::text
+        FROM pg_catalog.pg_locks blocked_locks
+        JOIN pg_catalog.pg_stat_activity blocked_activity
+            ON blocked_activity.pid = blocked_locks.pid
+        JOIN pg_catalog.pg_locks blocking_locks
+            ON blocking_locks.locktype = blocked_locks.locktype
+            AND blocking_locks.database IS NOT DISTINCT FROM blocked_locks.database
+            AND blocking_locks.relation IS NOT DISTINCT FROM blocked_locks.relation
+            AND blocking_locks.page IS NOT DISTINCT FROM blocked_locks.page
+            AND blocking_locks.tuple IS NOT DISTINCT FROM blocked_locks.tuple
+            AND blocking_locks.virtualxid IS NOT DISTINCT FROM blocked_locks.virtualxid
+            AND blocking_locks.transactionid IS NOT DISTINCT FROM blocked_locks.transactionid
+            AND blocking_locks.classid IS NOT DISTINCT FROM blocked_locks.classid
+            AND blocking_locks.objid IS NOT DISTINCT FROM blocked_locks.objid
+            AND blocking_locks.objsubid IS NOT DISTINCT FROM blocked_locks.objsubid
+            AND blocking_locks.pid <> blocked_locks.pid
+        JOIN pg_catalog.pg_stat_activity blocking_activity
+            ON blocking_activity.pid = blocking_locks.pid
+        WHERE NOT blocked_locks.granted
+            AND blocking_locks.granted
+        ORDER BY 4 DESC NULLS LAST, 1, 5
+    "#;
+
+    let rows = client.query(query, &[]).await?;
+
+    let locks = rows
+        .iter()
+        .map(|row| BlockingLock {
+            blocked_pid: row.get(0),
+            blocked_user: row.get(1),
+            blocked_query: row.get(2),
+            blocked_wait_seconds: row.get(3),
+            blocking_pid: row.get(4),
+            blocking_user: row.get(5),
+            blocking_query: row.get(6),
+            blocking_state: row.get(7),
+            lock_type: row.get(8),
+            lock_mode: row.get(9),
+            relation: row.get(10),
+        })
+        .collect();
+
+    Ok(locks)
+}
+
+/// Get the current backend process ID
+#[tauri::command]
+pub async fn get_backend_pid(state: State<'_, AppState>, connection_id: String) -> Result<i32> {
+    let client = state.get_client(&connection_id).await?;
+
+    let row = client.query_one("SELECT pg_backend_pid()", &[]).await?;
+    let pid: i32 = row.get(0);
+
+    Ok(pid)
+}
+
+/// Insert a single row into a table
+#[tauri::command]
+pub async fn insert_table_row(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: InsertRowRequest,
+) -> Result<u64> {
+    let schema = resolve_table_schema(
+        &state,
+        &connection_id,
+        request.schema.as_deref(),
+        &request.table_name,
+    )
+    .await?;
+
+    log::info!(
+        "Inserting row into table {}.{} on connection: {}",
+        schema,
+        request.table_name,
+        connection_id
+    );
+
+    if request.row.values.is_empty() {
+        return Err(RowFlowError::SchemaError(
+            "Insert request must include at least one column".to_string(),
+        ));
+    }
+
+    let table = qualified_table_name(&schema, &request.table_name)?;
+
+    let columns_metadata = get_table_columns(
+        state.clone(),
+        connection_id.clone(),
+        schema.clone(),
+        request.table_name.clone(),
+    )
+    .await?;
+
+    let column_lookup: HashMap<String, Column> =
+        columns_metadata.into_iter().map(|column| (column.name.clone(), column)).collect();
+
+    let mut columns = Vec::with_capacity(request.row.values.len());
+    let mut placeholders = Vec::with_capacity(request.row.values.len());
+    let mut values = Vec::with_capacity(request.row.values.len());
+
+    for (column, value) in &request.row.values {
+        validate_identifier(column, "column")?;
+        if !column_lookup.contains_key(column) {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Column '{}' does not exist on {}.{}",
+                column, schema, request.table_name
+            )));
+        }
+
+        columns.push(quote_identifier(column));
+        values.push(value.clone());
+        placeholders.push(format!("${}", values.len()));
+    }
+
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES ({});",
+        table,
+        columns.join(", "),
+        placeholders.join(", ")
+    );
+
+    let client = state.get_client(&connection_id).await?;
+
+    execute_with_values(&client, &sql, &values).await
+}
+
+/// Search for candidate rows that can satisfy a foreign key reference
+#[tauri::command]
+pub async fn search_foreign_key_targets(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: ForeignKeySearchRequest,
+) -> Result<Vec<ForeignKeySearchResult>> {
+    log::info!(
+        "Searching foreign key targets for {}.{} ({}) on connection: {}",
+        request.schema,
+        request.table,
+        request.column,
+        connection_id
+    );
+
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table, "table")?;
+    validate_identifier(&request.column, "column")?;
+
+    let client = state.get_client(&connection_id).await?;
+
+    let qualified_table = qualified_table_name(&request.schema, &request.table)?;
+    let column_ident = quote_identifier(&request.column);
+
+    let pattern = request
+        .search
+        .as_ref()
+        .map(|term| term.trim())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("%{term}%"));
+
+    let limit = request.limit.unwrap_or(20).clamp(1, 200);
+
+    let sql = format!(
+        "SELECT ({column})::text AS key, row_to_json(t) AS row \
+         FROM {table} AS t \
+         WHERE ($1::text IS NULL OR ({column})::text ILIKE $1) \
+         ORDER BY ({column})::text \
+         LIMIT $2",
+        column = column_ident,
+        table = qualified_table
+    );
+
+    let rows = client.query(&sql, &[&pattern, &limit]).await?;
+
+    let results = rows
+        .into_iter()
+        .map(|row| ForeignKeySearchResult { key: row.get(0), row: row.get(1) })
+        .collect();
+
+    Ok(results)
+}
+
+/// Fetch the single row identified by the provided key columns
+#[tauri::command]
+pub async fn get_row_by_key(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: RowByKeyRequest,
+) -> Result<Option<Value>> {
+    let schema =
+        resolve_table_schema(&state, &connection_id, request.schema.as_deref(), &request.table)
+            .await?;
+
+    log::info!(
+        "Fetching row by key from {}.{} on connection: {}",
+        schema,
+        request.table,
+        connection_id
+    );
+
+    if request.key.values.is_empty() {
+        return Err(RowFlowError::InvalidInput(
+            "Row lookup must include at least one key column".to_string(),
+        ));
     }
+
+    ensure_primary_key_columns(&state, &connection_id, &schema, &request.table, &request.key)
+        .await?;
+
+    let client = state.get_client(&connection_id).await?;
+    fetch_row_by_columns(&client, &schema, &request.table, &request.key.values).await
 }
 
-fn value_to_array_literal(value: &Value) -> String {
-    match value {
-        Value::Null => "NULL".to_string(),
-        Value::Array(items) => build_array_literal(items),
-        Value::String(text) => {
-            if let Ok(Value::Array(inner)) = serde_json::from_str::<Value>(text) {
-                return build_array_literal(&inner);
-            }
+/// Check the caller's key columns are exactly the table's primary key
+///
+/// Tables without a primary key fall back to the caller's columns; lookups still refuse to
+/// pick one row out of several matches.
+pub(crate) async fn ensure_primary_key_columns(
+    state: &State<'_, AppState>,
+    connection_id: &str,
+    schema: &str,
+    table: &str,
+    key: &TableRowData,
+) -> Result<()> {
+    let primary_keys = get_primary_keys(
+        state.clone(),
+        connection_id.to_string(),
+        schema.to_string(),
+        table.to_string(),
+    )
+    .await?;
 
-            let parts: Vec<Value> = text
-                .split(',')
-                .map(|segment| Value::String(segment.trim().to_string()))
-                .filter(|value| match value {
-                    Value::String(s) => !s.is_empty(),
-                    _ => true,
-                })
-                .collect();
-
-            if !parts.is_empty() {
-                build_array_literal(&parts)
-            } else {
-                build_array_literal(&[Value::String(text.clone())])
-            }
+    if !primary_keys.is_empty() {
+        let mut provided: Vec<&String> = key.values.keys().collect();
+        let mut expected: Vec<&String> = primary_keys.iter().collect();
+        provided.sort();
+        expected.sort();
+        if provided != expected {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Key columns must match the primary key of {}.{}: {}",
+                schema,
+                table,
+                primary_keys.join(", ")
+            )));
         }
-        other => build_array_literal(&[other.clone()]),
     }
+
+    Ok(())
 }
 
-/// Cancel a running query
+/// Fetch the raw bytes of a single `bytea` cell, e.g. to download a stored file
+///
+/// With `path` set the bytes are written to that file instead of being returned.
 #[tauri::command]
-pub async fn cancel_query(
+pub async fn get_cell_bytes(
     state: State<'_, AppState>,
     connection_id: String,
-    backend_pid: i32,
-) -> Result<()> {
-    log::info!("Cancelling query with PID {} on connection: {}", backend_pid, connection_id);
+    request: CellBytesRequest,
+) -> Result<CellBytes> {
+    log::info!(
+        "Fetching bytes of {}.{}.{} on connection: {}",
+        request.schema,
+        request.table,
+        request.column,
+        connection_id
+    );
+
+    if request.key.values.is_empty() {
+        return Err(RowFlowError::InvalidInput(
+            "Cell lookup must include at least one key column".to_string(),
+        ));
+    }
+
+    let columns = get_table_columns(
+        state.clone(),
+        connection_id.clone(),
+        request.schema.clone(),
+        request.table.clone(),
+    )
+    .await?;
+    let column = columns.iter().find(|column| column.name == request.column).ok_or_else(|| {
+        RowFlowError::InvalidInput(format!(
+            "Column '{}' does not exist on {}.{}",
+            request.column, request.schema, request.table
+        ))
+    })?;
+    if column.data_type != "bytea" {
+        return Err(RowFlowError::InvalidInput(format!(
+            "Column '{}' is {}, not bytea",
+            request.column, column.data_type
+        )));
+    }
+
+    ensure_primary_key_columns(
+        &state,
+        &connection_id,
+        &request.schema,
+        &request.table,
+        &request.key,
+    )
+    .await?;
 
     let client = state.get_client(&connection_id).await?;
 
-    // Use pg_cancel_backend to cancel the query
-    let query = "SELECT pg_cancel_backend($1)";
-    client.execute(query, &[&backend_pid]).await?;
+    let qualified_table = qualified_table_name(&request.schema, &request.table)?;
+    let (predicate, params) = key_equality_predicate(&request.key.values)?;
+    let sql = format!(
+        "SELECT t.{} FROM {} AS t WHERE {} LIMIT 2",
+        quote_identifier(&request.column),
+        qualified_table,
+        predicate
+    );
 
-    Ok(())
+    let rows = query_with_json_params(&client, &sql, &params).await?;
+    let bytes: Option<Vec<u8>> = match rows.len() {
+        0 => {
+            return Err(RowFlowError::InvalidInput(format!(
+                "No row in {}.{} matches the key",
+                request.schema, request.table
+            )))
+        }
+        1 => rows[0].get(0),
+        _ => {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Key columns do not identify a single row in {}.{}",
+                request.schema, request.table
+            )))
+        }
+    };
+    let bytes = bytes.ok_or_else(|| {
+        RowFlowError::InvalidInput(format!("Column '{}' is NULL for this row", request.column))
+    })?;
+
+    let size = bytes.len();
+    match request.path {
+        Some(path) => {
+            tokio::fs::write(&path, &bytes).await?;
+            Ok(CellBytes { size, data: None, path: Some(path) })
+        }
+        None => Ok(CellBytes { size, data: Some(bytes), path: None }),
+    }
 }
 
-/// Get the current backend process ID
+/// Follow the foreign key on a column to the parent row it references
 #[tauri::command]
-pub async fn get_backend_pid(state: State<'_, AppState>, connection_id: String) -> Result<i32> {
+pub async fn get_referenced_row(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: ReferencedRowRequest,
+) -> Result<ReferencedRow> {
+    log::info!(
+        "Resolving referenced row for {}.{} ({}) on connection: {}",
+        request.schema,
+        request.table,
+        request.column,
+        connection_id
+    );
+
+    let foreign_keys = get_foreign_keys(
+        state.clone(),
+        connection_id.clone(),
+        request.schema.clone(),
+        request.table.clone(),
+    )
+    .await?;
+
+    let foreign_key = foreign_keys
+        .into_iter()
+        .find(|fk| fk.columns.iter().any(|column| column == &request.column))
+        .ok_or_else(|| {
+            RowFlowError::InvalidInput(format!(
+                "Column '{}' on {}.{} is not part of a foreign key",
+                request.column, request.schema, request.table
+            ))
+        })?;
+
+    // Composite keys need every local column to locate the parent
+    let mut parent_key = BTreeMap::new();
+    for (column, foreign_column) in foreign_key.columns.iter().zip(&foreign_key.foreign_columns) {
+        let value = request.row.values.get(column).ok_or_else(|| {
+            RowFlowError::InvalidInput(format!(
+                "Foreign key '{}' requires a value for column '{}'",
+                foreign_key.name, column
+            ))
+        })?;
+        parent_key.insert(foreign_column.clone(), value.clone());
+    }
+
+    // A NULL in any referencing column means the row references nothing
+    if parent_key.values().any(Value::is_null) {
+        return Ok(ReferencedRow { foreign_key, row: None });
+    }
+
     let client = state.get_client(&connection_id).await?;
+    let row = fetch_row_by_columns(
+        &client,
+        &foreign_key.foreign_schema,
+        &foreign_key.foreign_table,
+        &parent_key,
+    )
+    .await?;
 
-    let row = client.query_one("SELECT pg_backend_pid()", &[]).await?;
-    let pid: i32 = row.get(0);
+    Ok(ReferencedRow { foreign_key, row })
+}
 
-    Ok(pid)
+/// Select a row as JSON where every given column equals its value, using bound parameters.
+/// Returns `None` when nothing matches and an error when the columns do not identify one row.
+pub(crate) async fn fetch_row_by_columns(
+    client: &deadpool_postgres::Client,
+    schema: &str,
+    table: &str,
+    values: &BTreeMap<String, Value>,
+) -> Result<Option<Value>> {
+    let qualified_table = qualified_table_name(schema, table)?;
+    let (predicate, params) = key_equality_predicate(values)?;
+
+    let sql = format!(
+        "SELECT row_to_json(t) AS row FROM {} AS t WHERE {} LIMIT 2",
+        qualified_table, predicate
+    );
+
+    let rows = query_with_json_params(client, &sql, &params).await?;
+
+    match rows.len() {
+        0 => Ok(None),
+        1 => Ok(Some(rows[0].get(0))),
+        _ => Err(RowFlowError::InvalidInput(format!(
+            "Key columns do not identify a single row in {}.{}",
+            schema, table
+        ))),
+    }
 }
 
-/// Insert a single row into a table
+/// Build `t."col" = $n AND ...` for the given key columns, returning the values to bind
+pub(crate) fn key_equality_predicate(
+    values: &BTreeMap<String, Value>,
+) -> Result<(String, Vec<Value>)> {
+    let mut predicates = Vec::with_capacity(values.len());
+    let mut params = Vec::with_capacity(values.len());
+    for (idx, (column, value)) in values.iter().enumerate() {
+        validate_identifier(column, "column")?;
+        if value.is_null() {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Key column '{}' cannot be null",
+                column
+            )));
+        }
+        predicates.push(format!("t.{} = ${}", quote_identifier(column), idx + 1));
+        params.push(value.clone());
+    }
+    Ok((predicates.join(" AND "), params))
+}
+
+/// Prepare a statement and bind JSON values using the parameter types Postgres inferred
+pub(crate) async fn query_with_json_params(
+    client: &deadpool_postgres::Client,
+    sql: &str,
+    params: &[Value],
+) -> Result<Vec<tokio_postgres::Row>> {
+    let statement = client.prepare(sql).await?;
+    let converted_params = convert_params(params, statement.params())?;
+    let param_refs: Vec<&(dyn ToSql + Sync)> =
+        converted_params.iter().map(ConvertedParam::as_sql).collect();
+    Ok(client.query(&statement, &param_refs).await?)
+}
+
+/// List tables whose foreign keys point at a row, with per-table counts and sample rows
 #[tauri::command]
-pub async fn insert_table_row(
+pub async fn get_referencing_rows(
     state: State<'_, AppState>,
     connection_id: String,
-    request: InsertRowRequest,
-) -> Result<u64> {
+    request: ReferencingRowsRequest,
+) -> Result<Vec<ReferencingTableRows>> {
     log::info!(
-        "Inserting row into table {}.{} on connection: {}",
+        "Finding rows referencing {}.{} on connection: {}",
         request.schema,
-        request.table_name,
+        request.table,
         connection_id
     );
 
-    if request.row.values.is_empty() {
-        return Err(RowFlowError::SchemaError(
-            "Insert request must include at least one column".to_string(),
+    if request.key.values.is_empty() {
+        return Err(RowFlowError::InvalidInput(
+            "Row lookup must include at least one key column".to_string(),
         ));
     }
 
-    let table = qualified_table_name(&request.schema, &request.table_name)?;
+    let sample_limit = request.sample_limit.unwrap_or(5).clamp(0, MAX_REFERENCING_SAMPLES);
 
-    let columns_metadata = get_table_columns(
+    let client = state.get_client(&connection_id).await?;
+    find_referencing_rows(
+        &client,
+        &request.schema,
+        &request.table,
+        &request.key.values,
+        sample_limit,
+        request.include_counts,
+    )
+    .await
+}
+
+pub(crate) async fn find_referencing_rows(
+    client: &deadpool_postgres::Client,
+    schema: &str,
+    table: &str,
+    key: &BTreeMap<String, Value>,
+    sample_limit: i64,
+    include_counts: bool,
+) -> Result<Vec<ReferencingTableRows>> {
+    // Inbound keys may target any unique column, so resolve the full parent row first
+    let parent = match fetch_row_by_columns(client, schema, table, key).await? {
+        Some(Value::Object(map)) => map,
+        _ => return Ok(Vec::new()),
+    };
+
+    let inbound_keys = fetch_inbound_foreign_keys(client, schema, table).await?;
+    let mut results = Vec::with_capacity(inbound_keys.len());
+
+    for foreign_key in inbound_keys {
+        let mut child_key = BTreeMap::new();
+        for (column, referenced_column) in
+            foreign_key.columns.iter().zip(&foreign_key.referenced_columns)
+        {
+            let value = parent.get(referenced_column).cloned().unwrap_or(Value::Null);
+            child_key.insert(column.clone(), value);
+        }
+
+        // A NULL parent value can never be matched by a referencing row
+        if child_key.values().any(Value::is_null) {
+            results.push(ReferencingTableRows {
+                foreign_key,
+                row_count: Some(0),
+                sample_rows: Vec::new(),
+            });
+            continue;
+        }
+
+        let child_table = qualified_table_name(&foreign_key.schema, &foreign_key.table)?;
+        let (predicate, params) = key_equality_predicate(&child_key)?;
+
+        let row_count = if include_counts {
+            let sql = format!("SELECT COUNT(*) FROM {} AS t WHERE {}", child_table, predicate);
+            let rows = query_with_json_params(client, &sql, &params).await?;
+            rows.first().map(|row| row.get::<_, i64>(0))
+        } else {
+            None
+        };
+
+        let sample_rows = if sample_limit > 0 {
+            let sql = format!(
+                "SELECT row_to_json(t) FROM {} AS t WHERE {} LIMIT {}",
+                child_table, predicate, sample_limit
+            );
+            query_with_json_params(client, &sql, &params)
+                .await?
+                .iter()
+                .map(|row| row.get::<_, Value>(0))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        results.push(ReferencingTableRows { foreign_key, row_count, sample_rows });
+    }
+
+    Ok(results)
+}
+
+/// Count and sample the rows whose foreign key points at a parent row that doesn't exist
+///
+/// Orphans appear when a constraint was added `NOT VALID`, dropped for a bulk load or
+/// disabled with triggers. As with `MATCH SIMPLE`, rows with a NULL in any key column
+/// reference nothing and are not orphans.
+#[tauri::command]
+pub async fn find_orphaned_rows(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: OrphanedRowsRequest,
+) -> Result<OrphanedRows> {
+    let schema =
+        resolve_table_schema(&state, &connection_id, request.schema.as_deref(), &request.table)
+            .await?;
+
+    log::info!(
+        "Finding orphaned rows of {} on {}.{} on connection: {}",
+        request.constraint_name,
+        schema,
+        request.table,
+        connection_id
+    );
+
+    let foreign_keys = get_foreign_keys(
         state.clone(),
         connection_id.clone(),
+        schema.clone(),
+        request.table.clone(),
+    )
+    .await?;
+    let foreign_key = foreign_keys
+        .into_iter()
+        .find(|fk| fk.name == request.constraint_name)
+        .ok_or_else(|| {
+            RowFlowError::InvalidInput(format!(
+                "Foreign key '{}' not found on {}.{}",
+                request.constraint_name, schema, request.table
+            ))
+        })?;
+
+    let child_table = qualified_table_name(&schema, &request.table)?;
+    let parent_table =
+        qualified_table_name(&foreign_key.foreign_schema, &foreign_key.foreign_table)?;
+    let join_condition = foreign_key
+        .columns
+        .iter()
+        .zip(&foreign_key.foreign_columns)
+        .map(|(column, foreign_column)| {
+            format!("p.{} = c.{}", quote_identifier(foreign_column), quote_identifier(column))
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let not_null = foreign_key
+        .columns
+        .iter()
+        .map(|column| format!("c.{} IS NOT NULL", quote_identifier(column)))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    // A matched parent row has a non-NULL value in every referenced column
+    let missing_parent = format!("p.{} IS NULL", quote_identifier(&foreign_key.foreign_columns[0]));
+    let from_clause = format!(
+        "FROM {} AS c LEFT JOIN {} AS p ON {} WHERE {} AND {}",
+        child_table, parent_table, join_condition, not_null, missing_parent
+    );
+
+    let sample_limit = request.sample_limit.unwrap_or(5).clamp(0, MAX_REFERENCING_SAMPLES);
+    let client = state.get_client(&connection_id).await?;
+
+    let orphan_count: i64 =
+        client.query_one(&format!("SELECT COUNT(*) {}", from_clause), &[]).await?.get(0);
+    let sample_rows = if orphan_count > 0 && sample_limit > 0 {
+        let sql = format!("SELECT row_to_json(c) {} LIMIT {}", from_clause, sample_limit);
+        client.query(&sql, &[]).await?.iter().map(|row| row.get::<_, Value>(0)).collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(OrphanedRows { foreign_key, orphan_count, sample_rows })
+}
+
+/// Report how many rows a delete would remove through ON DELETE CASCADE, without deleting
+#[tauri::command]
+pub async fn preview_delete_cascade(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: DeleteCascadePreviewRequest,
+) -> Result<DeleteCascadePreview> {
+    log::info!(
+        "Previewing delete cascade for {}.{} on connection: {}",
+        request.schema,
+        request.table,
+        connection_id
+    );
+
+    if request.key.values.is_empty() {
+        return Err(RowFlowError::InvalidInput(
+            "Delete preview must include at least one key column".to_string(),
+        ));
+    }
+
+    let table = qualified_table_name(&request.schema, &request.table)?;
+    let (predicate, params) = key_equality_predicate(&request.key.values)?;
+    let selector = format!("SELECT t.* FROM {} AS t WHERE {}", table, predicate);
+
+    let client = state.get_client(&connection_id).await?;
+    let root = cascade_delete_node(
+        &client,
+        &params,
         request.schema.clone(),
-        request.table_name.clone(),
+        request.table.clone(),
+        selector,
+        None,
+        0,
+        Vec::new(),
     )
     .await?;
 
-    let column_lookup: HashMap<String, Column> =
-        columns_metadata.into_iter().map(|column| (column.name.clone(), column)).collect();
+    let total_deleted_rows = count_cascaded_rows(&root);
+    let blocked_by_restrict = has_restricting_rows(&root);
+    let truncated = has_truncated_branch(&root);
 
-    let mut columns = Vec::with_capacity(request.row.values.len());
-    let mut values = Vec::with_capacity(request.row.values.len());
+    Ok(DeleteCascadePreview { root, total_deleted_rows, blocked_by_restrict, truncated })
+}
 
-    for (column, value) in &request.row.values {
-        validate_identifier(column, "column")?;
-        let column_info = column_lookup.get(column).ok_or_else(|| {
-            RowFlowError::InvalidInput(format!(
-                "Column '{}' does not exist on {}.{}",
-                column, request.schema, request.table_name
-            ))
-        })?;
+/// Count the rows selected by `selector` and recurse into the tables whose foreign keys
+/// would cascade from them. Each child selector nests its parent's, so one set of bound
+/// parameters drives the whole tree.
+#[allow(clippy::too_many_arguments)]
+fn cascade_delete_node<'a>(
+    client: &'a deadpool_postgres::Client,
+    params: &'a [Value],
+    schema: String,
+    table: String,
+    selector: String,
+    via: Option<InboundForeignKey>,
+    depth: usize,
+    path: Vec<String>,
+) -> Pin<Box<dyn Future<Output = Result<CascadeDeleteNode>> + Send + 'a>> {
+    Box::pin(async move {
+        let count_sql = format!("SELECT COUNT(*) FROM ({}) AS affected", selector);
+        let row_count: i64 = query_with_json_params(client, &count_sql, params)
+            .await?
+            .first()
+            .map(|row| row.get(0))
+            .unwrap_or(0);
+
+        let cascades = via.as_ref().is_none_or(|fk| fk.on_delete.eq_ignore_ascii_case("CASCADE"));
+
+        let mut children = Vec::new();
+        let mut truncated = false;
+        if row_count > 0 && cascades {
+            for foreign_key in fetch_inbound_foreign_keys(client, &schema, &table).await? {
+                // Skip keys already on this branch so cyclic references terminate
+                let path_key =
+                    format!("{}.{}.{}", foreign_key.schema, foreign_key.table, foreign_key.name);
+                if path.contains(&path_key) {
+                    continue;
+                }
+                // Past the depth limit, only note that the chain goes on
+                if depth >= MAX_CASCADE_DEPTH {
+                    truncated = true;
+                    break;
+                }
+
+                let child_selector =
+                    cascade_child_selector(&schema, &table, &foreign_key, &selector)?;
+
+                let mut child_path = path.clone();
+                child_path.push(path_key);
+
+                let child = cascade_delete_node(
+                    client,
+                    params,
+                    foreign_key.schema.clone(),
+                    foreign_key.table.clone(),
+                    child_selector,
+                    Some(foreign_key),
+                    depth + 1,
+                    child_path,
+                )
+                .await?;
+                children.push(child);
+            }
+        }
+
+        Ok(CascadeDeleteNode {
+            schema,
+            table,
+            constraint_name: via.as_ref().map(|fk| fk.name.clone()),
+            on_delete: via.map(|fk| fk.on_delete),
+            row_count,
+            children,
+            truncated,
+        })
+    })
+}
+
+/// Select the rows `foreign_key` ties to the rows of `selector`
+///
+/// A cascading self-reference such as `employees.manager_id -> employees.id` would only be
+/// followed one level before the branch's cycle guard stops it, so its selector walks the
+/// whole subtree with a recursive query over the referenced key columns instead.
+fn cascade_child_selector(
+    schema: &str,
+    table: &str,
+    foreign_key: &InboundForeignKey,
+    selector: &str,
+) -> Result<String> {
+    let child_table = qualified_table_name(&foreign_key.schema, &foreign_key.table)?;
+    let columns = |alias: &str, names: &[String]| {
+        names
+            .iter()
+            .map(|name| format!("{}.{}", alias, quote_identifier(name)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let child_columns = columns("t", &foreign_key.columns);
+    let parent_columns = columns("p", &foreign_key.referenced_columns);
+    let direct = |select_list: &str| {
+        format!(
+            "SELECT {} FROM {} AS t WHERE ({}) IN (SELECT {} FROM ({}) AS p)",
+            select_list, child_table, child_columns, parent_columns, selector
+        )
+    };
 
-        columns.push(quote_identifier(column));
-        let literal = value_to_sql_literal(value, column_info)?;
-        log::info!(
-            "[insert_table_row] column={} type={} input={} literal={}",
-            column,
-            column_info.data_type,
-            value,
-            literal
-        );
-        values.push(literal);
+    let self_referencing = foreign_key.schema == schema && foreign_key.table == table;
+    if !self_referencing || !foreign_key.on_delete.eq_ignore_ascii_case("CASCADE") {
+        return Ok(direct("t.*"));
     }
 
-    let sql =
-        format!("INSERT INTO {} ({}) VALUES ({});", table, columns.join(", "), values.join(", "));
+    // UNION drops rows already collected, so a cycle in the data ends the recursion
+    let key_columns = columns("t", &foreign_key.referenced_columns);
+    let join_condition = foreign_key
+        .columns
+        .iter()
+        .zip(&foreign_key.referenced_columns)
+        .map(|(column, referenced)| {
+            format!("t.{} = d.{}", quote_identifier(column), quote_identifier(referenced))
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    Ok(format!(
+        "SELECT t.* FROM {table} AS t WHERE ({keys}) IN (WITH RECURSIVE descendants AS ({direct} \
+         UNION SELECT {keys} FROM {table} AS t JOIN descendants AS d ON {join}) \
+         SELECT * FROM descendants)",
+        table = child_table,
+        keys = key_columns,
+        direct = direct(&key_columns),
+        join = join_condition,
+    ))
+}
 
-    let client = state.get_client(&connection_id).await?;
+/// Sum the rows each cascading node deletes
+///
+/// A row reachable through two foreign key paths is counted once per path, so this is an
+/// upper bound on what the delete removes.
+fn count_cascaded_rows(node: &CascadeDeleteNode) -> i64 {
+    let deleted = match node.on_delete.as_deref() {
+        None => node.row_count,
+        Some(action) if action.eq_ignore_ascii_case("CASCADE") => node.row_count,
+        Some(_) => 0,
+    };
+    deleted + node.children.iter().map(count_cascaded_rows).sum::<i64>()
+}
 
-    let affected = client.execute(sql.as_str(), &[]).await?;
-    Ok(affected)
+fn has_truncated_branch(node: &CascadeDeleteNode) -> bool {
+    node.truncated || node.children.iter().any(has_truncated_branch)
 }
 
-/// Search for candidate rows that can satisfy a foreign key reference
+fn has_restricting_rows(node: &CascadeDeleteNode) -> bool {
+    let restricts = node.row_count > 0
+        && node.on_delete.as_deref().is_some_and(|action| {
+            action.eq_ignore_ascii_case("RESTRICT") || action.eq_ignore_ascii_case("NO ACTION")
+        });
+    restricts || node.children.iter().any(has_restricting_rows)
+}
+
+/// Suggest distinct values for a column that start with the given prefix, most frequent first
 #[tauri::command]
-pub async fn search_foreign_key_targets(
+pub async fn get_column_value_suggestions(
     state: State<'_, AppState>,
     connection_id: String,
-    request: ForeignKeySearchRequest,
-) -> Result<Vec<ForeignKeySearchResult>> {
+    request: ColumnValueSuggestionRequest,
+) -> Result<Vec<ColumnValueSuggestion>> {
     log::info!(
-        "Searching foreign key targets for {}.{} ({}) on connection: {}",
+        "Fetching value suggestions for {}.{} ({}) on connection: {}",
         request.schema,
         request.table,
         request.column,
         connection_id
     );
 
-    validate_identifier(&request.schema, "schema")?;
-    validate_identifier(&request.table, "table")?;
     validate_identifier(&request.column, "column")?;
 
-    let client = state.get_client(&connection_id).await?;
-
     let qualified_table = qualified_table_name(&request.schema, &request.table)?;
     let column_ident = quote_identifier(&request.column);
 
     let pattern = request
-        .search
-        .as_ref()
-        .map(|term| term.trim())
-        .filter(|term| !term.is_empty())
-        .map(|term| format!("%{term}%"));
+        .prefix
+        .as_deref()
+        .filter(|prefix| !prefix.is_empty())
+        .map(|prefix| format!("{}%", escape_like_pattern(prefix)));
 
-    let limit = request.limit.unwrap_or(20).clamp(1, 200);
+    let limit = request.limit.unwrap_or(10).clamp(1, MAX_VALUE_SUGGESTIONS);
 
+    // Only the first SUGGESTION_SCAN_LIMIT matching rows are grouped so huge columns stay cheap
     let sql = format!(
-        "SELECT ({column})::text AS key, row_to_json(t) AS row \
-         FROM {table} AS t \
-         WHERE ($1::text IS NULL OR ({column})::text ILIKE $1) \
-         ORDER BY ({column})::text \
+        "SELECT value, COUNT(*) AS frequency \
+         FROM ( \
+             SELECT ({column})::text AS value \
+             FROM {table} \
+             WHERE {column} IS NOT NULL \
+               AND ($1::text IS NULL OR ({column})::text ILIKE $1) \
+             LIMIT {scan_limit} \
+         ) AS candidates \
+         GROUP BY value \
+         ORDER BY frequency DESC, value \
          LIMIT $2",
         column = column_ident,
-        table = qualified_table
+        table = qualified_table,
+        scan_limit = SUGGESTION_SCAN_LIMIT
     );
 
+    let client = state.get_client(&connection_id).await?;
     let rows = client.query(&sql, &[&pattern, &limit]).await?;
 
-    let results = rows
+    let suggestions = rows
         .into_iter()
-        .map(|row| ForeignKeySearchResult { key: row.get(0), row: row.get(1) })
+        .map(|row| ColumnValueSuggestion { value: row.get(0), frequency: row.get(1) })
         .collect();
 
-    Ok(results)
+    Ok(suggestions)
+}
+
+/// List the values a column can be filtered by: its distinct non-null values when there are few
+/// enough of them, and every label when it is an enum
+#[tauri::command]
+pub async fn get_filter_options(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: FilterOptionsRequest,
+) -> Result<FilterOptions> {
+    log::info!(
+        "Fetching filter options for {}.{} ({}) on connection: {}",
+        request.schema,
+        request.table,
+        request.column,
+        connection_id
+    );
+
+    validate_identifier(&request.column, "column")?;
+
+    let qualified_table = qualified_table_name(&request.schema, &request.table)?;
+    let column_ident = quote_identifier(&request.column);
+    let limit = request.limit.unwrap_or(DEFAULT_FILTER_OPTIONS).clamp(1, MAX_FILTER_OPTIONS);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let type_row = client
+        .query_opt(
+            r#"
+            SELECT
+                pg_catalog.format_type(a.atttypid, a.atttypmod),
+                CASE WHEN t.typtype = 'e' THEN ARRAY(
+                    SELECT e.enumlabel::text
+                    FROM pg_catalog.pg_enum e
+                    WHERE e.enumtypid = t.oid
+                    ORDER BY e.enumsortorder
+                ) END
+            FROM pg_catalog.pg_attribute a
+            JOIN pg_catalog.pg_type t ON t.oid = a.atttypid
+            WHERE a.attrelid = $1::text::regclass
+                AND a.attname = $2
+                AND a.attnum > 0
+                AND NOT a.attisdropped
+            "#,
+            &[&qualified_table, &request.column],
+        )
+        .await?
+        .ok_or_else(|| {
+            RowFlowError::InvalidInput(format!(
+                "Column '{}' does not exist on {}.{}",
+                request.column, request.schema, request.table
+            ))
+        })?;
+    let data_type: String = type_row.get(0);
+    let enum_labels: Option<Vec<String>> = type_row.get(1);
+
+    // One extra row tells whether the list was cut off; ordering by the column itself keeps
+    // enums in declaration order
+    let sql = format!(
+        "SELECT value::text \
+         FROM ( \
+             SELECT DISTINCT {column} AS value \
+             FROM {table} \
+             WHERE {column} IS NOT NULL \
+             ORDER BY 1 \
+             LIMIT $1 \
+         ) AS options",
+        column = column_ident,
+        table = qualified_table
+    );
+    let rows = client.query(&sql, &[&(limit + 1)]).await?;
+
+    let truncated = rows.len() as i64 > limit;
+    let values = rows.iter().take(limit as usize).map(|row| row.get(0)).collect();
+
+    Ok(FilterOptions { data_type, values, truncated, enum_labels })
 }
 
 /// Delete rows from a table matching the provided criteria
@@ -547,58 +2542,195 @@ pub async fn delete_table_rows(
     connection_id: String,
     request: DeleteRowRequest,
 ) -> Result<u64> {
+    let schema = resolve_table_schema(
+        &state,
+        &connection_id,
+        request.schema.as_deref(),
+        &request.table_name,
+    )
+    .await?;
+
     log::info!(
         "Deleting rows from table {}.{} on connection: {}",
-        request.schema,
+        schema,
+        request.table_name,
+        connection_id
+    );
+
+    let (table, condition, values) = build_delete_condition(
+        state.clone(),
+        &connection_id,
+        &schema,
+        &request.table_name,
+        &request.criteria,
+    )
+    .await?;
+
+    let limit_clause = request.limit.map(|limit| format!(" LIMIT {}", limit)).unwrap_or_default();
+
+    let sql = format!("DELETE FROM {} WHERE {}{};", table, condition, limit_clause);
+
+    let client = state.get_client(&connection_id).await?;
+
+    execute_with_values(&client, &sql, &values).await
+}
+
+/// Delete the rows matching the criteria a batch at a time, so no lock is held for long
+///
+/// Each batch deletes up to `batch_size` rows in its own transaction, followed by a short
+/// pause that lets autovacuum and other sessions get a turn, until no matching rows remain.
+/// A `batch-delete-progress` event with the running total follows every batch.
+#[tauri::command]
+pub async fn delete_in_batches(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: BatchDeleteRequest,
+) -> Result<BatchDeleteResult> {
+    let schema = resolve_table_schema(
+        &state,
+        &connection_id,
+        request.schema.as_deref(),
+        &request.table_name,
+    )
+    .await?;
+
+    log::info!(
+        "Deleting rows in batches from table {}.{} on connection: {}",
+        schema,
         request.table_name,
         connection_id
     );
 
-    if request.criteria.values.is_empty() {
+    let batch_size = request.batch_size.unwrap_or(DEFAULT_DELETE_BATCH_SIZE);
+    if batch_size == 0 {
+        return Err(RowFlowError::InvalidInput("Batch size must be at least 1".to_string()));
+    }
+    let pause =
+        std::time::Duration::from_millis(request.pause_ms.unwrap_or(DEFAULT_DELETE_BATCH_PAUSE_MS));
+
+    let (table, condition, values) = build_delete_condition(
+        state.clone(),
+        &connection_id,
+        &schema,
+        &request.table_name,
+        &request.criteria,
+    )
+    .await?;
+
+    // The condition is repeated outside the subquery since a ctid is only unique within one
+    // partition, and a row updated in the meantime may no longer match
+    let sql = format!(
+        "DELETE FROM {table} WHERE {condition} AND ctid IN \
+         (SELECT ctid FROM {table} WHERE {condition} LIMIT {batch_size})"
+    );
+
+    let start = Instant::now();
+    let mut deleted = 0;
+    let mut batches = 0;
+
+    loop {
+        let client = state.get_client(&connection_id).await?;
+        let affected = execute_with_values(&client, &sql, &values).await?;
+        drop(client);
+
+        if affected == 0 {
+            break;
+        }
+        deleted += affected;
+        batches += 1;
+
+        let _ = app.emit(
+            "batch-delete-progress",
+            serde_json::json!({
+                "connectionId": connection_id,
+                "table": table,
+                "deleted": deleted,
+                "batches": batches
+            }),
+        );
+
+        if affected < u64::from(batch_size) {
+            break;
+        }
+        tokio::time::sleep(pause).await;
+    }
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!(
+        "Deleted {} rows from {} in {} batches in {:.2}ms",
+        deleted,
+        table,
+        batches,
+        execution_time
+    );
+
+    Ok(BatchDeleteResult { deleted, batches, execution_time })
+}
+
+/// The qualified table name and the `WHERE` condition matching a delete's criteria columns,
+/// with the values for its `$n` placeholders
+async fn build_delete_condition(
+    state: State<'_, AppState>,
+    connection_id: &str,
+    schema: &str,
+    table_name: &str,
+    criteria: &TableRowData,
+) -> Result<(String, String, Vec<Value>)> {
+    if criteria.values.is_empty() {
         return Err(RowFlowError::SchemaError(
             "Delete request must include at least one criteria column".to_string(),
         ));
     }
 
-    let table = qualified_table_name(&request.schema, &request.table_name)?;
+    let table = qualified_table_name(schema, table_name)?;
 
     let columns_metadata = get_table_columns(
-        state.clone(),
-        connection_id.clone(),
-        request.schema.clone(),
-        request.table_name.clone(),
+        state,
+        connection_id.to_string(),
+        schema.to_string(),
+        table_name.to_string(),
     )
     .await?;
-    let column_lookup: HashMap<String, Column> =
-        columns_metadata.into_iter().map(|column| (column.name.clone(), column)).collect();
+    let column_names: HashSet<String> =
+        columns_metadata.into_iter().map(|column| column.name).collect();
 
-    let mut predicates = Vec::with_capacity(request.criteria.values.len());
-    for (column, value) in &request.criteria.values {
+    let mut predicates = Vec::with_capacity(criteria.values.len());
+    let mut values = Vec::new();
+    for (column, value) in &criteria.values {
         validate_identifier(column, "column")?;
-        let column_info = column_lookup.get(column).ok_or_else(|| {
-            RowFlowError::InvalidInput(format!(
+        if !column_names.contains(column) {
+            return Err(RowFlowError::InvalidInput(format!(
                 "Column '{}' does not exist on {}.{}",
-                column, request.schema, request.table_name
-            ))
-        })?;
+                column, schema, table_name
+            )));
+        }
         let ident = quote_identifier(column);
         let predicate = if value.is_null() {
             format!("{ident} IS NULL")
         } else {
-            let literal = value_to_sql_literal(value, column_info)?;
-            format!("{ident} = {literal}")
+            values.push(value.clone());
+            format!("{ident} = ${}", values.len())
         };
         predicates.push(predicate);
     }
 
-    let limit_clause = request.limit.map(|limit| format!(" LIMIT {}", limit)).unwrap_or_default();
-
-    let sql = format!("DELETE FROM {} WHERE {}{};", table, predicates.join(" AND "), limit_clause);
+    Ok((table, predicates.join(" AND "), values))
+}
 
-    let client = state.get_client(&connection_id).await?;
+/// Run a statement whose `$n` placeholders take `values` in order, each converted for the
+/// parameter type the server infers from the column it is written to or compared with
+async fn execute_with_values(
+    client: &tokio_postgres::Client,
+    sql: &str,
+    values: &[Value],
+) -> Result<u64> {
+    let statement = client.prepare(sql).await?;
+    let converted_params = convert_params(values, statement.params())?;
+    let param_refs: Vec<&(dyn ToSql + Sync)> =
+        converted_params.iter().map(ConvertedParam::as_sql).collect();
 
-    let affected = client.execute(sql.as_str(), &[]).await?;
-    Ok(affected)
+    Ok(client.execute(&statement, &param_refs).await?)
 }
 
 /// Helper function to convert a PostgreSQL row value to JSON
@@ -670,9 +2802,17 @@ pub(crate) fn row_to_json_value(row: &tokio_postgres::Row, idx: usize, col_type:
         &Type::FLOAT4_ARRAY => {
             array_cell_to_value(row, idx, |v: f32| Number::from_f64(v as f64).map(Value::Number))
         }
-        &Type::FLOAT8_ARRAY | &Type::NUMERIC_ARRAY => {
+        &Type::FLOAT8_ARRAY => {
             array_cell_to_value(row, idx, |v: f64| Number::from_f64(v).map(Value::Number))
         }
+        &Type::NUMERIC_ARRAY => {
+            match array_cell_to_value(row, idx, |v: Decimal| Some(decimal_to_value(v))) {
+                Value::Null => {
+                    array_cell_to_value(row, idx, |v: NumericText| Some(Value::String(v.0)))
+                }
+                value => value,
+            }
+        }
         &Type::BOOL_ARRAY => array_cell_to_value(row, idx, |v: bool| Some(Value::Bool(v))),
         &Type::JSON_ARRAY => array_cell_to_value(row, idx, |v: Value| Some(v)),
         &Type::JSON | &Type::JSONB => {
@@ -718,20 +2858,86 @@ pub(crate) fn row_to_json_value(row: &tokio_postgres::Row, idx: usize, col_type:
 }
 
 fn numeric_cell_to_value(row: &tokio_postgres::Row, idx: usize) -> Value {
-    if let Ok(Some(value)) = row.try_get::<_, Option<f64>>(idx) {
-        if let Some(number) = Number::from_f64(value) {
-            return Value::Number(number);
-        }
+    match row.try_get::<_, Option<Decimal>>(idx) {
+        Ok(value) => value.map(decimal_to_value).unwrap_or(Value::Null),
+        // NaN, the infinities and values beyond Decimal's 28 digits still have a text form
+        Err(_) => row
+            .try_get::<_, Option<NumericText>>(idx)
+            .ok()
+            .flatten()
+            .map(|numeric| Value::String(numeric.0))
+            .unwrap_or(Value::Null),
     }
+}
+
+/// A NUMERIC decoded straight from the wire format into its text representation
+#[derive(Debug, PartialEq)]
+struct NumericText(String);
+
+impl<'a> FromSql<'a> for NumericText {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> std::result::Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let read_u16 = |offset: usize| -> std::result::Result<u16, String> {
+            raw.get(offset..offset + 2)
+                .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+                .ok_or_else(|| "truncated numeric value".to_string())
+        };
+
+        let digit_count = read_u16(0)? as usize;
+        let weight = read_u16(2)? as i16 as i64;
+        let sign = read_u16(4)?;
+        let scale = read_u16(6)? as usize;
+        let digits = (0..digit_count)
+            .map(|i| read_u16(8 + i * 2))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let negative = match sign {
+            0x0000 => false,
+            0x4000 => true,
+            0xC000 => return Ok(NumericText("NaN".to_string())),
+            0xD000 => return Ok(NumericText("Infinity".to_string())),
+            0xF000 => return Ok(NumericText("-Infinity".to_string())),
+            other => return Err(format!("invalid numeric sign: {:#x}", other).into()),
+        };
 
-    if let Ok(Some(text)) = row.try_get::<_, Option<String>>(idx) {
-        if let Ok(number) = Number::from_str(&text) {
-            return Value::Number(number);
+        // Each digit is a base-10000 group; the first sits at 10000^weight
+        let group = |index: i64| -> u16 {
+            usize::try_from(index).ok().and_then(|index| digits.get(index)).copied().unwrap_or(0)
+        };
+        let mut text = String::new();
+        if negative {
+            text.push('-');
+        }
+        if weight < 0 {
+            text.push('0');
+        } else {
+            text.push_str(&group(0).to_string());
+            for index in 1..=weight {
+                text.push_str(&format!("{:04}", group(index)));
+            }
         }
-        return Value::String(text);
+        if scale > 0 {
+            let mut fraction = String::new();
+            for offset in 1..=scale.div_ceil(4) as i64 {
+                fraction.push_str(&format!("{:04}", group(weight + offset)));
+            }
+            text.push('.');
+            text.push_str(&fraction[..scale]);
+        }
+
+        Ok(NumericText(text))
     }
 
-    Value::Null
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+/// Serialize a decimal as a JSON string so its exact digits and scale survive the trip to JS
+fn decimal_to_value(value: Decimal) -> Value {
+    Value::String(value.to_string())
 }
 
 fn array_cell_to_value<T, F>(row: &tokio_postgres::Row, idx: usize, mapper: F) -> Value
@@ -758,7 +2964,10 @@ where
     Value::Null
 }
 
-fn convert_params(params: &[Value], expected_types: &[Type]) -> Result<Vec<ConvertedParam>> {
+pub(crate) fn convert_params(
+    params: &[Value],
+    expected_types: &[Type],
+) -> Result<Vec<ConvertedParam>> {
     if params.len() != expected_types.len() {
         return Err(RowFlowError::QueryError(format!(
             "Expected {} parameter(s) but received {}",
@@ -779,6 +2988,59 @@ fn convert_param(index: usize, value: &Value, ty: &Type) -> Result<ConvertedPara
         return Ok(convert_null_param(ty));
     }
 
+    // A JSON array binds to an array parameter, e.g. `WHERE id = ANY($1)` with `[1, 2, 3]`
+    if let Kind::Array(element_type) = ty.kind() {
+        return match value {
+            Value::Array(elements) if has_binary_encoding(element_type) => {
+                let converted = elements
+                    .iter()
+                    .map(|element| convert_param(index, element, element_type))
+                    .collect::<Result<Vec<_>>>()?;
+                // A binary array can't carry text elements, so one the server must parse
+                // sends the whole array as a literal
+                if converted.iter().any(|element| matches!(element, ConvertedParam::Text(_))) {
+                    Ok(ConvertedParam::Text(TextParam(Some(format!(
+                        "{{{}}}",
+                        format_array_elements(elements)
+                    )))))
+                } else {
+                    Ok(ConvertedParam::Array(Some(converted)))
+                }
+            }
+            // Elements without a binary encoding here go as one literal for the server to parse
+            Value::Array(elements) => Ok(ConvertedParam::Text(TextParam(Some(format!(
+                "{{{}}}",
+                format_array_elements(elements)
+            ))))),
+            // An array literal such as `{a,b}` is left for the server to parse
+            Value::String(text) if text.trim_start().starts_with('{') => {
+                Ok(ConvertedParam::Text(TextParam(Some(text.clone()))))
+            }
+            // Otherwise a string holds a JSON array or comma-separated elements
+            Value::String(text) => {
+                let elements = match serde_json::from_str::<Value>(text) {
+                    Ok(Value::Array(elements)) => elements,
+                    _ => text
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|element| !element.is_empty())
+                        .map(|element| Value::String(element.to_string()))
+                        .collect(),
+                };
+                convert_param(index, &Value::Array(elements), ty)
+            }
+            _ => Err(param_type_error(index, &format!("{} (a JSON array)", ty.name()), value)),
+        };
+    }
+
+    // Text the local parsers don't know, such as `2024-03-15 14:30:00+00` or a numeric beyond
+    // `Decimal`'s range, is left for the server to cast
+    let server_cast = |expected: &str| match value {
+        Value::String(text) => Ok(ConvertedParam::Text(TextParam(Some(text.clone())))),
+        Value::Number(num) => Ok(ConvertedParam::Text(TextParam(Some(num.to_string())))),
+        _ => Err(param_type_error(index, expected, value)),
+    };
+
     match *ty {
         Type::BOOL => match value {
             Value::Bool(b) => Ok(ConvertedParam::Bool(Some(*b))),
@@ -809,21 +3071,27 @@ fn convert_param(index: usize, value: &Value, ty: &Type) -> Result<ConvertedPara
             Some(v) => Ok(ConvertedParam::F32(Some(v as f32))),
             None => Err(param_type_error(index, "REAL", value)),
         },
-        Type::FLOAT8 | Type::NUMERIC => match value_to_f64(value) {
+        Type::FLOAT8 => match value_to_f64(value) {
             Some(v) => Ok(ConvertedParam::F64(Some(v))),
             None => Err(param_type_error(index, "DOUBLE PRECISION", value)),
         },
+        Type::NUMERIC => match value_to_decimal(value) {
+            Some(v) => Ok(ConvertedParam::Decimal(Some(v))),
+            None => server_cast("NUMERIC"),
+        },
         Type::JSON | Type::JSONB => Ok(ConvertedParam::Json(Some(Json(value.clone())))),
         Type::TIMESTAMP => match value {
-            Value::String(s) => parse_naive_datetime(s)
-                .map(|ts| ConvertedParam::Timestamp(Some(ts)))
-                .ok_or_else(|| param_type_error(index, "TIMESTAMP", value)),
+            Value::String(s) => match parse_naive_datetime(s) {
+                Some(ts) => Ok(ConvertedParam::Timestamp(Some(ts))),
+                None => server_cast("TIMESTAMP"),
+            },
             _ => Err(param_type_error(index, "TIMESTAMP", value)),
         },
         Type::TIMESTAMPTZ => match value {
-            Value::String(s) => parse_datetime_with_tz(s)
-                .map(|ts| ConvertedParam::Timestamptz(Some(ts)))
-                .ok_or_else(|| param_type_error(index, "TIMESTAMP WITH TIME ZONE", value)),
+            Value::String(s) => match parse_datetime_with_tz(s) {
+                Some(ts) => Ok(ConvertedParam::Timestamptz(Some(ts))),
+                None => server_cast("TIMESTAMP WITH TIME ZONE"),
+            },
             _ => Err(param_type_error(index, "TIMESTAMP WITH TIME ZONE", value)),
         },
         Type::DATE => match value {
@@ -835,21 +3103,23 @@ fn convert_param(index: usize, value: &Value, ty: &Type) -> Result<ConvertedPara
                 } else if let Some(dt) = parse_naive_datetime(s) {
                     Ok(ConvertedParam::Date(Some(dt.date())))
                 } else {
-                    Err(param_type_error(index, "DATE", value))
+                    server_cast("DATE")
                 }
             }
             _ => Err(param_type_error(index, "DATE", value)),
         },
         Type::TIME => match value {
-            Value::String(s) => parse_naive_time(s)
-                .map(|t| ConvertedParam::Time(Some(t)))
-                .ok_or_else(|| param_type_error(index, "TIME", value)),
+            Value::String(s) => match parse_naive_time(s) {
+                Some(t) => Ok(ConvertedParam::Time(Some(t))),
+                None => server_cast("TIME"),
+            },
             _ => Err(param_type_error(index, "TIME", value)),
         },
         Type::TIMETZ => match value {
-            Value::String(s) => parse_time_with_tz(s)
-                .map(|t| ConvertedParam::TimeTz(Some(t)))
-                .ok_or_else(|| param_type_error(index, "TIME WITH TIME ZONE", value)),
+            Value::String(s) => match parse_time_with_tz(s) {
+                Some(t) => Ok(ConvertedParam::TimeTz(Some(t))),
+                None => server_cast("TIME WITH TIME ZONE"),
+            },
             _ => Err(param_type_error(index, "TIME WITH TIME ZONE", value)),
         },
         Type::UUID => match value {
@@ -861,18 +3131,51 @@ fn convert_param(index: usize, value: &Value, ty: &Type) -> Result<ConvertedPara
         Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
             Ok(ConvertedParam::String(Some(value_to_string(value))))
         }
-        _ => Ok(ConvertedParam::String(Some(value_to_string(value)))),
+        // Enums, domains, `inet`, `interval` and the like are parsed by the server
+        _ => Ok(ConvertedParam::Text(TextParam(Some(value_to_string(value))))),
     }
 }
 
+/// Whether `convert_param` encodes values of a type itself rather than sending them as text
+fn has_binary_encoding(ty: &Type) -> bool {
+    matches!(
+        *ty,
+        Type::BOOL
+            | Type::INT2
+            | Type::INT4
+            | Type::INT8
+            | Type::FLOAT4
+            | Type::FLOAT8
+            | Type::NUMERIC
+            | Type::JSON
+            | Type::JSONB
+            | Type::TIMESTAMP
+            | Type::TIMESTAMPTZ
+            | Type::DATE
+            | Type::TIME
+            | Type::TIMETZ
+            | Type::UUID
+            | Type::TEXT
+            | Type::VARCHAR
+            | Type::BPCHAR
+            | Type::NAME
+            | Type::UNKNOWN
+    )
+}
+
 fn convert_null_param(ty: &Type) -> ConvertedParam {
+    if let Kind::Array(_) = ty.kind() {
+        return ConvertedParam::Array(None);
+    }
+
     match *ty {
         Type::BOOL => ConvertedParam::Bool(None),
         Type::INT2 => ConvertedParam::I16(None),
         Type::INT4 => ConvertedParam::I32(None),
         Type::INT8 => ConvertedParam::I64(None),
         Type::FLOAT4 => ConvertedParam::F32(None),
-        Type::FLOAT8 | Type::NUMERIC => ConvertedParam::F64(None),
+        Type::FLOAT8 => ConvertedParam::F64(None),
+        Type::NUMERIC => ConvertedParam::Decimal(None),
         Type::JSON | Type::JSONB => ConvertedParam::Json(None),
         Type::TIMESTAMP => ConvertedParam::Timestamp(None),
         Type::TIMESTAMPTZ => ConvertedParam::Timestamptz(None),
@@ -880,17 +3183,22 @@ fn convert_null_param(ty: &Type) -> ConvertedParam {
         Type::TIME => ConvertedParam::Time(None),
         Type::TIMETZ => ConvertedParam::TimeTz(None),
         Type::UUID => ConvertedParam::Uuid(None),
-        _ => ConvertedParam::String(None),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+            ConvertedParam::String(None)
+        }
+        _ => ConvertedParam::Text(TextParam(None)),
     }
 }
 
-enum ConvertedParam {
+#[derive(Debug)]
+pub(crate) enum ConvertedParam {
     Bool(Option<bool>),
     I16(Option<i16>),
     I32(Option<i32>),
     I64(Option<i64>),
     F32(Option<f32>),
     F64(Option<f64>),
+    Decimal(Option<Decimal>),
     String(Option<String>),
     Json(Option<Json<Value>>),
     Timestamp(Option<chrono::NaiveDateTime>),
@@ -899,10 +3207,42 @@ enum ConvertedParam {
     Time(Option<chrono::NaiveTime>),
     TimeTz(Option<chrono::DateTime<chrono::FixedOffset>>),
     Uuid(Option<Uuid>),
+    Array(Option<Vec<ConvertedParam>>),
+    Text(TextParam),
+}
+
+/// A value sent in text format, which the server parses with its type's input function
+#[derive(Debug)]
+pub(crate) struct TextParam(Option<String>);
+
+impl ToSql for TextParam {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match &self.0 {
+            Some(text) => {
+                out.extend_from_slice(text.as_bytes());
+                Ok(IsNull::No)
+            }
+            None => Ok(IsNull::Yes),
+        }
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, _ty: &Type) -> Format {
+        Format::Text
+    }
+
+    to_sql_checked!();
 }
 
 impl ConvertedParam {
-    fn as_sql(&self) -> &(dyn ToSql + Sync) {
+    pub(crate) fn as_sql(&self) -> &(dyn ToSql + Sync) {
         match self {
             ConvertedParam::Bool(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::I16(v) => v as &(dyn ToSql + Sync),
@@ -910,6 +3250,7 @@ impl ConvertedParam {
             ConvertedParam::I64(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::F32(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::F64(v) => v as &(dyn ToSql + Sync),
+            ConvertedParam::Decimal(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::String(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::Json(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::Timestamp(v) => v as &(dyn ToSql + Sync),
@@ -918,10 +3259,34 @@ impl ConvertedParam {
             ConvertedParam::Time(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::TimeTz(v) => v as &(dyn ToSql + Sync),
             ConvertedParam::Uuid(v) => v as &(dyn ToSql + Sync),
+            ConvertedParam::Array(v) => v as &(dyn ToSql + Sync),
+            ConvertedParam::Text(v) => v as &(dyn ToSql + Sync),
         }
     }
 }
 
+/// Lets array elements be encoded as a `Vec<ConvertedParam>`; each element was converted for
+/// the array's element type, which its own `ToSql` impl checks
+impl ToSql for ConvertedParam {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        self.as_sql().to_sql_checked(ty, out)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, ty: &Type) -> Format {
+        self.as_sql().encode_format(ty)
+    }
+
+    to_sql_checked!();
+}
+
 fn param_type_error(index: usize, expected: &str, actual: &Value) -> RowFlowError {
     RowFlowError::QueryError(format!(
         "Parameter ${} expected {} but received {:?}",
@@ -951,6 +3316,21 @@ fn value_to_f64(value: &Value) -> Option<f64> {
     }
 }
 
+/// Parse a JSON value as an exact decimal; pass strings to avoid f64 rounding of large values
+fn value_to_decimal(value: &Value) -> Option<Decimal> {
+    let parse = |text: &str| {
+        let text = text.trim();
+        Decimal::from_str_exact(text).or_else(|_| Decimal::from_scientific(text)).ok()
+    };
+
+    match value {
+        Value::Number(num) => parse(&num.to_string()),
+        Value::String(s) => parse(s),
+        Value::Bool(b) => Some(if *b { Decimal::ONE } else { Decimal::ZERO }),
+        _ => None,
+    }
+}
+
 fn value_to_string(value: &Value) -> String {
     match value {
         Value::String(s) => s.clone(),
@@ -1133,6 +3513,12 @@ pub async fn list_mcp_profiles() -> Result<Vec<ConnectionProfile>> {
                 lock_timeout: None,
                 idle_timeout: None,
                 read_only: false,
+                search_path: None,
+                session_timezone: None,
+                recycling_method: Default::default(),
+                channel_binding: Default::default(),
+                ssl_negotiation: Default::default(),
+                slow_query_threshold: None,
             });
         }
     }
@@ -1140,3 +3526,277 @@ pub async fn list_mcp_profiles() -> Result<Vec<ConnectionProfile>> {
     log::info!("Found {} MCP profiles", profiles.len());
     Ok(profiles)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_large_numeric_string_param_exactly() {
+        let value = Value::String("9999999999999999.99".to_string());
+        match convert_param(0, &value, &Type::NUMERIC).expect("numeric param") {
+            ConvertedParam::Decimal(Some(decimal)) => {
+                assert_eq!(decimal.to_string(), "9999999999999999.99");
+            }
+            _ => panic!("expected a decimal parameter"),
+        }
+    }
+
+    #[test]
+    fn classifies_connect_failures() {
+        use std::io::ErrorKind;
+        use ConnectionFailureCategory::*;
+
+        let cases = [
+            (Some(&SqlState::INVALID_PASSWORD), None, false, "", Authentication),
+            (Some(&SqlState::INVALID_AUTHORIZATION_SPECIFICATION), None, false, "", Authentication),
+            (Some(&SqlState::INVALID_CATALOG_NAME), None, false, "", DatabaseNotFound),
+            (Some(&SqlState::TOO_MANY_CONNECTIONS), None, false, "", TooManyConnections),
+            (Some(&SqlState::CANNOT_CONNECT_NOW), None, false, "", Server),
+            (
+                Some(&SqlState::INVALID_PASSWORD),
+                Some(ErrorKind::TimedOut),
+                true,
+                "tls",
+                Authentication,
+            ),
+            (None, Some(ErrorKind::ConnectionRefused), false, "", ConnectionRefused),
+            (None, Some(ErrorKind::TimedOut), true, "", Timeout),
+            (None, Some(ErrorKind::ConnectionReset), true, "", Tls),
+            (None, None, false, "error performing TLS handshake: server does not support TLS", Tls),
+            (None, None, false, "invalid peer certificate", Tls),
+            (None, None, false, "timeout waiting for server", Timeout),
+            (None, Some(ErrorKind::ConnectionReset), false, "connection closed", Unknown),
+        ];
+
+        for (code, io_kind, tls_source, message, expected) in cases {
+            assert_eq!(
+                classify_connect_failure(code, io_kind, tls_source, message),
+                expected,
+                "{:?} {:?} {} {}",
+                code,
+                io_kind,
+                tls_source,
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn describes_connection_failures_with_a_hint_per_category() {
+        use ConnectionFailureCategory::*;
+
+        for category in [
+            Dns,
+            ConnectionRefused,
+            Timeout,
+            Tls,
+            Authentication,
+            DatabaseNotFound,
+            TooManyConnections,
+            Server,
+            Unknown,
+        ] {
+            let diagnosis = connection_failure(category, None, "failed".to_string());
+            assert!(!diagnosis.ok);
+            assert_eq!(diagnosis.category, Some(category));
+            assert_eq!(diagnosis.sql_state, None);
+            assert_eq!(diagnosis.error.as_deref(), Some("failed"));
+            assert!(diagnosis.hint.is_some_and(|hint| !hint.is_empty()), "{:?}", category);
+        }
+
+        let wrong_password =
+            connection_failure(Authentication, Some(&SqlState::INVALID_PASSWORD), String::new());
+        let rejected_role = connection_failure(
+            Authentication,
+            Some(&SqlState::INVALID_AUTHORIZATION_SPECIFICATION),
+            String::new(),
+        );
+        assert_eq!(wrong_password.sql_state.as_deref(), Some("28P01"));
+        assert_eq!(rejected_role.sql_state.as_deref(), Some("28000"));
+        assert!(wrong_password.hint.unwrap().contains("password"));
+        assert!(rejected_role.hint.unwrap().contains("pg_hba.conf"));
+    }
+
+    #[test]
+    fn detects_statements_that_deallocate_prepared_statements() {
+        assert!(deallocates_statements("DISCARD ALL"));
+        assert!(deallocates_statements("  deallocate prepare my_stmt"));
+        assert!(deallocates_statements("discard plans;"));
+        assert!(!deallocates_statements("SELECT 'DISCARD ALL'"));
+        assert!(!deallocates_statements(""));
+    }
+
+    #[test]
+    fn serializes_decimal_with_original_scale() {
+        let decimal = Decimal::from_str("9999999999999999.99").expect("decimal");
+        assert_eq!(decimal_to_value(decimal), Value::String("9999999999999999.99".to_string()));
+
+        let decimal = Decimal::from_str("12.50").expect("decimal");
+        assert_eq!(decimal_to_value(decimal), Value::String("12.50".to_string()));
+    }
+
+    #[test]
+    fn parses_decimal_from_numbers_and_scientific_notation() {
+        assert_eq!(value_to_decimal(&serde_json::json!(42)), Some(Decimal::from(42)));
+        assert_eq!(
+            value_to_decimal(&Value::String("1.5e3".to_string())),
+            Some(Decimal::from(1500))
+        );
+        assert_eq!(value_to_decimal(&Value::String("not a number".to_string())), None);
+    }
+
+    #[test]
+    fn decodes_numerics_outside_decimal_range_as_text() {
+        let decode = |words: &[u16]| {
+            let raw: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+            NumericText::from_sql(&Type::NUMERIC, &raw).expect("numeric").0
+        };
+
+        assert_eq!(decode(&[0, 0, 0xC000, 0]), "NaN");
+        assert_eq!(decode(&[0, 0, 0xD000, 0]), "Infinity");
+        assert_eq!(decode(&[0, 0, 0xF000, 0]), "-Infinity");
+        // 123456789.0500 with digit groups 1, 2345, 6789, 0500
+        assert_eq!(decode(&[4, 2, 0x4000, 4, 1, 2345, 6789, 500]), "-123456789.0500");
+        // 0.00012 is the groups 1 and 2000 starting at 10000^-1
+        assert_eq!(decode(&[2, (-1i16) as u16, 0, 5, 1, 2000]), "0.00012");
+        // 10^40 is a single group far beyond Decimal's range
+        assert_eq!(decode(&[1, 10, 0, 0, 1]), format!("1{}", "0".repeat(40)));
+        assert_eq!(decode(&[0, 0, 0, 2]), "0.00");
+    }
+
+    #[test]
+    fn binds_json_arrays_to_array_parameters() {
+        let converted =
+            convert_params(&[json!([1, "2", null])], &[Type::INT8_ARRAY]).expect("converts");
+
+        match converted.as_slice() {
+            [ConvertedParam::Array(Some(elements))] => assert!(matches!(
+                elements.as_slice(),
+                [
+                    ConvertedParam::I64(Some(1)),
+                    ConvertedParam::I64(Some(2)),
+                    ConvertedParam::I64(None)
+                ]
+            )),
+            other => panic!("unexpected conversion: {:?}", other),
+        }
+
+        assert!(matches!(
+            convert_params(&[json!(null)], &[Type::TEXT_ARRAY]).expect("converts").as_slice(),
+            [ConvertedParam::Array(None)]
+        ));
+        assert!(convert_params(&[json!([1, "x"])], &[Type::INT4_ARRAY]).is_err());
+        assert!(convert_params(&[json!(1)], &[Type::INT4_ARRAY]).is_err());
+    }
+
+    #[test]
+    fn leaves_values_the_local_parsers_reject_for_the_server_to_cast() {
+        let huge = format!("1{}", "0".repeat(40));
+        let cases = [
+            (json!("2024-03-15 14:30:00"), Type::TIMESTAMPTZ),
+            (json!("2024-03-15 14:30:00+00"), Type::TIMESTAMPTZ),
+            (json!("2024-03-15"), Type::TIMESTAMP),
+            (json!("2024-03-15T14:30"), Type::TIMESTAMP),
+            (json!(huge), Type::NUMERIC),
+        ];
+        for (value, ty) in cases {
+            let text = value.as_str().unwrap().to_string();
+            match convert_param(0, &value, &ty).expect("converts") {
+                ConvertedParam::Text(TextParam(Some(sent))) => assert_eq!(sent, text),
+                other => panic!("{} as {}: {:?}", text, ty, other),
+            }
+        }
+
+        // One element the server must cast sends the whole array as a literal
+        assert!(matches!(
+            convert_param(0, &json!(["2024-03-15T14:30:00Z", "2024-03-15"]), &Type::TIMESTAMPTZ_ARRAY),
+            Ok(ConvertedParam::Text(TextParam(Some(literal))))
+                if literal == r#"{"2024-03-15T14:30:00Z","2024-03-15"}"#
+        ));
+        assert!(convert_param(0, &json!(true), &Type::TIMESTAMP).is_err());
+    }
+
+    #[test]
+    fn sends_unencodable_values_and_array_literals_as_text() {
+        let converted = convert_params(
+            &[json!("O'Brien"), json!("192.168.0.1"), json!("{a,b}"), json!("1, 2")],
+            &[Type::TEXT, Type::INET, Type::TEXT_ARRAY, Type::INT4_ARRAY],
+        )
+        .expect("converts");
+
+        assert!(matches!(&converted[0], ConvertedParam::String(Some(name)) if name == "O'Brien"));
+        assert!(matches!(
+            &converted[1],
+            ConvertedParam::Text(TextParam(Some(address))) if address == "192.168.0.1"
+        ));
+        assert!(matches!(
+            &converted[2],
+            ConvertedParam::Text(TextParam(Some(literal))) if literal == "{a,b}"
+        ));
+        assert!(matches!(
+            &converted[3],
+            ConvertedParam::Array(Some(elements)) if matches!(
+                elements.as_slice(),
+                [ConvertedParam::I32(Some(1)), ConvertedParam::I32(Some(2))]
+            )
+        ));
+    }
+
+    /// Needs a server, e.g. `ROWFLOW_TEST_DATABASE_URL=postgres://postgres@127.0.0.1/postgres
+    /// cargo test --lib cascade_preview -- --ignored`.
+    #[tokio::test]
+    #[ignore]
+    async fn cascade_preview_follows_self_references_to_every_level() {
+        let url = std::env::var("ROWFLOW_TEST_DATABASE_URL")
+            .expect("ROWFLOW_TEST_DATABASE_URL is not set");
+        let config: tokio_postgres::Config = url.parse().expect("invalid database URL");
+        let manager = deadpool_postgres::Manager::from_config(
+            config,
+            NoTls,
+            deadpool_postgres::ManagerConfig::default(),
+        );
+        let pool = deadpool_postgres::Pool::builder(manager).max_size(1).build().unwrap();
+        let client = pool.get().await.unwrap();
+        client
+            .batch_execute(
+                "DROP SCHEMA IF EXISTS rowflow_cascade_preview CASCADE;
+                 CREATE SCHEMA rowflow_cascade_preview;
+                 CREATE TABLE rowflow_cascade_preview.employees (
+                     id int PRIMARY KEY,
+                     manager_id int REFERENCES rowflow_cascade_preview.employees (id)
+                         ON DELETE CASCADE
+                 );
+                 INSERT INTO rowflow_cascade_preview.employees VALUES
+                     (1, NULL), (2, 1), (3, 2), (4, 2), (5, 3), (6, NULL);",
+            )
+            .await
+            .unwrap();
+
+        let key = BTreeMap::from([("id".to_string(), json!(1))]);
+        let (predicate, params) = key_equality_predicate(&key).unwrap();
+        let selector =
+            format!("SELECT t.* FROM rowflow_cascade_preview.employees AS t WHERE {}", predicate);
+        let root = cascade_delete_node(
+            &client,
+            &params,
+            "rowflow_cascade_preview".to_string(),
+            "employees".to_string(),
+            selector,
+            None,
+            0,
+            Vec::new(),
+        )
+        .await;
+        client.batch_execute("DROP SCHEMA rowflow_cascade_preview CASCADE").await.unwrap();
+
+        // The manager, their report, two below that and one more at the bottom
+        let root = root.unwrap();
+        assert_eq!(root.row_count, 1);
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].row_count, 4);
+        assert_eq!(count_cascaded_rows(&root), 5);
+        assert!(!has_truncated_branch(&root));
+    }
+}
@@ -0,0 +1,1550 @@
+use super::s3::{
+    build_full_s3_key, content_encoding, parse_content_encoding, server_side_encryption_options,
+    StreamDecoder, StreamEncoder,
+};
+use super::schema::{
+    get_constraints, get_foreign_keys, get_indexes, get_primary_keys, get_table_columns,
+    list_tables, qualified_table_name, quote_identifier, validate_identifier,
+};
+use crate::copy_progress::CopyProgressReporter;
+use crate::error::{Result, RowFlowError};
+use crate::state::AppState;
+use crate::types::{
+    Compression, ExportFileFormat, QueryResult, QueryS3ExportRequest, QueryS3ExportResult,
+    ResultFormat, S3TableImportRequest, S3TableImportResult, SchemaDdlExportRequest,
+    SchemaExportResult, SchemaStructure, Table, TableChecksum, TableStructure,
+    TableTransferRequest, TableTransferResult,
+};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::Instant;
+use tauri::State;
+use tokio_postgres::{IsolationLevel, Transaction};
+
+/// Tables introspected at once by `export_schema_json`, each using several pooled clients
+const CONCURRENT_TABLE_INTROSPECTIONS: usize = 4;
+
+/// Rows fetched from the cursor at a time by `table_checksum`
+const CHECKSUM_FETCH_SIZE: usize = 10_000;
+
+/// Size of each part `export_query_to_s3` uploads, above the 5 MiB S3 minimum
+const S3_UPLOAD_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// SQL condition excluding objects installed by an extension
+fn not_from_extension(catalog: &str, oid: &str) -> String {
+    format!(
+        "NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend dep WHERE dep.classid = '{}'::regclass \
+         AND dep.objid = {} AND dep.deptype = 'e')",
+        catalog, oid
+    )
+}
+
+/// A column of a table being exported
+#[derive(Clone)]
+struct ExportColumn {
+    name: String,
+    data_type: String,
+    not_null: bool,
+    default: Option<String>,
+    identity: String,
+    generated: String,
+}
+
+/// A constraint of a table being exported
+struct ExportConstraint {
+    name: String,
+    constraint_type: String,
+    definition: String,
+    referenced_schema: Option<String>,
+    referenced_table: Option<String>,
+}
+
+/// Order tables so each comes after the tables its foreign keys reference
+///
+/// Tables caught in a reference cycle follow the rest in name order; their foreign keys to
+/// tables created later have to be added afterwards. Any other named objects and their
+/// dependencies can be ordered the same way.
+fn order_tables_by_references(tables: &[String], references: &[(String, String)]) -> Vec<String> {
+    let known: HashSet<&str> = tables.iter().map(String::as_str).collect();
+    let mut depends_on: BTreeMap<&str, BTreeSet<&str>> =
+        tables.iter().map(|table| (table.as_str(), BTreeSet::new())).collect();
+    let mut referenced_by: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+
+    for (table, referenced) in references {
+        if table == referenced || !known.contains(table.as_str()) {
+            continue;
+        }
+        if !known.contains(referenced.as_str()) {
+            continue;
+        }
+        if depends_on.entry(table.as_str()).or_default().insert(referenced.as_str()) {
+            referenced_by.entry(referenced.as_str()).or_default().push(table.as_str());
+        }
+    }
+
+    let mut ready: BTreeSet<&str> =
+        depends_on.iter().filter(|(_, deps)| deps.is_empty()).map(|(table, _)| *table).collect();
+    let mut ordered = Vec::with_capacity(tables.len());
+    let mut placed = HashSet::new();
+
+    while let Some(table) = ready.pop_first() {
+        ordered.push(table.to_string());
+        placed.insert(table);
+        for dependent in referenced_by.get(table).into_iter().flatten() {
+            if let Some(deps) = depends_on.get_mut(dependent) {
+                deps.remove(table);
+                if deps.is_empty() && !placed.contains(dependent) {
+                    ready.insert(*dependent);
+                }
+            }
+        }
+    }
+
+    let remaining: BTreeSet<&str> =
+        tables.iter().map(String::as_str).filter(|table| !placed.contains(table)).collect();
+    ordered.extend(remaining.into_iter().map(str::to_string));
+    ordered
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn build_column_ddl(column: &ExportColumn) -> String {
+    let mut definition = format!("{} {}", quote_identifier(&column.name), column.data_type);
+
+    match (column.generated.as_str(), &column.default) {
+        ("s", Some(expression)) => {
+            definition.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expression))
+        }
+        (_, Some(expression)) => definition.push_str(&format!(" DEFAULT {}", expression)),
+        _ => {}
+    }
+
+    match column.identity.as_str() {
+        "a" => definition.push_str(" GENERATED ALWAYS AS IDENTITY"),
+        "d" => definition.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+        _ => {}
+    }
+
+    if column.not_null {
+        definition.push_str(" NOT NULL");
+    }
+
+    definition
+}
+
+/// Columns and constraints of each table, keyed by table name
+type TableDefinitions =
+    (BTreeMap<String, Vec<ExportColumn>>, BTreeMap<String, Vec<ExportConstraint>>);
+
+/// Columns and local constraints of the ordinary and partitioned tables in a schema, keyed by
+/// table name; `table` narrows it to a single table
+async fn fetch_table_definitions(
+    transaction: &Transaction<'_>,
+    schema: &str,
+    table: Option<&str>,
+) -> Result<TableDefinitions> {
+    let table_filter = format!(
+        "n.nspname = $1 AND ($2::text IS NULL OR c.relname = $2) AND c.relkind IN ('r', 'p') \
+         AND NOT c.relispartition AND {}",
+        not_from_extension("pg_catalog.pg_class", "c.oid")
+    );
+    let column_query = format!(
+        r#"
+        SELECT
+            c.relname::text,
+            a.attname::text,
+            pg_catalog.format_type(a.atttypid, a.atttypmod),
+            a.attnotnull,
+            pg_catalog.pg_get_expr(d.adbin, d.adrelid),
+            a.attidentity::text,
+            a.attgenerated::text
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_catalog.pg_attribute a
+            ON a.attrelid = c.oid
+            AND a.attnum > 0
+            AND NOT a.attisdropped
+        LEFT JOIN pg_catalog.pg_attrdef d ON d.adrelid = c.oid AND d.adnum = a.attnum
+        WHERE {}
+        ORDER BY c.relname, a.attnum
+    "#,
+        table_filter
+    );
+    let mut table_columns: BTreeMap<String, Vec<ExportColumn>> = BTreeMap::new();
+    for row in transaction.query(column_query.as_str(), &[&schema, &table]).await? {
+        table_columns.entry(row.get(0)).or_default().push(ExportColumn {
+            name: row.get(1),
+            data_type: row.get(2),
+            not_null: row.get(3),
+            default: row.get(4),
+            identity: row.get(5),
+            generated: row.get(6),
+        });
+    }
+
+    let constraint_query = format!(
+        r#"
+        SELECT
+            c.relname::text,
+            con.conname::text,
+            con.contype::text,
+            pg_catalog.pg_get_constraintdef(con.oid, true),
+            fn.nspname::text,
+            fc.relname::text
+        FROM pg_catalog.pg_constraint con
+        JOIN pg_catalog.pg_class c ON c.oid = con.conrelid
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        LEFT JOIN pg_catalog.pg_class fc ON fc.oid = con.confrelid
+        LEFT JOIN pg_catalog.pg_namespace fn ON fn.oid = fc.relnamespace
+        WHERE {}
+            AND con.contype IN ('p', 'u', 'c', 'x', 'f')
+            AND con.conislocal
+        ORDER BY
+            c.relname,
+            CASE con.contype WHEN 'p' THEN 0 WHEN 'u' THEN 1 WHEN 'c' THEN 2 WHEN 'x' THEN 3 ELSE 4 END,
+            con.conname
+    "#,
+        table_filter
+    );
+    let mut table_constraints: BTreeMap<String, Vec<ExportConstraint>> = BTreeMap::new();
+    for row in transaction.query(constraint_query.as_str(), &[&schema, &table]).await? {
+        table_constraints.entry(row.get(0)).or_default().push(ExportConstraint {
+            name: row.get(1),
+            constraint_type: row.get(2),
+            definition: row.get(3),
+            referenced_schema: row.get(4),
+            referenced_table: row.get(5),
+        });
+    }
+
+    Ok((table_columns, table_constraints))
+}
+
+/// Export the DDL of a schema to a `.sql` file, in an order that can be replayed
+///
+/// Covers enum, domain and composite types, sequences, tables with their constraints and
+/// indexes, and optionally functions and views. Partitions of partitioned tables are not
+/// included.
+#[tauri::command]
+pub async fn export_schema_ddl(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: SchemaDdlExportRequest,
+) -> Result<SchemaExportResult> {
+    log::info!(
+        "Exporting DDL for schema: {} to {} on connection: {}",
+        request.schema,
+        request.path,
+        connection_id
+    );
+
+    validate_identifier(&request.schema, "Schema")?;
+    let schema = request.schema.as_str();
+
+    let mut client = state.get_client(&connection_id).await?;
+    let transaction = client
+        .build_transaction()
+        .isolation_level(IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await?;
+
+    // With only pg_catalog on the search path, every generated definition is schema-qualified
+    transaction.batch_execute("SET LOCAL search_path = pg_catalog").await?;
+
+    let mut statements = vec![format!("CREATE SCHEMA IF NOT EXISTS {}", quote_identifier(schema))];
+
+    // Enum types
+    let enum_query = format!(
+        r#"
+        SELECT t.typname::text, ARRAY_AGG(e.enumlabel::text ORDER BY e.enumsortorder)
+        FROM pg_catalog.pg_type t
+        JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+        JOIN pg_catalog.pg_enum e ON e.enumtypid = t.oid
+        WHERE n.nspname = $1
+            AND {}
+        GROUP BY t.typname
+        ORDER BY t.typname
+    "#,
+        not_from_extension("pg_catalog.pg_type", "t.oid")
+    );
+    for row in transaction.query(enum_query.as_str(), &[&schema]).await? {
+        let name: String = row.get(0);
+        let labels: Vec<String> = row.get(1);
+        statements.push(format!(
+            "CREATE TYPE {} AS ENUM ({})",
+            qualified_table_name(schema, &name)?,
+            labels.iter().map(|label| quote_literal(label)).collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    // Domain types
+    let domain_query = format!(
+        r#"
+        SELECT
+            t.typname::text,
+            pg_catalog.format_type(t.typbasetype, t.typtypmod),
+            t.typnotnull,
+            t.typdefault,
+            ARRAY(
+                SELECT 'CONSTRAINT ' || quote_ident(c.conname) || ' '
+                    || pg_catalog.pg_get_constraintdef(c.oid, true)
+                FROM pg_catalog.pg_constraint c
+                WHERE c.contypid = t.oid
+                    AND c.contype = 'c'
+                ORDER BY c.conname
+            )
+        FROM pg_catalog.pg_type t
+        JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+        WHERE n.nspname = $1
+            AND t.typtype = 'd'
+            AND {}
+        ORDER BY t.oid
+    "#,
+        not_from_extension("pg_catalog.pg_type", "t.oid")
+    );
+    for row in transaction.query(domain_query.as_str(), &[&schema]).await? {
+        let name: String = row.get(0);
+        let base_type: String = row.get(1);
+        let not_null: bool = row.get(2);
+        let default: Option<String> = row.get(3);
+        let checks: Vec<String> = row.get(4);
+
+        let mut statement =
+            format!("CREATE DOMAIN {} AS {}", qualified_table_name(schema, &name)?, base_type);
+        if let Some(default) = default {
+            statement.push_str(&format!(" DEFAULT {}", default));
+        }
+        if not_null {
+            statement.push_str(" NOT NULL");
+        }
+        for check in checks {
+            statement.push_str(&format!(" {}", check));
+        }
+        statements.push(statement);
+    }
+
+    // Standalone composite types, in creation order so a type built on another follows it
+    let composite_query = format!(
+        r#"
+        SELECT
+            t.typname::text,
+            ARRAY(
+                SELECT quote_ident(a.attname) || ' '
+                    || pg_catalog.format_type(a.atttypid, a.atttypmod)
+                FROM pg_catalog.pg_attribute a
+                WHERE a.attrelid = t.typrelid
+                    AND a.attnum > 0
+                    AND NOT a.attisdropped
+                ORDER BY a.attnum
+            )
+        FROM pg_catalog.pg_type t
+        JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+        JOIN pg_catalog.pg_class c ON c.oid = t.typrelid
+        WHERE n.nspname = $1
+            AND t.typtype = 'c'
+            AND c.relkind = 'c'
+            AND {}
+        ORDER BY t.oid
+    "#,
+        not_from_extension("pg_catalog.pg_type", "t.oid")
+    );
+    for row in transaction.query(composite_query.as_str(), &[&schema]).await? {
+        let name: String = row.get(0);
+        let attributes: Vec<String> = row.get(1);
+        statements.push(format!(
+            "CREATE TYPE {} AS ({})",
+            qualified_table_name(schema, &name)?,
+            attributes.join(", ")
+        ));
+    }
+
+    // Sequences not backing identity columns, with the column that owns them if any
+    let sequence_query = r#"
+        SELECT
+            c.relname::text,
+            pg_catalog.format_type(s.seqtypid, NULL),
+            s.seqstart,
+            s.seqincrement,
+            s.seqmin,
+            s.seqmax,
+            s.seqcache,
+            s.seqcycle,
+            oc.relname::text AS owner_table,
+            oa.attname::text AS owner_column
+        FROM pg_catalog.pg_sequence s
+        JOIN pg_catalog.pg_class c ON c.oid = s.seqrelid
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        LEFT JOIN pg_catalog.pg_depend od
+            ON od.classid = 'pg_catalog.pg_class'::regclass
+            AND od.objid = c.oid
+            AND od.deptype = 'a'
+        LEFT JOIN pg_catalog.pg_class oc
+            ON oc.oid = od.refobjid
+            AND oc.relnamespace = n.oid
+        LEFT JOIN pg_catalog.pg_attribute oa
+            ON oa.attrelid = oc.oid
+            AND oa.attnum = od.refobjsubid
+        WHERE n.nspname = $1
+            AND NOT EXISTS (
+                SELECT 1
+                FROM pg_catalog.pg_depend d
+                WHERE d.classid = 'pg_catalog.pg_class'::regclass
+                    AND d.objid = c.oid
+                    AND d.deptype IN ('i', 'e')
+            )
+        ORDER BY c.relname
+    "#;
+    let mut sequence_owners = Vec::new();
+    for row in transaction.query(sequence_query, &[&schema]).await? {
+        let name: String = row.get(0);
+        let data_type: String = row.get(1);
+        let start: i64 = row.get(2);
+        let increment: i64 = row.get(3);
+        let min: i64 = row.get(4);
+        let max: i64 = row.get(5);
+        let cache: i64 = row.get(6);
+        let cycle: bool = row.get(7);
+        let owner_table: Option<String> = row.get(8);
+        let owner_column: Option<String> = row.get(9);
+
+        let sequence = qualified_table_name(schema, &name)?;
+        statements.push(format!(
+            "CREATE SEQUENCE {} AS {} START WITH {} INCREMENT BY {} MINVALUE {} MAXVALUE {} CACHE {}{}",
+            sequence,
+            data_type,
+            start,
+            increment,
+            min,
+            max,
+            cache,
+            if cycle { " CYCLE" } else { "" }
+        ));
+
+        if let (Some(table), Some(column)) = (owner_table, owner_column) {
+            sequence_owners.push(format!(
+                "ALTER SEQUENCE {} OWNED BY {}.{}",
+                sequence,
+                qualified_table_name(schema, &table)?,
+                quote_identifier(&column)
+            ));
+        }
+    }
+
+    // Functions come before tables since defaults and checks may call them, except those whose
+    // signature uses the row type of a table or view, which follow the views
+    let mut row_type_functions = Vec::new();
+    if request.include_functions {
+        let function_query = format!(
+            r#"
+            SELECT
+                pg_catalog.pg_get_functiondef(p.oid),
+                EXISTS (
+                    SELECT 1
+                    FROM pg_catalog.pg_type t
+                    LEFT JOIN pg_catalog.pg_type e ON e.oid = t.typelem
+                    JOIN pg_catalog.pg_class c ON c.oid IN (t.typrelid, e.typrelid)
+                    WHERE t.oid = ANY (
+                            COALESCE(p.proallargtypes, p.proargtypes::oid[]) || p.prorettype
+                        )
+                        AND c.relkind <> 'c'
+                )
+            FROM pg_catalog.pg_proc p
+            JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
+            JOIN pg_catalog.pg_language l ON l.oid = p.prolang
+            WHERE n.nspname = $1
+                AND p.prokind IN ('f', 'p')
+                AND l.lanname NOT IN ('internal', 'c')
+                AND {}
+            ORDER BY p.proname, p.oid
+        "#,
+            not_from_extension("pg_catalog.pg_proc", "p.oid")
+        );
+        let functions = transaction.query(function_query.as_str(), &[&schema]).await?;
+        if !functions.is_empty() {
+            // Function bodies may reference tables that don't exist yet
+            statements.push("SET check_function_bodies = false".to_string());
+        }
+        for row in functions {
+            let definition: String = row.get(0);
+            let uses_row_type: bool = row.get(1);
+            if uses_row_type {
+                row_type_functions.push(definition.trim_end().to_string());
+            } else {
+                statements.push(definition.trim_end().to_string());
+            }
+        }
+    }
+
+    // Tables
+    let (table_columns, table_constraints) =
+        fetch_table_definitions(&transaction, schema, None).await?;
+
+    let tables: Vec<String> = table_columns.keys().cloned().collect();
+    let references: Vec<(String, String)> = table_constraints
+        .iter()
+        .flat_map(|(table, constraints)| {
+            constraints
+                .iter()
+                .filter(|constraint| constraint.referenced_schema.as_deref() == Some(schema))
+                .filter_map(|constraint| constraint.referenced_table.clone())
+                .map(move |referenced| (table.clone(), referenced))
+        })
+        .collect();
+
+    let mut created = HashSet::new();
+    let mut deferred_foreign_keys = Vec::new();
+    for table in order_tables_by_references(&tables, &references) {
+        let qualified = qualified_table_name(schema, &table)?;
+        let mut definitions: Vec<String> =
+            table_columns.get(&table).into_iter().flatten().map(build_column_ddl).collect();
+
+        for constraint in table_constraints.get(&table).into_iter().flatten() {
+            let clause = format!(
+                "CONSTRAINT {} {}",
+                quote_identifier(&constraint.name),
+                constraint.definition
+            );
+            let references_later_table = constraint.constraint_type == "f"
+                && constraint.referenced_schema.as_deref() == Some(schema)
+                && constraint
+                    .referenced_table
+                    .as_deref()
+                    .is_some_and(|referenced| referenced != table && !created.contains(referenced));
+
+            if references_later_table {
+                deferred_foreign_keys.push(format!("ALTER TABLE {} ADD {}", qualified, clause));
+            } else {
+                definitions.push(clause);
+            }
+        }
+
+        statements.push(format!(
+            "CREATE TABLE {} (\n    {}\n)",
+            qualified,
+            definitions.join(",\n    ")
+        ));
+        created.insert(table);
+    }
+
+    statements.extend(deferred_foreign_keys);
+    statements.extend(sequence_owners);
+
+    // Views, in creation order so views built on other views follow them
+    if request.include_views {
+        let view_query = format!(
+            r#"
+            SELECT c.relname::text, c.relkind = 'm', pg_catalog.pg_get_viewdef(c.oid, true)
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE n.nspname = $1
+                AND c.relkind IN ('v', 'm')
+                AND {}
+            ORDER BY c.oid
+        "#,
+            not_from_extension("pg_catalog.pg_class", "c.oid")
+        );
+        for row in transaction.query(view_query.as_str(), &[&schema]).await? {
+            let name: String = row.get(0);
+            let materialized: bool = row.get(1);
+            let definition: String = row.get(2);
+            let definition = definition.trim().trim_end_matches(';');
+
+            statements.push(if materialized {
+                format!(
+                    "CREATE MATERIALIZED VIEW {} AS\n{}\nWITH NO DATA",
+                    qualified_table_name(schema, &name)?,
+                    definition
+                )
+            } else {
+                format!("CREATE VIEW {} AS\n{}", qualified_table_name(schema, &name)?, definition)
+            });
+        }
+    }
+    statements.extend(row_type_functions);
+
+    // Indexes that don't back a primary key, unique or exclusion constraint
+    let index_query = format!(
+        r#"
+        SELECT pg_catalog.pg_get_indexdef(ix.indexrelid)
+        FROM pg_catalog.pg_index ix
+        JOIN pg_catalog.pg_class c ON c.oid = ix.indrelid
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = $1
+            AND NOT c.relispartition
+            AND (c.relkind IN ('r', 'p') OR ($2::boolean AND c.relkind = 'm'))
+            AND {}
+            AND NOT EXISTS (
+                SELECT 1
+                FROM pg_catalog.pg_constraint con
+                WHERE con.conindid = ix.indexrelid
+                    AND con.contype IN ('p', 'u', 'x')
+            )
+        ORDER BY c.relname, ix.indexrelid
+    "#,
+        not_from_extension("pg_catalog.pg_class", "c.oid")
+    );
+    for row in transaction.query(index_query.as_str(), &[&schema, &request.include_views]).await? {
+        statements.push(row.get(0));
+    }
+
+    transaction.commit().await?;
+
+    let statement_count = statements.len();
+    let mut script = format!("-- Schema export of {}\n\n", quote_identifier(schema));
+    for statement in &statements {
+        script.push_str(statement);
+        script.push_str(";\n\n");
+    }
+    tokio::fs::write(&request.path, script).await?;
+
+    log::info!("Exported {} statements for schema {} to {}", statement_count, schema, request.path);
+
+    Ok(SchemaExportResult { path: request.path, statement_count })
+}
+
+/// SQL expression mapping a `pg_depend` entry to the table, view, sequence or function it
+/// belongs to, so that e.g. a view's rewrite rule or a table's foreign key counts as the
+/// relation itself
+fn dependency_owner(classid: &str, objid: &str) -> String {
+    format!(
+        "CASE {classid} \
+         WHEN 'pg_catalog.pg_class'::regclass THEN COALESCE((SELECT i.indrelid \
+         FROM pg_catalog.pg_index i WHERE i.indexrelid = {objid}), {objid}) \
+         WHEN 'pg_catalog.pg_rewrite'::regclass THEN (SELECT r.ev_class \
+         FROM pg_catalog.pg_rewrite r WHERE r.oid = {objid}) \
+         WHEN 'pg_catalog.pg_constraint'::regclass THEN (SELECT con.conrelid \
+         FROM pg_catalog.pg_constraint con WHERE con.oid = {objid}) \
+         WHEN 'pg_catalog.pg_attrdef'::regclass THEN (SELECT ad.adrelid \
+         FROM pg_catalog.pg_attrdef ad WHERE ad.oid = {objid}) \
+         WHEN 'pg_catalog.pg_trigger'::regclass THEN (SELECT tg.tgrelid \
+         FROM pg_catalog.pg_trigger tg WHERE tg.oid = {objid}) \
+         WHEN 'pg_catalog.pg_type'::regclass THEN (SELECT t.typrelid \
+         FROM pg_catalog.pg_type t WHERE t.oid = {objid}) \
+         WHEN 'pg_catalog.pg_proc'::regclass THEN {objid} \
+         END",
+        classid = classid,
+        objid = objid
+    )
+}
+
+/// The `DROP` statements for a schema's tables, views, sequences and functions, ordered so
+/// that every object is dropped before the objects it depends on
+///
+/// Dependencies come from `pg_depend`, covering foreign keys, view definitions, column
+/// defaults, triggers and row types used by functions. Partitions and sequences owned by a
+/// column go with their table. Objects caught in a dependency cycle come first in name order
+/// and need `CASCADE` or a dropped constraint to go through.
+#[tauri::command]
+pub async fn get_schema_drop_order(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+) -> Result<Vec<String>> {
+    log::info!("Getting drop order for schema: {} on connection: {}", schema, connection_id);
+
+    validate_identifier(&schema, "Schema")?;
+
+    let client = state.get_client(&connection_id).await?;
+
+    let object_query = format!(
+        r#"
+        SELECT
+            c.oid,
+            CASE c.relkind
+                WHEN 'v' THEN 'VIEW'
+                WHEN 'm' THEN 'MATERIALIZED VIEW'
+                WHEN 'S' THEN 'SEQUENCE'
+                ELSE 'TABLE'
+            END,
+            pg_catalog.quote_ident(n.nspname) || '.' || pg_catalog.quote_ident(c.relname)
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = $1
+            AND c.relkind IN ('r', 'p', 'v', 'm', 'S')
+            AND NOT c.relispartition
+            AND (c.relkind <> 'S' OR NOT EXISTS (
+                SELECT 1 FROM pg_catalog.pg_depend owned
+                WHERE owned.classid = 'pg_catalog.pg_class'::regclass
+                    AND owned.objid = c.oid
+                    AND owned.deptype IN ('a', 'i')
+            ))
+            AND {}
+        UNION ALL
+        SELECT
+            p.oid,
+            CASE p.prokind
+                WHEN 'p' THEN 'PROCEDURE'
+                WHEN 'a' THEN 'AGGREGATE'
+                ELSE 'FUNCTION'
+            END,
+            pg_catalog.quote_ident(n.nspname) || '.' || pg_catalog.quote_ident(p.proname)
+                || '(' || pg_catalog.pg_get_function_identity_arguments(p.oid) || ')'
+        FROM pg_catalog.pg_proc p
+        JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
+        WHERE n.nspname = $1
+            AND {}
+    "#,
+        not_from_extension("pg_catalog.pg_class", "c.oid"),
+        not_from_extension("pg_catalog.pg_proc", "p.oid")
+    );
+    let mut kinds: BTreeMap<String, String> = BTreeMap::new();
+    let mut names_by_oid: HashMap<u32, String> = HashMap::new();
+    for row in client.query(object_query.as_str(), &[&schema]).await? {
+        let name: String = row.get(2);
+        kinds.insert(name.clone(), row.get(1));
+        names_by_oid.insert(row.get(0), name);
+    }
+
+    let dependency_query = format!(
+        r#"
+        SELECT DISTINCT dependent, referenced
+        FROM (
+            SELECT {} AS dependent, {} AS referenced
+            FROM pg_catalog.pg_depend d
+            WHERE d.deptype IN ('n', 'a')
+        ) dependencies
+        WHERE dependent IS NOT NULL
+            AND referenced IS NOT NULL
+            AND dependent <> referenced
+    "#,
+        dependency_owner("d.classid", "d.objid"),
+        dependency_owner("d.refclassid", "d.refobjid")
+    );
+    let dependencies: Vec<(String, String)> = client
+        .query(dependency_query.as_str(), &[])
+        .await?
+        .iter()
+        .filter_map(|row| {
+            let dependent = names_by_oid.get(&row.get::<_, u32>(0))?;
+            let referenced = names_by_oid.get(&row.get::<_, u32>(1))?;
+            Some((dependent.clone(), referenced.clone()))
+        })
+        .collect();
+
+    // Creation order with every object after its dependencies, reversed for dropping
+    let names: Vec<String> = kinds.keys().cloned().collect();
+    let statements: Vec<String> = order_tables_by_references(&names, &dependencies)
+        .into_iter()
+        .rev()
+        .map(|name| format!("DROP {} {};", kinds[&name], name))
+        .collect();
+
+    log::info!("Found {} objects to drop in schema {}", statements.len(), schema);
+
+    Ok(statements)
+}
+
+/// Export a schema's tables, columns, keys, indexes and constraints as a JSON document
+///
+/// With `path` set the document is written there and the path is returned; otherwise the
+/// document itself is returned.
+#[tauri::command]
+pub async fn export_schema_json(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+    path: Option<String>,
+) -> Result<String> {
+    log::info!("Exporting structure of schema: {} on connection: {}", schema, connection_id);
+
+    validate_identifier(&schema, "schema")?;
+
+    let tables = list_tables(state.clone(), connection_id.clone(), Some(schema.clone())).await?;
+
+    let tables = futures_util::stream::iter(tables)
+        .map(|table| introspect_table(state.clone(), connection_id.clone(), table))
+        .buffered(CONCURRENT_TABLE_INTROSPECTIONS)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let structure =
+        SchemaStructure { schema, exported_at: chrono::Utc::now().to_rfc3339(), tables };
+    let document = serde_json::to_string_pretty(&structure)?;
+
+    match path {
+        Some(path) => {
+            tokio::fs::write(&path, document).await?;
+            log::info!("Exported structure of {} tables to {}", structure.tables.len(), path);
+            Ok(path)
+        }
+        None => Ok(document),
+    }
+}
+
+async fn introspect_table(
+    state: State<'_, AppState>,
+    connection_id: String,
+    table: Table,
+) -> Result<TableStructure> {
+    let (columns, primary_key, indexes, constraints, foreign_keys) = tokio::try_join!(
+        get_table_columns(
+            state.clone(),
+            connection_id.clone(),
+            table.schema.clone(),
+            table.name.clone()
+        ),
+        get_primary_keys(
+            state.clone(),
+            connection_id.clone(),
+            table.schema.clone(),
+            table.name.clone()
+        ),
+        get_indexes(state.clone(), connection_id.clone(), table.schema.clone(), table.name.clone()),
+        get_constraints(
+            state.clone(),
+            connection_id.clone(),
+            table.schema.clone(),
+            table.name.clone()
+        ),
+        get_foreign_keys(state.clone(), connection_id, table.schema.clone(), table.name.clone()),
+    )?;
+
+    Ok(TableStructure { table, columns, primary_key, indexes, constraints, foreign_keys })
+}
+
+/// Copy a table's rows from one connection to another
+///
+/// Rows stream from `COPY ... TO STDOUT` on the source straight into `COPY ... FROM STDIN` on
+/// the destination, one chunk at a time, with `copy-progress` events along the way. The
+/// destination side runs in a single transaction, so a failure part way through leaves it
+/// untouched. With `create_table`, the table is first created from the source definition, minus
+/// foreign keys and sequence-backed defaults.
+#[tauri::command]
+pub async fn transfer_table(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: TableTransferRequest,
+) -> Result<TableTransferResult> {
+    log::info!(
+        "Transferring table {}.{} from connection {} to connection: {}",
+        request.schema,
+        request.table,
+        request.source_connection_id,
+        request.destination_connection_id
+    );
+
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table, "table")?;
+    let schema = request.schema.as_str();
+    let table = request.table.as_str();
+    let qualified = qualified_table_name(schema, table)?;
+
+    let mut source_client = state.get_client(&request.source_connection_id).await?;
+    let mut destination_client = state.get_client(&request.destination_connection_id).await?;
+    let start = Instant::now();
+
+    // One snapshot covers both the table definition and the copied rows
+    let source = source_client
+        .build_transaction()
+        .isolation_level(IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await?;
+    source.batch_execute("SET LOCAL search_path = pg_catalog").await?;
+
+    let (mut table_columns, mut table_constraints) =
+        fetch_table_definitions(&source, schema, Some(table)).await?;
+    let columns = table_columns.remove(table).ok_or_else(|| {
+        RowFlowError::SchemaError(format!("Table not found: {}.{}", schema, table))
+    })?;
+
+    // Generated columns are computed again on the destination
+    let copy_columns = columns
+        .iter()
+        .filter(|column| column.generated.is_empty())
+        .map(|column| quote_identifier(&column.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let destination = destination_client.transaction().await?;
+
+    if request.create_table {
+        let mut definitions: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone();
+                // The sequence lives on the source; the destination has nothing to draw from
+                if column.default.as_deref().is_some_and(|default| default.starts_with("nextval("))
+                {
+                    column.default = None;
+                }
+                build_column_ddl(&column)
+            })
+            .collect();
+        definitions.extend(
+            table_constraints
+                .remove(table)
+                .into_iter()
+                .flatten()
+                .filter(|constraint| constraint.constraint_type != "f")
+                .map(|constraint| {
+                    format!(
+                        "CONSTRAINT {} {}",
+                        quote_identifier(&constraint.name),
+                        constraint.definition
+                    )
+                }),
+        );
+
+        destination
+            .batch_execute(&format!(
+                "CREATE TABLE {} (\n    {}\n)",
+                qualified,
+                definitions.join(",\n    ")
+            ))
+            .await?;
+    }
+
+    // Planner statistics are only an estimate, and -1 before the table was first analyzed
+    let estimated_rows: f32 = source
+        .query_one("SELECT reltuples FROM pg_class WHERE oid = $1::text::regclass", &[&qualified])
+        .await?
+        .get(0);
+    let mut progress = CopyProgressReporter::new(
+        app,
+        "transfer_table",
+        format!("{}.{}", schema, table),
+        None,
+        (estimated_rows >= 0.0).then_some(estimated_rows as u64),
+    );
+
+    let rows = source
+        .copy_out(format!("COPY {} ({}) TO STDOUT", qualified, copy_columns).as_str())
+        .await?;
+    let sink = destination
+        .copy_in::<_, Bytes>(format!("COPY {} ({}) FROM STDIN", qualified, copy_columns).as_str())
+        .await?;
+    futures_util::pin_mut!(rows);
+    futures_util::pin_mut!(sink);
+
+    while let Some(chunk) = rows.try_next().await? {
+        progress.record(&chunk);
+        sink.send(chunk).await?;
+    }
+    let rows_transferred = sink.as_mut().finish().await?;
+    progress.finish(Some(rows_transferred));
+
+    if request.create_table {
+        // Identity sequences start over on a new table; move them past the copied values
+        for column in columns.iter().filter(|column| !column.identity.is_empty()) {
+            let column_name = quote_identifier(&column.name);
+            let sql = format!(
+                "SELECT pg_catalog.setval(pg_catalog.pg_get_serial_sequence($1, $2), MAX({})) \
+                 FROM {} HAVING MAX({}) IS NOT NULL",
+                column_name, qualified, column_name
+            );
+            destination.execute(sql.as_str(), &[&qualified, &column.name]).await?;
+        }
+    }
+
+    destination.commit().await?;
+    source.commit().await?;
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!("Transferred {} rows of {} in {:.2}ms", rows_transferred, qualified, execution_time);
+
+    Ok(TableTransferResult {
+        rows_transferred,
+        table_created: request.create_table,
+        execution_time,
+    })
+}
+
+/// Export a query's rows to an S3 object
+///
+/// The output of `COPY (...) TO STDOUT` is uploaded in parts of a multipart upload as it
+/// arrives, so the result is never written to disk or held in memory as a whole, with
+/// `copy-progress` events along the way. The query runs in a read-only transaction, and the
+/// upload is aborted if either side fails. With `compress` the rows are compressed as they
+/// stream; `if_match` and `if_none_match` are checked when the upload completes.
+#[tauri::command]
+pub async fn export_query_to_s3(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: QueryS3ExportRequest,
+) -> Result<QueryS3ExportResult> {
+    log::info!(
+        "Exporting query on connection {} to S3 key {} on connection: {}",
+        request.connection_id,
+        request.key,
+        request.s3_connection_id
+    );
+
+    let query = request.sql.trim().trim_end_matches(';').trim();
+    if query.is_empty() {
+        return Err(RowFlowError::InvalidInput("SQL query is required".to_string()));
+    }
+    if request.key.trim().is_empty() {
+        return Err(RowFlowError::InvalidInput("S3 key is required".to_string()));
+    }
+    let header = request.format == ExportFileFormat::Csv && request.header.unwrap_or(true);
+    let (copy_sql, content_type) = match request.format {
+        ExportFileFormat::Csv => {
+            (format!("COPY ({}) TO STDOUT WITH (FORMAT csv, HEADER {})", query, header), "text/csv")
+        }
+        // JSON text never holds a raw control character, so CSV with control characters as
+        // quote and delimiter writes each document unchanged on its own line
+        ExportFileFormat::Ndjson => (
+            format!(
+                "COPY (SELECT pg_catalog.row_to_json(q) FROM ({}) q) TO STDOUT \
+                 WITH (FORMAT csv, QUOTE E'\\x01', DELIMITER E'\\x02')",
+                query
+            ),
+            "application/x-ndjson",
+        ),
+    };
+
+    let encryption = server_side_encryption_options(
+        request.server_side_encryption.as_deref(),
+        request.sse_kms_key_id.as_deref(),
+    )?;
+    let mut encoder = request.compress.map(StreamEncoder::new).transpose()?;
+
+    let (s3_client, profile) = state.get_s3_client(&request.s3_connection_id).await?;
+    let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &request.key);
+    let mut client = state.get_client(&request.connection_id).await?;
+    let start = Instant::now();
+
+    let transaction = client.build_transaction().read_only(true).start().await?;
+    let rows = transaction.copy_out(copy_sql.as_str()).await?;
+
+    let mut create_upload = s3_client
+        .create_multipart_upload()
+        .bucket(&profile.bucket)
+        .key(&full_key)
+        .content_type(content_type);
+    if let Some(compression) = request.compress {
+        create_upload = create_upload.content_encoding(content_encoding(compression));
+    }
+    if let Some((encryption, kms_key_id)) = encryption {
+        create_upload =
+            create_upload.server_side_encryption(encryption).set_ssekms_key_id(kms_key_id);
+    }
+    let upload = create_upload.send().await.map_err(|e| {
+        RowFlowError::InternalError(format!("Failed to start S3 multipart upload: {}", e))
+    })?;
+    let upload_id = upload.upload_id().unwrap_or_default().to_string();
+
+    let mut progress =
+        CopyProgressReporter::new(app, "export_query_to_s3", full_key.clone(), None, None);
+
+    let uploaded: Result<Vec<CompletedPart>> = async {
+        futures_util::pin_mut!(rows);
+        let mut parts = Vec::new();
+        let mut buffer = Vec::with_capacity(S3_UPLOAD_PART_SIZE);
+
+        let mut finished = false;
+
+        while !finished {
+            match rows.try_next().await? {
+                Some(chunk) => {
+                    progress.record(&chunk);
+                    match encoder.as_mut() {
+                        Some(encoder) => buffer.extend(encoder.encode(&chunk)?),
+                        None => buffer.extend_from_slice(&chunk),
+                    }
+                }
+                None => {
+                    if let Some(encoder) = encoder.take() {
+                        buffer.extend(encoder.finish()?);
+                    }
+                    finished = true;
+                }
+            }
+            // Every part but the last has to reach the S3 minimum part size, and an empty
+            // result still needs one part
+            if buffer.len() < S3_UPLOAD_PART_SIZE
+                && !(finished && (!buffer.is_empty() || parts.is_empty()))
+            {
+                continue;
+            }
+
+            let part_number = parts.len() as i32 + 1;
+            let part = s3_client
+                .upload_part()
+                .bucket(&profile.bucket)
+                .key(&full_key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from(std::mem::take(&mut buffer)))
+                .send()
+                .await
+                .map_err(|e| {
+                    RowFlowError::InternalError(format!("Failed to upload S3 part: {}", e))
+                })?;
+            parts.push(
+                CompletedPart::builder()
+                    .part_number(part_number)
+                    .set_e_tag(part.e_tag().map(str::to_string))
+                    .build(),
+            );
+        }
+        Ok(parts)
+    }
+    .await;
+
+    let parts = match uploaded {
+        Ok(parts) => parts,
+        Err(error) => {
+            let _ = s3_client
+                .abort_multipart_upload()
+                .bucket(&profile.bucket)
+                .key(&full_key)
+                .upload_id(&upload_id)
+                .send()
+                .await;
+            return Err(error);
+        }
+    };
+    let part_count = parts.len();
+
+    // Conditions are checked when the upload completes, since only then does the object change
+    let completed = s3_client
+        .complete_multipart_upload()
+        .bucket(&profile.bucket)
+        .key(&full_key)
+        .upload_id(&upload_id)
+        .multipart_upload(CompletedMultipartUpload::builder().set_parts(Some(parts)).build())
+        .set_if_match(request.if_match.clone())
+        .set_if_none_match(request.if_none_match.clone())
+        .send()
+        .await;
+    let completed = match completed {
+        Ok(completed) => completed,
+        Err(e) => {
+            let _ = s3_client
+                .abort_multipart_upload()
+                .bucket(&profile.bucket)
+                .key(&full_key)
+                .upload_id(&upload_id)
+                .send()
+                .await;
+            if e.raw_response().map(|response| response.status().as_u16()) == Some(412) {
+                return Err(RowFlowError::PreconditionFailed(format!(
+                    "S3 object {} was modified or already exists",
+                    full_key
+                )));
+            }
+            return Err(RowFlowError::InternalError(format!(
+                "Failed to complete S3 multipart upload: {}",
+                e
+            )));
+        }
+    };
+    transaction.commit().await?;
+
+    let bytes = progress.bytes();
+    let rows_exported = progress.rows().saturating_sub(u64::from(header));
+    progress.finish(Some(rows_exported));
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!(
+        "Exported {} rows ({} bytes) to S3 key {} in {:.2}ms",
+        rows_exported,
+        bytes,
+        full_key,
+        execution_time
+    );
+
+    Ok(QueryS3ExportResult {
+        key: full_key,
+        etag: completed.e_tag().map(|etag| etag.to_string()),
+        rows_exported,
+        bytes,
+        part_count,
+        execution_time,
+    })
+}
+
+/// Load an S3 object holding CSV into a table
+///
+/// The object body streams from S3 straight into `COPY ... FROM STDIN`, so it is never written
+/// to disk or held in memory as a whole, with `copy-progress` events along the way. Objects with
+/// a gzip or zstd `Content-Encoding`, or a `.gz` or `.zst` key, are decompressed as they arrive.
+/// Without `columns`, the CSV fields have to follow the table's column order.
+#[tauri::command]
+pub async fn import_s3_object_to_table(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: S3TableImportRequest,
+) -> Result<S3TableImportResult> {
+    log::info!(
+        "Importing S3 key {} from connection {} into table {}.{} on connection: {}",
+        request.key,
+        request.s3_connection_id,
+        request.schema,
+        request.table,
+        request.connection_id
+    );
+
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table, "table")?;
+    let qualified = qualified_table_name(&request.schema, &request.table)?;
+
+    let table_columns = get_table_columns(
+        state.clone(),
+        request.connection_id.clone(),
+        request.schema.clone(),
+        request.table.clone(),
+    )
+    .await?;
+    if table_columns.is_empty() {
+        return Err(RowFlowError::SchemaError(format!(
+            "Table not found: {}.{}",
+            request.schema, request.table
+        )));
+    }
+    let column_list = match request.columns.as_ref().filter(|columns| !columns.is_empty()) {
+        Some(columns) => {
+            let mut seen = HashSet::new();
+            for column in columns {
+                validate_identifier(column, "column")?;
+                if !table_columns.iter().any(|table_column| &table_column.name == column) {
+                    return Err(RowFlowError::SchemaError(format!(
+                        "Column not found: {} in {}.{}",
+                        column, request.schema, request.table
+                    )));
+                }
+                if !seen.insert(column.as_str()) {
+                    return Err(RowFlowError::InvalidInput(format!(
+                        "Column listed more than once: {}",
+                        column
+                    )));
+                }
+            }
+            format!(
+                " ({})",
+                columns
+                    .iter()
+                    .map(|column| quote_identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        None => String::new(),
+    };
+
+    let mut options = format!("FORMAT csv, HEADER {}", request.header.unwrap_or(true));
+    if let Some(delimiter) = &request.delimiter {
+        if delimiter.chars().count() != 1 {
+            return Err(RowFlowError::InvalidInput(format!(
+                "CSV delimiter must be a single character, got '{}'",
+                delimiter
+            )));
+        }
+        options.push_str(&format!(", DELIMITER {}", quote_literal(delimiter)));
+    }
+
+    let (s3_client, profile) = state.get_s3_client(&request.s3_connection_id).await?;
+    let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &request.key);
+    let start = Instant::now();
+
+    let object = s3_client
+        .get_object()
+        .bucket(&profile.bucket)
+        .key(&full_key)
+        .send()
+        .await
+        .map_err(|e| RowFlowError::InternalError(format!("Failed to get S3 object: {}", e)))?;
+
+    // Compressed files are often stored as they are, with only the extension saying so
+    let compression = object.content_encoding().and_then(parse_content_encoding).or_else(|| {
+        if full_key.ends_with(".gz") {
+            Some(Compression::Gzip)
+        } else if full_key.ends_with(".zst") {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    });
+    let total_bytes = object
+        .content_length()
+        .filter(|_| compression.is_none())
+        .and_then(|length| u64::try_from(length).ok());
+    let mut decoder = compression.map(StreamDecoder::new).transpose()?;
+    let mut body = object.body;
+
+    let client = state.get_client(&request.connection_id).await?;
+    let mut progress = CopyProgressReporter::new(
+        app,
+        "import_s3_object_to_table",
+        format!("{}.{}", request.schema, request.table),
+        total_bytes,
+        None,
+    );
+
+    let sink = client
+        .copy_in::<_, Bytes>(
+            format!("COPY {}{} FROM STDIN WITH ({})", qualified, column_list, options).as_str(),
+        )
+        .await?;
+    futures_util::pin_mut!(sink);
+
+    while let Some(chunk) = body
+        .try_next()
+        .await
+        .map_err(|e| RowFlowError::InternalError(format!("Failed to read S3 object body: {}", e)))?
+    {
+        let chunk = match decoder.as_mut() {
+            Some(decoder) => Bytes::from(decoder.decode(&chunk)?),
+            None => chunk,
+        };
+        if !chunk.is_empty() {
+            progress.record(&chunk);
+            sink.send(chunk).await?;
+        }
+    }
+    if let Some(decoder) = decoder {
+        let rest = decoder.finish()?;
+        if !rest.is_empty() {
+            progress.record(&rest);
+            sink.send(Bytes::from(rest)).await?;
+        }
+    }
+    let rows_imported = sink.as_mut().finish().await?;
+    let bytes = progress.bytes();
+    progress.finish(Some(rows_imported));
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!("Imported {} rows into {} in {:.2}ms", rows_imported, qualified, execution_time);
+
+    Ok(S3TableImportResult { rows_imported, bytes, execution_time })
+}
+
+/// Compute a fingerprint of a table's rows, to check that two copies of a table hold the same data
+///
+/// The server hashes each row's text form with md5 and the row hashes are folded together in
+/// key order through a cursor, so the table is never held in memory. Rows are ordered by
+/// `order_by`, or the primary key when omitted. Settings that affect the text form of values are
+/// pinned, but the compared tables still need the same columns in the same order.
+#[tauri::command]
+pub async fn table_checksum(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+    table: String,
+    order_by: Option<Vec<String>>,
+) -> Result<TableChecksum> {
+    log::info!("Computing checksum of {}.{} on connection: {}", schema, table, connection_id);
+
+    let qualified = qualified_table_name(&schema, &table)?;
+    let order_by = match order_by {
+        Some(columns) if !columns.is_empty() => columns,
+        _ => {
+            get_primary_keys(state.clone(), connection_id.clone(), schema.clone(), table.clone())
+                .await?
+        }
+    };
+    if order_by.is_empty() {
+        return Err(RowFlowError::InvalidInput(format!(
+            "{}.{} has no primary key; specify the columns to order rows by",
+            schema, table
+        )));
+    }
+    let order_clause = order_by
+        .iter()
+        .map(|column| {
+            validate_identifier(column, "column")?;
+            Ok(format!("t.{}", quote_identifier(column)))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+
+    let mut client = state.get_client(&connection_id).await?;
+    let start = Instant::now();
+
+    let transaction = client
+        .build_transaction()
+        .isolation_level(IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await?;
+    transaction
+        .batch_execute(
+            "SET LOCAL TimeZone = 'UTC';
+             SET LOCAL DateStyle = 'ISO, MDY';
+             SET LOCAL IntervalStyle = 'postgres';
+             SET LOCAL extra_float_digits = 1;
+             SET LOCAL bytea_output = 'hex'",
+        )
+        .await?;
+
+    // The row hash breaks ties, so a non-unique ordering key still gives a stable result
+    transaction
+        .batch_execute(&format!(
+            "DECLARE rowflow_checksum NO SCROLL CURSOR FOR \
+             SELECT md5(t::text) AS row_hash FROM {} AS t ORDER BY {}, row_hash",
+            qualified, order_clause
+        ))
+        .await?;
+
+    let fetch = format!("FETCH {} FROM rowflow_checksum", CHECKSUM_FETCH_SIZE);
+    let mut hasher = blake3::Hasher::new();
+    let mut row_count = 0u64;
+    loop {
+        let rows = transaction.query(fetch.as_str(), &[]).await?;
+        if rows.is_empty() {
+            break;
+        }
+        for row in &rows {
+            let row_hash: &str = row.get(0);
+            hasher.update(row_hash.as_bytes());
+        }
+        row_count += rows.len() as u64;
+    }
+
+    transaction.commit().await?;
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!("Checksummed {} rows of {} in {:.2}ms", row_count, qualified, execution_time);
+
+    Ok(TableChecksum {
+        schema,
+        table,
+        row_count,
+        checksum: hasher.finalize().to_hex().to_string(),
+        execution_time,
+    })
+}
+
+/// Format a query result for pasting into documents, issues or spreadsheets
+///
+/// Markdown tables are padded so the columns line up in plain text, with pipes escaped and line
+/// breaks turned into `<br>`. TSV escapes tabs, line breaks and backslashes with a backslash.
+/// NULL is written as `NULL` in Markdown and as an empty field in TSV.
+#[tauri::command]
+pub async fn format_query_result(result: QueryResult, format: ResultFormat) -> Result<String> {
+    let headers: Vec<&str> = result.fields.iter().map(|field| field.name.as_str()).collect();
+    let rows: Vec<Vec<Option<String>>> = result
+        .rows
+        .iter()
+        .map(|row| headers.iter().map(|name| cell_text(row.get(*name))).collect())
+        .collect();
+
+    Ok(match format {
+        ResultFormat::Markdown => format_markdown(&headers, &rows),
+        ResultFormat::Tsv => format_tsv(&headers, &rows),
+        ResultFormat::Json => serde_json::to_string_pretty(&result.rows)?,
+    })
+}
+
+/// Text of a result cell, or `None` for NULL
+fn cell_text(value: Option<&Value>) -> Option<String> {
+    match value {
+        None | Some(Value::Null) => None,
+        Some(Value::String(text)) => Some(text.clone()),
+        Some(other) => Some(other.to_string()),
+    }
+}
+
+fn format_markdown(headers: &[&str], rows: &[Vec<Option<String>>]) -> String {
+    let escape =
+        |text: &str| text.replace('|', "\\|").replace("\r\n", "<br>").replace(['\n', '\r'], "<br>");
+    let header_cells: Vec<String> = headers.iter().map(|header| escape(header)).collect();
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.iter().map(|cell| cell.as_deref().map_or("NULL".to_string(), escape)).collect()
+        })
+        .collect();
+
+    // Separator cells need at least three dashes
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            body.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header_cells[column].chars().count(), 3])
+                .max()
+                .unwrap_or(3)
+        })
+        .collect();
+
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - cell.chars().count())))
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut lines = vec![line(&header_cells), line(&separator)];
+    lines.extend(body.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn format_tsv(headers: &[&str], rows: &[Vec<Option<String>>]) -> String {
+    let escape = |text: &str| {
+        text.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+    };
+
+    let mut lines =
+        vec![headers.iter().map(|header| escape(header)).collect::<Vec<_>>().join("\t")];
+    lines.extend(rows.iter().map(|row| {
+        row.iter()
+            .map(|cell| cell.as_deref().map(escape).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\t")
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_markdown, format_tsv, order_tables_by_references};
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn edge(table: &str, referenced: &str) -> (String, String) {
+        (table.to_string(), referenced.to_string())
+    }
+
+    #[test]
+    fn orders_referenced_tables_first() {
+        let tables = names(&["order_items", "orders", "products", "customers"]);
+        let references = vec![
+            edge("order_items", "orders"),
+            edge("order_items", "products"),
+            edge("orders", "customers"),
+            edge("customers", "customers"),
+        ];
+
+        assert_eq!(
+            order_tables_by_references(&tables, &references),
+            names(&["customers", "orders", "products", "order_items"])
+        );
+    }
+
+    #[test]
+    fn appends_reference_cycles_in_name_order() {
+        let tables = names(&["b", "a", "c"]);
+        let references = vec![edge("a", "b"), edge("b", "a"), edge("c", "missing")];
+
+        assert_eq!(order_tables_by_references(&tables, &references), names(&["c", "a", "b"]));
+    }
+
+    #[test]
+    fn formats_markdown_and_tsv_with_escaping() {
+        let headers = ["id", "note"];
+        let rows = vec![
+            vec![Some("1".to_string()), Some("a|b\nc".to_string())],
+            vec![Some("22".to_string()), None],
+        ];
+
+        assert_eq!(
+            format_markdown(&headers, &rows),
+            "| id  | note      |\n| --- | --------- |\n| 1   | a\\|b<br>c |\n| 22  | NULL      |"
+        );
+        assert_eq!(format_tsv(&headers, &rows), "id\tnote\n1\ta|b\\nc\n22\t");
+    }
+}
@@ -1,10 +1,18 @@
 pub mod ai;
 pub mod database;
+pub mod export;
+pub mod notify;
 pub mod s3;
 pub mod schema;
+pub mod script;
+pub mod transaction;
 
 // Re-export all commands for easy access
 pub use ai::*;
 pub use database::*;
+pub use export::*;
+pub use notify::*;
 pub use s3::*;
 pub use schema::*;
+pub use script::*;
+pub use transaction::*;
@@ -0,0 +1,80 @@
+use super::schema::validate_identifier;
+use crate::error::Result;
+use crate::state::AppState;
+use crate::types::NotificationSubscription;
+use tauri::State;
+
+/// Subscribe to a NOTIFY channel, returning the listener and all of its channels
+///
+/// Without `listener_id` a new listener is opened on its own connection; passing the ID of an
+/// existing listener adds the channel to that connection instead. Notifications arrive as
+/// `pg-notification` events.
+#[tauri::command]
+pub async fn listen_channel(
+    state: State<'_, AppState>,
+    connection_id: String,
+    channel: String,
+    listener_id: Option<String>,
+) -> Result<NotificationSubscription> {
+    validate_identifier(&channel, "Channel")?;
+    log::info!("Listening on channel {} on connection: {}", channel, connection_id);
+
+    let (listener_id, opened) = match listener_id {
+        Some(listener_id) => (listener_id, false),
+        None => (state.open_notification_listener(&connection_id).await?, true),
+    };
+
+    match state.listen(&listener_id, &channel).await {
+        Ok(channels) => Ok(NotificationSubscription { listener_id, channels }),
+        Err(err) => {
+            // Don't leave a listener without channels behind
+            if opened {
+                let _ = state.close_notification_listener(&listener_id).await;
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Unsubscribe a listener from one channel, keeping its other subscriptions
+///
+/// The listener's connection is closed once its last channel is removed, in which case the
+/// returned channel list is empty.
+#[tauri::command]
+pub async fn unlisten_channel(
+    state: State<'_, AppState>,
+    listener_id: String,
+    channel: String,
+) -> Result<NotificationSubscription> {
+    log::info!("Unlistening from channel {} on listener: {}", channel, listener_id);
+
+    let channels = state.unlisten(&listener_id, &channel).await?;
+    Ok(NotificationSubscription { listener_id, channels })
+}
+
+/// Close a listener and its connection, dropping every subscription
+#[tauri::command]
+pub async fn close_listener(state: State<'_, AppState>, listener_id: String) -> Result<()> {
+    log::info!("Closing listener: {}", listener_id);
+
+    state.close_notification_listener(&listener_id).await
+}
+
+/// Publish a notification on a channel with `pg_notify`
+#[tauri::command]
+pub async fn send_notification(
+    state: State<'_, AppState>,
+    connection_id: String,
+    channel: String,
+    payload: Option<String>,
+) -> Result<()> {
+    validate_identifier(&channel, "Channel")?;
+    log::info!("Sending notification on channel {} on connection: {}", channel, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+    client
+        .execute("SELECT pg_catalog.pg_notify($1, $2)", &[&channel, &payload.unwrap_or_default()])
+        .await?;
+
+    Ok(())
+}
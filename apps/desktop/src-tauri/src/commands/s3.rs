@@ -1,24 +1,42 @@
 use crate::error::{Result, RowFlowError};
 use crate::state::AppState;
 use crate::types::{
-    S3BucketInfo, S3ConnectionProfile, S3DeleteError, S3DeleteObjectsRequest, S3DeleteResult,
-    S3GetObjectRequest, S3GetObjectResponse, S3ListRequest, S3ListResult, S3Object,
-    S3PresignedUrlRequest, S3PresignedUrlResponse, S3PutObjectRequest,
+    Compression, S3BucketInfo, S3ConnectionProfile, S3DeleteError, S3DeleteObjectsRequest,
+    S3DeleteResult, S3GetObjectRequest, S3GetObjectResponse, S3ListRequest, S3ListResult, S3Object,
+    S3ObjectHead, S3ObjectTagsRequest, S3ObjectVersion, S3PrefixStats, S3PresignedUrlRequest,
+    S3PresignedUrlResponse, S3PutObjectRequest, S3PutObjectResponse, S3RenamePrefixRequest,
+    S3RenamePrefixResult, S3StorageClassStats,
 };
 use aws_config::meta::region::RegionProviderChain;
 use aws_config::BehaviorVersion;
 use aws_credential_types::Credentials;
+use aws_sdk_s3::config::retry::RetryConfig;
 use aws_sdk_s3::config::Region;
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{ServerSideEncryption, StorageClass, Tag, Tagging};
 use aws_sdk_s3::Client as S3Client;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
 use std::time::{Duration, SystemTime};
-use tauri::State;
+use tauri::{Emitter, State};
+
+/// Retries of a failed S3 request when the profile does not set `max_retries`
+const DEFAULT_S3_MAX_RETRIES: u32 = 3;
+
+/// Delay before the first S3 retry when the profile does not set `retry_base_delay_ms`
+const DEFAULT_S3_RETRY_BASE_DELAY_MS: u64 = 200;
+
+/// Longest delay between two S3 retries
+const MAX_S3_RETRY_DELAY: Duration = Duration::from_secs(20);
+
+/// Objects per listing page when the request does not set `max_keys`, as S3 itself defaults to
+const DEFAULT_LIST_MAX_KEYS: i32 = 1000;
 
 fn normalized_path_prefix(path_prefix: Option<&String>) -> Option<&str> {
     path_prefix.map(|prefix| prefix.trim_matches('/')).filter(|trimmed| !trimmed.is_empty())
 }
 
-fn build_full_s3_key(path_prefix: Option<&String>, key: &str) -> String {
+pub(crate) fn build_full_s3_key(path_prefix: Option<&String>, key: &str) -> String {
     if let Some(base) = normalized_path_prefix(path_prefix) {
         let trimmed_key = key.trim_start_matches('/');
 
@@ -71,6 +89,251 @@ fn build_effective_prefix(
     }
 }
 
+/// Resolve a folder-like prefix to a full key prefix ending in '/'
+fn build_folder_prefix(path_prefix: Option<&String>, prefix: &str) -> Option<String> {
+    let trimmed = prefix.trim_matches('/');
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}/", build_full_s3_key(path_prefix, trimmed).trim_end_matches('/')))
+}
+
+/// Percent-encode a value, optionally leaving '/' separators intact
+fn percent_encode(value: &str, keep_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if keep_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Build the `x-amz-copy-source` header value for an object
+fn encode_copy_source(bucket: &str, key: &str) -> String {
+    format!("{}/{}", bucket, percent_encode(key, true))
+}
+
+/// Encode tags as the URL query string expected by the `x-amz-tagging` header
+fn encode_tagging(tags: &BTreeMap<String, String>) -> String {
+    tags.iter()
+        .map(|(key, value)| {
+            format!("{}={}", percent_encode(key, false), percent_encode(value, false))
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Parse a server-side encryption algorithm name
+fn parse_server_side_encryption(value: &str) -> Result<ServerSideEncryption> {
+    match value.trim() {
+        "AES256" => Ok(ServerSideEncryption::Aes256),
+        "aws:kms" => Ok(ServerSideEncryption::AwsKms),
+        other => Err(RowFlowError::InvalidInput(format!(
+            "Unsupported server-side encryption '{}'; expected AES256 or aws:kms",
+            other
+        ))),
+    }
+}
+
+/// Validate server-side encryption options, returning the algorithm and the KMS key to send
+///
+/// A KMS key id is required with `aws:kms` and rejected with any other algorithm.
+pub(crate) fn server_side_encryption_options(
+    algorithm: Option<&str>,
+    kms_key_id: Option<&str>,
+) -> Result<Option<(ServerSideEncryption, Option<String>)>> {
+    let Some(algorithm) = algorithm else {
+        return Ok(None);
+    };
+    let encryption = parse_server_side_encryption(algorithm)?;
+    let kms_key_id = kms_key_id.map(str::trim).filter(|id| !id.is_empty());
+
+    match (&encryption, kms_key_id) {
+        (ServerSideEncryption::AwsKms, None) => Err(RowFlowError::InvalidInput(
+            "A KMS key id is required when using aws:kms encryption".to_string(),
+        )),
+        (ServerSideEncryption::AwsKms, Some(_)) | (_, None) => {
+            Ok(Some((encryption, kms_key_id.map(str::to_string))))
+        }
+        (_, Some(_)) => Err(RowFlowError::InvalidInput(
+            "A KMS key id can only be used with aws:kms encryption".to_string(),
+        )),
+    }
+}
+
+/// Parse a storage class name, rejecting values the SDK does not know
+fn parse_storage_class(value: &str) -> Result<StorageClass> {
+    let normalized = value.trim().to_ascii_uppercase();
+    if StorageClass::values().contains(&normalized.as_str()) {
+        Ok(StorageClass::from(normalized.as_str()))
+    } else {
+        Err(RowFlowError::InvalidInput(format!(
+            "Unknown S3 storage class '{}'; expected one of: {}",
+            value,
+            StorageClass::values().join(", ")
+        )))
+    }
+}
+
+/// Extract the total object size from a `Content-Range` header like `bytes 0-1023/5000`
+fn parse_content_range_total(content_range: &str) -> Option<i64> {
+    content_range.rsplit_once('/').and_then(|(_, total)| total.trim().parse().ok())
+}
+
+/// The `Content-Encoding` value for a compression
+pub(crate) fn content_encoding(compression: Compression) -> &'static str {
+    match compression {
+        Compression::Gzip => "gzip",
+        Compression::Zstd => "zstd",
+    }
+}
+
+/// The compression named by a `Content-Encoding` header, if it is one RowFlow can undo
+pub(crate) fn parse_content_encoding(value: &str) -> Option<Compression> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "gzip" | "x-gzip" => Some(Compression::Gzip),
+        "zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn compress_body(content: &[u8], compression: Compression) -> std::io::Result<Vec<u8>> {
+    match compression {
+        Compression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(content)?;
+            encoder.finish()
+        }
+        Compression::Zstd => zstd::encode_all(content, 0),
+    }
+}
+
+fn decompress_body(content: &[u8], compression: Compression) -> std::io::Result<Vec<u8>> {
+    match compression {
+        Compression::Gzip => {
+            let mut decompressed = Vec::new();
+            flate2::read::GzDecoder::new(content).read_to_end(&mut decompressed)?;
+            Ok(decompressed)
+        }
+        Compression::Zstd => zstd::decode_all(content),
+    }
+}
+
+/// Content type for a key from its extension, e.g. `text/csv` for `export.csv`
+///
+/// When the upload is compressed, a `.gz` or `.zst` extension is skipped so the type describes
+/// the content beneath the `Content-Encoding`.
+fn guess_content_type(key: &str, compressed: bool) -> Option<String> {
+    let key = if compressed {
+        key.strip_suffix(".gz").or_else(|| key.strip_suffix(".zst")).unwrap_or(key)
+    } else {
+        key
+    };
+    mime_guess::from_path(key).first().map(|mime| mime.to_string())
+}
+
+/// Decompresses an object body one chunk at a time, as it streams in
+pub(crate) enum StreamDecoder {
+    Gzip(flate2::write::GzDecoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+}
+
+impl StreamDecoder {
+    pub(crate) fn new(compression: Compression) -> Result<Self> {
+        Ok(match compression {
+            Compression::Gzip => Self::Gzip(flate2::write::GzDecoder::new(Vec::new())),
+            Compression::Zstd => Self::Zstd(
+                zstd::stream::write::Decoder::new(Vec::new()).map_err(decompression_error)?,
+            ),
+        })
+    }
+
+    /// Feed a compressed chunk, returning whatever it decompressed to so far
+    pub(crate) fn decode(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        let output = match self {
+            Self::Gzip(decoder) => {
+                decoder.write_all(chunk).map_err(decompression_error)?;
+                decoder.get_mut()
+            }
+            Self::Zstd(decoder) => {
+                decoder.write_all(chunk).map_err(decompression_error)?;
+                decoder.flush().map_err(decompression_error)?;
+                decoder.get_mut()
+            }
+        };
+        Ok(std::mem::take(output))
+    }
+
+    /// End the stream, returning the last of the decompressed data
+    pub(crate) fn finish(self) -> Result<Vec<u8>> {
+        match self {
+            Self::Gzip(decoder) => decoder.finish().map_err(decompression_error),
+            Self::Zstd(mut decoder) => {
+                decoder.flush().map_err(decompression_error)?;
+                Ok(decoder.into_inner())
+            }
+        }
+    }
+}
+
+/// Compresses an object body one chunk at a time, as it streams out
+pub(crate) enum StreamEncoder {
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl StreamEncoder {
+    pub(crate) fn new(compression: Compression) -> Result<Self> {
+        Ok(match compression {
+            Compression::Gzip => Self::Gzip(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::default(),
+            )),
+            Compression::Zstd => Self::Zstd(
+                zstd::stream::write::Encoder::new(Vec::new(), 0).map_err(compression_error)?,
+            ),
+        })
+    }
+
+    /// Feed a chunk, returning whatever it compressed to so far
+    pub(crate) fn encode(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        let output = match self {
+            Self::Gzip(encoder) => {
+                encoder.write_all(chunk).map_err(compression_error)?;
+                encoder.get_mut()
+            }
+            Self::Zstd(encoder) => {
+                encoder.write_all(chunk).map_err(compression_error)?;
+                encoder.get_mut()
+            }
+        };
+        Ok(std::mem::take(output))
+    }
+
+    /// End the stream, returning the last of the compressed data
+    pub(crate) fn finish(self) -> Result<Vec<u8>> {
+        match self {
+            Self::Gzip(encoder) => encoder.finish().map_err(compression_error),
+            Self::Zstd(encoder) => encoder.finish().map_err(compression_error),
+        }
+    }
+}
+
+fn compression_error(error: std::io::Error) -> RowFlowError {
+    RowFlowError::InternalError(format!("Failed to compress S3 object body: {}", error))
+}
+
+fn decompression_error(error: std::io::Error) -> RowFlowError {
+    RowFlowError::InternalError(format!("Failed to decompress S3 object body: {}", error))
+}
+
 /// Create S3 client from connection profile
 async fn create_s3_client(profile: &S3ConnectionProfile) -> Result<S3Client> {
     log::info!("Creating S3 client for bucket: {}", profile.bucket);
@@ -112,6 +375,16 @@ async fn create_s3_client(profile: &S3ConnectionProfile) -> Result<S3Client> {
         s3_config_builder = s3_config_builder.force_path_style(true);
     }
 
+    // Throttling, 5xx responses and dropped connections are retried with exponential backoff
+    // and jitter; client errors such as 403 or 404 fail on the first attempt
+    let retry_config = RetryConfig::standard()
+        .with_max_attempts(profile.max_retries.unwrap_or(DEFAULT_S3_MAX_RETRIES).saturating_add(1))
+        .with_initial_backoff(Duration::from_millis(
+            profile.retry_base_delay_ms.unwrap_or(DEFAULT_S3_RETRY_BASE_DELAY_MS),
+        ))
+        .with_max_backoff(MAX_S3_RETRY_DELAY);
+    s3_config_builder = s3_config_builder.retry_config(retry_config);
+
     let s3_config = s3_config_builder.build();
     let client = S3Client::from_conf(s3_config);
 
@@ -171,6 +444,9 @@ pub async fn test_s3_connection(profile: S3ConnectionProfile) -> Result<S3Bucket
 }
 
 /// List objects in S3 bucket
+///
+/// With `modified_after`, later pages are read until the page is full of matches or the
+/// listing ends, so a page only comes back short when nothing after it matches.
 #[tauri::command]
 pub async fn list_s3_objects(
     state: State<'_, AppState>,
@@ -184,65 +460,168 @@ pub async fn list_s3_objects(
     // Build prefix with path_prefix if set
     let prefix = build_effective_prefix(profile.path_prefix.as_ref(), request.prefix.as_ref());
 
-    let mut list_request = client.list_objects_v2().bucket(&profile.bucket);
+    let modified_after = request
+        .modified_after
+        .as_deref()
+        .map(|value| {
+            chrono::DateTime::parse_from_rfc3339(value).map_err(|e| {
+                RowFlowError::InvalidInput(format!("Invalid modifiedAfter timestamp: {}", e))
+            })
+        })
+        .transpose()?;
+
+    // ListObjectsV2 can't filter by time, so with `modified_after` pages are read and filtered
+    // here until the requested page size is matched or the listing ends. Each read asks for
+    // only the keys still missing, so the returned token resumes right after the last match.
+    let page_size = request.max_keys.unwrap_or(DEFAULT_LIST_MAX_KEYS).max(1);
+    let mut continuation_token = request.continuation_token.clone();
+    let mut objects = Vec::new();
+    let mut common_prefixes = Vec::new();
+
+    let is_truncated = loop {
+        let mut list_request = client
+            .list_objects_v2()
+            .bucket(&profile.bucket)
+            .set_prefix(prefix.clone())
+            .set_delimiter(request.delimiter.clone())
+            .set_continuation_token(continuation_token.take());
+
+        if modified_after.is_some() {
+            list_request = list_request.max_keys(page_size - objects.len() as i32);
+        } else if let Some(max_keys) = request.max_keys {
+            list_request = list_request.max_keys(max_keys);
+        }
 
-    if let Some(p) = &prefix {
-        list_request = list_request.prefix(p);
-    }
+        let result = list_request.send().await.map_err(|e| {
+            RowFlowError::InternalError(format!("Failed to list S3 objects: {}", e))
+        })?;
 
-    if let Some(d) = &request.delimiter {
-        list_request = list_request.delimiter(d);
-    }
+        objects.extend(
+            result
+                .contents()
+                .iter()
+                .filter(|obj| match (&modified_after, obj.last_modified()) {
+                    (Some(cutoff), Some(last_modified)) => {
+                        (last_modified.secs(), last_modified.subsec_nanos())
+                            > (cutoff.timestamp(), cutoff.timestamp_subsec_nanos())
+                    }
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                })
+                .map(|obj| {
+                    let key = obj.key().unwrap_or_default().to_string();
+                    let is_directory = key.ends_with('/');
+
+                    S3Object {
+                        key: key.clone(),
+                        size: obj.size().unwrap_or(0),
+                        last_modified: obj
+                            .last_modified()
+                            .map(|dt| dt.to_string())
+                            .unwrap_or_default(),
+                        etag: obj.e_tag().unwrap_or_default().to_string(),
+                        content_type: None, // ListObjects doesn't return content type
+                        storage_class: obj.storage_class().map(|sc| sc.as_str().to_string()),
+                        is_directory,
+                    }
+                }),
+        );
+
+        // Get common prefixes (directories)
+        common_prefixes
+            .extend(result.common_prefixes().iter().filter_map(|cp| cp.prefix().map(String::from)));
+
+        continuation_token = result.next_continuation_token().map(|t| t.to_string());
+        let is_truncated = result.is_truncated().unwrap_or(false) && continuation_token.is_some();
+        if modified_after.is_none() || !is_truncated || objects.len() as i32 >= page_size {
+            break is_truncated;
+        }
+    };
 
-    if let Some(max_keys) = request.max_keys {
-        list_request = list_request.max_keys(max_keys);
-    }
+    Ok(S3ListResult { objects, common_prefixes, is_truncated, continuation_token })
+}
 
-    if let Some(token) = &request.continuation_token {
-        list_request = list_request.continuation_token(token);
-    }
+/// List every version of the objects under a prefix, newest first for each key
+///
+/// Delete markers are included. Objects in a bucket that was never versioned come back as a
+/// single latest version whose version id is `null`.
+#[tauri::command]
+pub async fn list_s3_object_versions(
+    state: State<'_, AppState>,
+    connection_id: String,
+    prefix: Option<String>,
+) -> Result<Vec<S3ObjectVersion>> {
+    log::info!("Listing S3 object versions for connection: {}", connection_id);
 
-    let result = list_request
-        .send()
-        .await
-        .map_err(|e| RowFlowError::InternalError(format!("Failed to list S3 objects: {}", e)))?;
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
 
-    // Convert objects
-    let objects: Vec<S3Object> = result
-        .contents()
-        .iter()
-        .map(|obj| {
-            let key = obj.key().unwrap_or_default().to_string();
-            let is_directory = key.ends_with('/');
-
-            S3Object {
-                key: key.clone(),
-                size: obj.size().unwrap_or(0),
-                last_modified: obj.last_modified().map(|dt| dt.to_string()).unwrap_or_default(),
-                etag: obj.e_tag().unwrap_or_default().to_string(),
-                content_type: None, // ListObjects doesn't return content type
-                storage_class: obj.storage_class().map(|sc| sc.as_str().to_string()),
-                is_directory,
-            }
-        })
-        .collect();
+    let prefix = build_effective_prefix(profile.path_prefix.as_ref(), prefix.as_ref());
+
+    let mut versions = Vec::new();
+    let mut key_marker = None;
+    let mut version_id_marker = None;
+
+    loop {
+        let result = client
+            .list_object_versions()
+            .bucket(&profile.bucket)
+            .set_prefix(prefix.clone())
+            .set_key_marker(key_marker.take())
+            .set_version_id_marker(version_id_marker.take())
+            .send()
+            .await
+            .map_err(|e| {
+                RowFlowError::InternalError(format!("Failed to list S3 object versions: {}", e))
+            })?;
+
+        // Keep each timestamp's value for sorting; its text drops zero subseconds
+        versions.extend(result.versions().iter().map(|version| {
+            let modified = version.last_modified().map(|dt| (dt.secs(), dt.subsec_nanos()));
+            let version = S3ObjectVersion {
+                key: version.key().unwrap_or_default().to_string(),
+                version_id: version.version_id().map(|id| id.to_string()),
+                is_latest: version.is_latest().unwrap_or(false),
+                is_delete_marker: false,
+                size: version.size().unwrap_or(0),
+                last_modified: version.last_modified().map(|dt| dt.to_string()).unwrap_or_default(),
+                etag: version.e_tag().map(|etag| etag.to_string()),
+            };
+            (modified, version)
+        }));
+        versions.extend(result.delete_markers().iter().map(|marker| {
+            let modified = marker.last_modified().map(|dt| (dt.secs(), dt.subsec_nanos()));
+            let marker = S3ObjectVersion {
+                key: marker.key().unwrap_or_default().to_string(),
+                version_id: marker.version_id().map(|id| id.to_string()),
+                is_latest: marker.is_latest().unwrap_or(false),
+                is_delete_marker: true,
+                size: 0,
+                last_modified: marker.last_modified().map(|dt| dt.to_string()).unwrap_or_default(),
+                etag: None,
+            };
+            (modified, marker)
+        }));
+
+        key_marker = result.next_key_marker().map(|marker| marker.to_string());
+        version_id_marker = result.next_version_id_marker().map(|marker| marker.to_string());
+        if !result.is_truncated().unwrap_or(false) || key_marker.is_none() {
+            break;
+        }
+    }
 
-    // Get common prefixes (directories)
-    let common_prefixes: Vec<String> = result
-        .common_prefixes()
-        .iter()
-        .filter_map(|cp| cp.prefix().map(|p| p.to_string()))
-        .collect();
-
-    Ok(S3ListResult {
-        objects,
-        common_prefixes,
-        is_truncated: result.is_truncated().unwrap_or(false),
-        continuation_token: result.next_continuation_token().map(|t| t.to_string()),
-    })
+    // Versions and delete markers come back in separate lists
+    versions.sort_by(|(a_modified, a), (b_modified, b)| {
+        a.key.cmp(&b.key).then_with(|| b_modified.cmp(a_modified))
+    });
+
+    Ok(versions.into_iter().map(|(_, version)| version).collect())
 }
 
 /// Get S3 object content
+///
+/// Bodies stored with a gzip or zstd `Content-Encoding` are decompressed unless
+/// `keep_compressed` is set. Ranged reads are returned as stored, since part of a compressed
+/// stream cannot be decoded on its own.
 #[tauri::command]
 pub async fn get_s3_object(
     state: State<'_, AppState>,
@@ -255,22 +634,42 @@ pub async fn get_s3_object(
 
     let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &request.key);
 
-    let result = client
+    let mut get_request = client
         .get_object()
         .bucket(&profile.bucket)
         .key(&full_key)
+        .set_version_id(request.version_id.clone());
+
+    if let Some((start, end)) = request.range {
+        if start > end {
+            return Err(RowFlowError::InvalidInput(format!(
+                "Invalid byte range: start {} is after end {}",
+                start, end
+            )));
+        }
+        get_request = get_request.range(format!("bytes={}-{}", start, end));
+    }
+
+    let result = get_request
         .send()
         .await
         .map_err(|e| RowFlowError::InternalError(format!("Failed to get S3 object: {}", e)))?;
 
     // Extract metadata before consuming the body
     let content_type = result.content_type().map(|ct| ct.to_string());
-    let content_length = result.content_length().unwrap_or(0);
+    let mut content_encoding = result.content_encoding().map(|ce| ce.to_string());
+    let mut content_length = result.content_length().unwrap_or(0);
+    let total_size =
+        result.content_range().and_then(parse_content_range_total).unwrap_or(content_length);
     let last_modified = result.last_modified().map(|dt| dt.to_string());
     let etag = result.e_tag().map(|e| e.to_string());
+    let metadata: BTreeMap<String, String> = result
+        .metadata()
+        .map(|meta| meta.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
 
     // Read body into bytes
-    let content = result
+    let mut content = result
         .body
         .collect()
         .await
@@ -278,36 +677,241 @@ pub async fn get_s3_object(
         .into_bytes()
         .to_vec();
 
-    Ok(S3GetObjectResponse { content, content_type, content_length, last_modified, etag })
+    let compression = content_encoding.as_deref().and_then(parse_content_encoding);
+    if let Some(compression) = compression.filter(|_| !request.keep_compressed) {
+        if request.range.is_none() {
+            content = decompress_body(&content, compression).map_err(decompression_error)?;
+            content_length = content.len() as i64;
+            content_encoding = None;
+        }
+    }
+
+    Ok(S3GetObjectResponse {
+        content,
+        content_type,
+        content_encoding,
+        content_length,
+        total_size,
+        last_modified,
+        etag,
+        metadata,
+    })
+}
+
+/// Get S3 object details without downloading its content
+#[tauri::command]
+pub async fn head_s3_object(
+    state: State<'_, AppState>,
+    connection_id: String,
+    key: String,
+) -> Result<S3ObjectHead> {
+    log::info!("Getting S3 object details: {} for connection: {}", key, connection_id);
+
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
+
+    let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &key);
+
+    let result =
+        client.head_object().bucket(&profile.bucket).key(&full_key).send().await.map_err(|e| {
+            RowFlowError::InternalError(format!("Failed to get S3 object details: {}", e))
+        })?;
+
+    Ok(S3ObjectHead {
+        key: full_key,
+        content_type: result.content_type().map(|ct| ct.to_string()),
+        content_length: result.content_length().unwrap_or(0),
+        last_modified: result.last_modified().map(|dt| dt.to_string()),
+        etag: result.e_tag().map(|e| e.to_string()),
+        // HeadObject omits the storage class for STANDARD objects
+        storage_class: Some(
+            result
+                .storage_class()
+                .map(|sc| sc.as_str().to_string())
+                .unwrap_or_else(|| "STANDARD".to_string()),
+        ),
+        metadata: result
+            .metadata()
+            .map(|meta| meta.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// Check whether an object exists, without downloading it
+#[tauri::command]
+pub async fn s3_object_exists(
+    state: State<'_, AppState>,
+    connection_id: String,
+    key: String,
+) -> Result<bool> {
+    log::info!("Checking whether S3 object exists: {} for connection: {}", key, connection_id);
+
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
+
+    let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &key);
+
+    match client.head_object().bucket(&profile.bucket).key(&full_key).send().await {
+        Ok(_) => Ok(true),
+        Err(e) if e.raw_response().map(|response| response.status().as_u16()) == Some(404) => {
+            Ok(false)
+        }
+        Err(e) => Err(RowFlowError::InternalError(format!(
+            "Failed to check whether S3 object exists: {}",
+            e
+        ))),
+    }
 }
 
 /// Upload object to S3
+///
+/// Without a `content_type`, one is guessed from the key's extension.
 #[tauri::command]
 pub async fn put_s3_object(
     state: State<'_, AppState>,
     connection_id: String,
     request: S3PutObjectRequest,
-) -> Result<String> {
+) -> Result<S3PutObjectResponse> {
     log::info!("Uploading S3 object: {} for connection: {}", request.key, connection_id);
 
     let (client, profile) = state.get_s3_client(&connection_id).await?;
 
     let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &request.key);
 
-    let body = ByteStream::from(request.content);
+    let content = match request.compress {
+        Some(compression) => {
+            compress_body(&request.content, compression).map_err(compression_error)?
+        }
+        None => request.content,
+    };
+
+    let body = ByteStream::from(content);
 
     let mut put_request = client.put_object().bucket(&profile.bucket).key(&full_key).body(body);
 
-    if let Some(content_type) = &request.content_type {
+    if let Some(compression) = request.compress {
+        put_request = put_request.content_encoding(content_encoding(compression));
+    }
+
+    let content_type = request
+        .content_type
+        .clone()
+        .or_else(|| guess_content_type(&request.key, request.compress.is_some()));
+    if let Some(content_type) = &content_type {
         put_request = put_request.content_type(content_type);
     }
 
-    let result = put_request
+    if let Some(metadata) = &request.metadata {
+        for (key, value) in metadata {
+            put_request = put_request.metadata(key, value);
+        }
+    }
+
+    if let Some(tags) = request.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        put_request = put_request.tagging(encode_tagging(tags));
+    }
+
+    if let Some(storage_class) = &request.storage_class {
+        put_request = put_request.storage_class(parse_storage_class(storage_class)?);
+    }
+
+    if let Some((encryption, kms_key_id)) = server_side_encryption_options(
+        request.server_side_encryption.as_deref(),
+        request.sse_kms_key_id.as_deref(),
+    )? {
+        put_request = put_request.server_side_encryption(encryption).set_ssekms_key_id(kms_key_id);
+    }
+
+    if let Some(if_match) = &request.if_match {
+        put_request = put_request.if_match(if_match);
+    }
+
+    if let Some(if_none_match) = &request.if_none_match {
+        put_request = put_request.if_none_match(if_none_match);
+    }
+
+    let result = put_request.send().await.map_err(|e| {
+        if e.raw_response().map(|response| response.status().as_u16()) == Some(412) {
+            RowFlowError::PreconditionFailed(format!(
+                "S3 object {} was modified or already exists",
+                full_key
+            ))
+        } else {
+            RowFlowError::InternalError(format!("Failed to upload S3 object: {}", e))
+        }
+    })?;
+
+    Ok(S3PutObjectResponse {
+        etag: result.e_tag().unwrap_or_default().to_string(),
+        server_side_encryption: result.server_side_encryption().map(|sse| sse.as_str().to_string()),
+        sse_kms_key_id: result.ssekms_key_id().map(|id| id.to_string()),
+    })
+}
+
+/// Get the tag set of an S3 object
+#[tauri::command]
+pub async fn get_s3_object_tags(
+    state: State<'_, AppState>,
+    connection_id: String,
+    key: String,
+) -> Result<BTreeMap<String, String>> {
+    log::info!("Getting tags for S3 object: {} for connection: {}", key, connection_id);
+
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
+
+    let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &key);
+
+    let result =
+        client.get_object_tagging().bucket(&profile.bucket).key(&full_key).send().await.map_err(
+            |e| RowFlowError::InternalError(format!("Failed to get S3 object tags: {}", e)),
+        )?;
+
+    Ok(result
+        .tag_set()
+        .iter()
+        .map(|tag| (tag.key().to_string(), tag.value().to_string()))
+        .collect())
+}
+
+/// Replace the tag set of an S3 object
+#[tauri::command]
+pub async fn put_s3_object_tags(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: S3ObjectTagsRequest,
+) -> Result<()> {
+    log::info!("Updating tags for S3 object: {} for connection: {}", request.key, connection_id);
+
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
+
+    let full_key = build_full_s3_key(profile.path_prefix.as_ref(), &request.key);
+
+    let tag_set =
+        request
+            .tags
+            .iter()
+            .map(|(key, value)| {
+                Tag::builder().key(key).value(value).build().map_err(|e| {
+                    RowFlowError::InvalidInput(format!("Invalid S3 tag '{}': {}", key, e))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+    let tagging = Tagging::builder()
+        .set_tag_set(Some(tag_set))
+        .build()
+        .map_err(|e| RowFlowError::InternalError(format!("Failed to build S3 tag set: {}", e)))?;
+
+    client
+        .put_object_tagging()
+        .bucket(&profile.bucket)
+        .key(&full_key)
+        .tagging(tagging)
         .send()
         .await
-        .map_err(|e| RowFlowError::InternalError(format!("Failed to upload S3 object: {}", e)))?;
+        .map_err(|e| {
+            RowFlowError::InternalError(format!("Failed to update S3 object tags: {}", e))
+        })?;
 
-    Ok(result.e_tag().unwrap_or_default().to_string())
+    Ok(())
 }
 
 /// Delete objects from S3
@@ -345,6 +949,208 @@ pub async fn delete_s3_objects(
     Ok(S3DeleteResult { deleted, errors })
 }
 
+/// Count the objects under a prefix and sum their sizes, per storage class as well
+///
+/// Every page of the listing is fetched, emitting an `s3-prefix-stats-progress` event after
+/// each. Pass an `operation_id` to be able to stop a long count with `cancel_s3_operation`.
+/// Zero-byte "folder" markers are not counted.
+#[tauri::command]
+pub async fn get_s3_prefix_stats(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    connection_id: String,
+    prefix: Option<String>,
+    operation_id: Option<String>,
+) -> Result<S3PrefixStats> {
+    log::info!("Getting stats of S3 prefix {:?} for connection: {}", prefix, connection_id);
+
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
+
+    let effective_prefix = build_effective_prefix(profile.path_prefix.as_ref(), prefix.as_ref());
+    let operation_id = operation_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let token = state.register_s3_operation(&operation_id).await?;
+
+    let result = async {
+        let mut stats = S3PrefixStats {
+            prefix: effective_prefix.clone().unwrap_or_default(),
+            object_count: 0,
+            total_size: 0,
+            by_storage_class: BTreeMap::new(),
+        };
+        let mut continuation_token = None;
+        let mut pages = 0;
+
+        loop {
+            if token.is_cancelled() {
+                return Err(RowFlowError::JobCancelled(operation_id.clone()));
+            }
+
+            let page = client
+                .list_objects_v2()
+                .bucket(&profile.bucket)
+                .set_prefix(effective_prefix.clone())
+                .set_continuation_token(continuation_token.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    RowFlowError::InternalError(format!("Failed to list S3 objects: {}", e))
+                })?;
+            pages += 1;
+
+            for object in page.contents() {
+                let size = object.size().unwrap_or(0);
+                if size == 0 && object.key().is_some_and(|key| key.ends_with('/')) {
+                    continue;
+                }
+                // ListObjects omits the storage class for STANDARD objects on some services
+                let storage_class =
+                    object.storage_class().map_or("STANDARD", |class| class.as_str());
+                let class_stats = stats
+                    .by_storage_class
+                    .entry(storage_class.to_string())
+                    .or_insert(S3StorageClassStats { object_count: 0, total_size: 0 });
+                class_stats.object_count += 1;
+                class_stats.total_size += size;
+                stats.object_count += 1;
+                stats.total_size += size;
+            }
+
+            let _ = app.emit(
+                "s3-prefix-stats-progress",
+                serde_json::json!({
+                    "connectionId": connection_id,
+                    "operationId": operation_id,
+                    "prefix": stats.prefix,
+                    "objectCount": stats.object_count,
+                    "totalSize": stats.total_size,
+                    "pages": pages
+                }),
+            );
+
+            continuation_token = page.next_continuation_token().map(|t| t.to_string());
+            if !page.is_truncated().unwrap_or(false) || continuation_token.is_none() {
+                return Ok(stats);
+            }
+        }
+    }
+    .await;
+
+    state.finish_s3_operation(&operation_id).await;
+    result
+}
+
+/// Stop a running `get_s3_prefix_stats`; returns false when no such operation is running
+#[tauri::command]
+pub async fn cancel_s3_operation(state: State<'_, AppState>, operation_id: String) -> Result<bool> {
+    log::info!("Cancelling S3 operation: {}", operation_id);
+    Ok(state.cancel_s3_operation(&operation_id).await)
+}
+
+/// Rename an S3 "folder" by copying every object under the source prefix and deleting the originals
+#[tauri::command]
+pub async fn rename_s3_prefix(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: S3RenamePrefixRequest,
+) -> Result<S3RenamePrefixResult> {
+    log::info!(
+        "Renaming S3 prefix {} to {} for connection: {}",
+        request.source_prefix,
+        request.destination_prefix,
+        connection_id
+    );
+
+    let (client, profile) = state.get_s3_client(&connection_id).await?;
+
+    let source_prefix = build_folder_prefix(profile.path_prefix.as_ref(), &request.source_prefix)
+        .ok_or_else(|| {
+        RowFlowError::InvalidInput("Source prefix cannot be empty".to_string())
+    })?;
+    let destination_prefix =
+        build_folder_prefix(profile.path_prefix.as_ref(), &request.destination_prefix).ok_or_else(
+            || RowFlowError::InvalidInput("Destination prefix cannot be empty".to_string()),
+        )?;
+
+    if destination_prefix.starts_with(&source_prefix)
+        || source_prefix.starts_with(&destination_prefix)
+    {
+        return Err(RowFlowError::InvalidInput(
+            "Source and destination prefixes must not overlap".to_string(),
+        ));
+    }
+
+    // Collect the full key list up front so copies never show up in later pages
+    let mut source_keys = Vec::new();
+    let mut continuation_token: Option<String> = None;
+    loop {
+        let mut list_request =
+            client.list_objects_v2().bucket(&profile.bucket).prefix(&source_prefix);
+        if let Some(token) = &continuation_token {
+            list_request = list_request.continuation_token(token);
+        }
+
+        let result = list_request.send().await.map_err(|e| {
+            RowFlowError::InternalError(format!("Failed to list S3 objects: {}", e))
+        })?;
+
+        source_keys.extend(result.contents().iter().filter_map(|obj| obj.key().map(String::from)));
+
+        continuation_token = result.next_continuation_token().map(|t| t.to_string());
+        if !result.is_truncated().unwrap_or(false) || continuation_token.is_none() {
+            break;
+        }
+    }
+
+    let total = source_keys.len();
+    let mut moved = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, source_key) in source_keys.iter().enumerate() {
+        let destination_key =
+            format!("{}{}", destination_prefix, &source_key[source_prefix.len()..]);
+
+        let copy_result = client
+            .copy_object()
+            .bucket(&profile.bucket)
+            .copy_source(encode_copy_source(&profile.bucket, source_key))
+            .key(&destination_key)
+            .send()
+            .await;
+
+        match copy_result {
+            Ok(_) => {
+                match client.delete_object().bucket(&profile.bucket).key(source_key).send().await {
+                    Ok(_) => moved.push(destination_key.clone()),
+                    Err(e) => errors.push(S3DeleteError {
+                        key: source_key.clone(),
+                        code: "DeleteFailed".to_string(),
+                        message: format!("{}", e),
+                    }),
+                }
+            }
+            Err(e) => errors.push(S3DeleteError {
+                key: source_key.clone(),
+                code: "CopyFailed".to_string(),
+                message: format!("{}", e),
+            }),
+        }
+
+        let _ = app.emit(
+            "s3-rename-progress",
+            serde_json::json!({
+                "connectionId": connection_id,
+                "key": source_key,
+                "completed": index + 1,
+                "total": total,
+                "failed": errors.len()
+            }),
+        );
+    }
+
+    Ok(S3RenamePrefixResult { moved, errors })
+}
+
 /// Generate presigned URL for S3 object
 #[tauri::command]
 pub async fn get_s3_presigned_url(
@@ -368,6 +1174,7 @@ pub async fn get_s3_presigned_url(
         .get_object()
         .bucket(&profile.bucket)
         .key(&full_key)
+        .set_version_id(request.version_id.clone())
         .presigned(
             aws_sdk_s3::presigning::PresigningConfig::builder()
                 .expires_in(expires_in)
@@ -1,11 +1,18 @@
+use crate::commands::database::build_query_result;
+use crate::commands::script::{catalog_query_violation, is_volatile_default};
 use crate::error::{Result, RowFlowError};
 use crate::state::AppState;
 use crate::types::{
-    AddTableColumnRequest, Column, ColumnReference, Constraint, CreateSchemaRequest,
-    CreateTableRequest, DropSchemaRequest, DropTableColumnRequest, DropTableRequest, ForeignKey,
-    Index, RenameSchemaRequest, Schema, Table, TableColumnDefinition, TableStats,
+    AddTableColumnRequest, AlterTableOperation, AlterTableRequest, BloatEstimate, Column,
+    ColumnMismatch, ColumnReference, CompositeType, CompositeTypeAttribute, Constraint,
+    CreateSchemaRequest, CreateTableRequest, DatabaseFunction, DdlOperation, Dependency, Domain,
+    DomainConstraint, DropSchemaRequest, DropTableColumnRequest, DropTableRequest, ExpectedColumn,
+    Extension, ForeignKey, InboundForeignKey, Index, ObjectKind, QueryResult, RenameSchemaRequest,
+    Schema, Table, TableBloat, TableColumnDefinition, TableConstraintDefinition,
+    TableSchemaAssertion, TableStats, Trigger,
 };
 use std::collections::{BTreeMap, HashSet};
+use std::time::Instant;
 use tauri::State;
 
 /// Ensure the provided identifier is safe to use in generated SQL
@@ -35,6 +42,47 @@ pub(crate) fn quote_identifier(identifier: &str) -> String {
     quoted
 }
 
+/// The schema `table` lives in: `schema` when given, otherwise the first schema on the
+/// connection's search_path that has a relation by that name
+///
+/// Requests whose `schema` is optional resolve it here, so a table can be named the way it
+/// would be in SQL run on the connection. An empty schema counts as unset.
+pub(crate) async fn resolve_table_schema(
+    state: &AppState,
+    connection_id: &str,
+    schema: Option<&str>,
+    table: &str,
+) -> Result<String> {
+    if let Some(schema) = schema.filter(|schema| !schema.trim().is_empty()) {
+        return Ok(schema.to_string());
+    }
+    validate_identifier(table, "table")?;
+
+    let client = state.get_client(connection_id).await?;
+    let row = client
+        .query_opt(
+            r#"
+            SELECT s.nspname::text
+            FROM unnest(current_schemas(false)) WITH ORDINALITY AS s(nspname, position)
+            JOIN pg_namespace n ON n.nspname = s.nspname
+            JOIN pg_class c ON c.relnamespace = n.oid
+            WHERE c.relname = $1
+                AND c.relkind IN ('r', 'p', 'v', 'm', 'f')
+            ORDER BY s.position
+            LIMIT 1
+            "#,
+            &[&table],
+        )
+        .await?;
+
+    row.map(|row| row.get(0)).ok_or_else(|| {
+        RowFlowError::SchemaError(format!(
+            "Table '{}' was not found in any schema on the connection's search_path",
+            table
+        ))
+    })
+}
+
 /// Convenience helper for `schema.table` formatting with validation
 pub(crate) fn qualified_table_name(schema: &str, table: &str) -> Result<String> {
     validate_identifier(schema, "schema")?;
@@ -73,37 +121,126 @@ fn build_column_definition(
 }
 
 fn build_reference_clause(reference: &ColumnReference) -> Result<String> {
-    let table = reference.table.trim();
-    let column = reference.column.trim();
-    if table.is_empty() || column.is_empty() {
+    build_references_sql(
+        reference.schema.as_deref(),
+        &reference.table,
+        &[reference.column.as_str()],
+        reference.on_delete.as_deref(),
+        reference.on_update.as_deref(),
+    )
+}
+
+fn build_references_sql(
+    schema: Option<&str>,
+    table: &str,
+    columns: &[&str],
+    on_delete: Option<&str>,
+    on_update: Option<&str>,
+) -> Result<String> {
+    let table = table.trim();
+    let columns: Vec<&str> = columns.iter().map(|column| column.trim()).collect();
+    if table.is_empty() || columns.is_empty() || columns.iter().any(|column| column.is_empty()) {
         return Err(RowFlowError::SchemaError(
             "Foreign key requires target table and column".to_string(),
         ));
     }
 
-    if let Some(schema) = &reference.schema {
+    if let Some(schema) = schema {
         validate_identifier(schema, "schema")?;
     }
     validate_identifier(table, "table")?;
-    validate_identifier(column, "column")?;
+    for column in &columns {
+        validate_identifier(column, "column")?;
+    }
 
     let mut clause = String::from("REFERENCES ");
-    if let Some(schema) = &reference.schema {
+    if let Some(schema) = schema {
         clause.push_str(&format!("{}.", quote_identifier(schema)));
     }
-    clause.push_str(&format!("{}({})", quote_identifier(table), quote_identifier(column)));
+    clause.push_str(&format!(
+        "{}({})",
+        quote_identifier(table),
+        columns.iter().map(|column| quote_identifier(column)).collect::<Vec<_>>().join(", ")
+    ));
 
-    if let Some(on_delete) = reference.on_delete.as_ref().and_then(|s| parse_fk_action(s)) {
+    if let Some(on_delete) = on_delete.and_then(parse_fk_action) {
         clause.push_str(&format!(" ON DELETE {on_delete}"));
     }
 
-    if let Some(on_update) = reference.on_update.as_ref().and_then(|s| parse_fk_action(s)) {
+    if let Some(on_update) = on_update.and_then(parse_fk_action) {
         clause.push_str(&format!(" ON UPDATE {on_update}"));
     }
 
     Ok(clause)
 }
 
+fn build_table_constraint(constraint: &TableConstraintDefinition) -> Result<String> {
+    let mut clause = String::new();
+    if let Some(name) = constraint.name.as_deref().map(str::trim).filter(|name| !name.is_empty()) {
+        validate_identifier(name, "constraint")?;
+        clause.push_str(&format!("CONSTRAINT {} ", quote_identifier(name)));
+    }
+
+    for column in &constraint.columns {
+        validate_identifier(column, "column")?;
+    }
+    let column_list =
+        constraint.columns.iter().map(|column| quote_identifier(column)).collect::<Vec<_>>();
+
+    match constraint.constraint_type.trim().to_uppercase().as_str() {
+        "UNIQUE" => {
+            if column_list.is_empty() {
+                return Err(RowFlowError::SchemaError(
+                    "Unique constraint requires at least one column".to_string(),
+                ));
+            }
+            clause.push_str(&format!("UNIQUE ({})", column_list.join(", ")));
+        }
+        "CHECK" => {
+            let expression = constraint
+                .check_expression
+                .as_deref()
+                .map(str::trim)
+                .filter(|expression| !expression.is_empty())
+                .ok_or_else(|| {
+                    RowFlowError::SchemaError("Check constraint requires an expression".to_string())
+                })?;
+            clause.push_str(&format!("CHECK ({expression})"));
+        }
+        "FOREIGN KEY" => {
+            let reference = constraint.references.as_ref().ok_or_else(|| {
+                RowFlowError::SchemaError("Foreign key constraint requires a target".to_string())
+            })?;
+            if column_list.is_empty() || column_list.len() != reference.columns.len() {
+                return Err(RowFlowError::SchemaError(
+                    "Foreign key constraint must list the same number of local and referenced columns"
+                        .to_string(),
+                ));
+            }
+            let referenced_columns: Vec<&str> =
+                reference.columns.iter().map(String::as_str).collect();
+            clause.push_str(&format!(
+                "FOREIGN KEY ({}) {}",
+                column_list.join(", "),
+                build_references_sql(
+                    reference.schema.as_deref(),
+                    &reference.table,
+                    &referenced_columns,
+                    reference.on_delete.as_deref(),
+                    reference.on_update.as_deref(),
+                )?
+            ));
+        }
+        other => {
+            return Err(RowFlowError::SchemaError(format!(
+                "Unsupported table constraint type '{other}'; expected UNIQUE, CHECK or FOREIGN KEY"
+            )));
+        }
+    }
+
+    Ok(clause)
+}
+
 fn parse_fk_action(action: &str) -> Option<&'static str> {
     match action.to_uppercase().as_str() {
         "CASCADE" => Some("CASCADE"),
@@ -115,11 +252,16 @@ fn parse_fk_action(action: &str) -> Option<&'static str> {
     }
 }
 
-/// List all schemas in the database
+/// List schemas in the database, user schemas first
+///
+/// System schemas are included unless `include_system` is false. Per-session temporary schemas
+/// (`pg_temp_*` and `pg_toast_temp_*`) are only listed with `include_temporary`.
 #[tauri::command]
 pub async fn list_schemas(
     state: State<'_, AppState>,
     connection_id: String,
+    include_system: Option<bool>,
+    include_temporary: Option<bool>,
 ) -> Result<Vec<Schema>> {
     log::info!("Listing schemas for connection: {}", connection_id);
 
@@ -130,17 +272,21 @@ pub async fn list_schemas(
             n.nspname AS name,
             pg_catalog.pg_get_userbyid(n.nspowner) AS owner,
             CASE
-                WHEN n.nspname LIKE 'pg_%' OR n.nspname = 'information_schema' THEN true
+                WHEN n.nspname ~ '^pg_' OR n.nspname = 'information_schema' THEN true
                 ELSE false
             END AS is_system,
             pg_catalog.obj_description(n.oid, 'pg_namespace') AS description
         FROM pg_catalog.pg_namespace n
+        WHERE ($1::boolean OR NOT (n.nspname ~ '^pg_' OR n.nspname = 'information_schema'))
+            AND ($2::boolean OR n.nspname !~ '^pg_(toast_)?temp_')
         ORDER BY
             is_system ASC,
             n.nspname ASC
     "#;
 
-    let rows = client.query(query, &[]).await?;
+    let include_system = include_system.unwrap_or(true);
+    let include_temporary = include_temporary.unwrap_or(false);
+    let rows = client.query(query, &[&include_system, &include_temporary]).await?;
 
     let schemas = rows
         .iter()
@@ -179,8 +325,8 @@ pub async fn list_tables(
             pg_size_pretty(pg_total_relation_size(c.oid)) AS size,
             pg_catalog.obj_description(c.oid, 'pg_class') AS description
         FROM information_schema.tables t
-        LEFT JOIN pg_catalog.pg_class c ON c.relname = t.table_name
-        LEFT JOIN pg_catalog.pg_namespace n ON n.nspname = t.table_schema AND n.oid = c.relnamespace
+        LEFT JOIN pg_catalog.pg_namespace n ON n.nspname = t.table_schema
+        LEFT JOIN pg_catalog.pg_class c ON c.relname = t.table_name AND c.relnamespace = n.oid
         WHERE t.table_schema NOT IN ('pg_catalog', 'information_schema')
             AND ($1::text IS NULL OR t.table_schema = $1)
         ORDER BY t.table_schema, t.table_name
@@ -232,9 +378,18 @@ pub async fn get_table_columns(
     let query = r#"
         SELECT
             c.column_name,
-            c.data_type,
+            -- Arrays and user-defined types are only named by udt_name, e.g. _int4
+            CASE
+                WHEN c.data_type = 'ARRAY' THEN substr(c.udt_name::text, 2) || '[]'
+                WHEN c.data_type = 'USER-DEFINED' THEN c.udt_name::text
+                ELSE c.data_type
+            END AS data_type,
             c.is_nullable = 'YES' AS is_nullable,
             c.column_default,
+            c.is_identity = 'YES' AS is_identity,
+            c.identity_generation::text,
+            c.is_generated = 'ALWAYS' AS is_generated,
+            c.generation_expression::text,
             c.character_maximum_length,
             c.numeric_precision,
             c.numeric_scale,
@@ -326,25 +481,222 @@ pub async fn get_table_columns(
         .iter()
         .map(|row| Column {
             name: row.get(0),
-            data_type: row.get(1),
+            data_type: normalize_type_name(row.get(1)),
             is_nullable: row.get(2),
             column_default: row.get(3),
-            character_maximum_length: row.get(4),
-            numeric_precision: row.get(5),
-            numeric_scale: row.get(6),
-            is_primary_key: row.get(7),
-            is_unique: row.get(8),
-            is_foreign_key: row.get(9),
-            foreign_key_schema: row.get(11),
-            foreign_key_table: row.get(10),
-            foreign_key_column: row.get(12),
-            description: row.get(13),
+            is_identity: row.get(4),
+            identity_generation: row.get(5),
+            is_generated: row.get(6),
+            generation_expression: row.get(7),
+            character_maximum_length: row.get(8),
+            numeric_precision: row.get(9),
+            numeric_scale: row.get(10),
+            is_primary_key: row.get(11),
+            is_unique: row.get(12),
+            is_foreign_key: row.get(13),
+            foreign_key_schema: row.get(15),
+            foreign_key_table: row.get(14),
+            foreign_key_column: row.get(16),
+            description: row.get(17),
         })
         .collect();
 
     Ok(columns)
 }
 
+/// Whether the database fills a column in on insert, so generated or blank rows should leave
+/// it out
+///
+/// Identity and generated columns are known from the column metadata; any other column counts
+/// when its default is volatile, as for serials (`nextval`) or `created_at DEFAULT now()`.
+pub(crate) fn is_database_filled_column(column: &Column) -> bool {
+    column.is_identity
+        || column.is_generated
+        || column.column_default.as_deref().is_some_and(is_volatile_default)
+}
+
+/// Compare a table's columns with an expected list, e.g. to check a migration in CI
+///
+/// Types match when they name the same type under any common spelling, so `int4` matches
+/// `integer` and `varchar` matches `character varying`. A length, precision or scale in the
+/// expected type is checked as well; without one any is accepted.
+#[tauri::command]
+pub async fn assert_table_schema(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+    table: String,
+    expected: Vec<ExpectedColumn>,
+) -> Result<TableSchemaAssertion> {
+    log::info!("Asserting schema of table: {}.{} on connection: {}", schema, table, connection_id);
+
+    let columns = get_table_columns(state, connection_id, schema.clone(), table.clone()).await?;
+    if columns.is_empty() {
+        return Err(RowFlowError::SchemaError(format!("Table not found: {}.{}", schema, table)));
+    }
+
+    Ok(compare_table_schema(&expected, &columns))
+}
+
+fn compare_table_schema(expected: &[ExpectedColumn], columns: &[Column]) -> TableSchemaAssertion {
+    let expected_names: HashSet<&str> =
+        expected.iter().map(|column| column.name.as_str()).collect();
+    let mut missing_columns = Vec::new();
+    let mut mismatches = Vec::new();
+
+    for expected_column in expected {
+        let Some(column) = columns.iter().find(|column| column.name == expected_column.name) else {
+            missing_columns.push(expected_column.name.clone());
+            continue;
+        };
+
+        if !column_type_matches(&expected_column.data_type, column) {
+            mismatches.push(ColumnMismatch {
+                column: column.name.clone(),
+                field: "dataType".to_string(),
+                expected: expected_column.data_type.clone(),
+                actual: describe_column_type(column),
+            });
+        }
+        if let Some(is_nullable) = expected_column.is_nullable {
+            if is_nullable != column.is_nullable {
+                mismatches.push(ColumnMismatch {
+                    column: column.name.clone(),
+                    field: "isNullable".to_string(),
+                    expected: is_nullable.to_string(),
+                    actual: column.is_nullable.to_string(),
+                });
+            }
+        }
+    }
+
+    let extra_columns: Vec<String> = columns
+        .iter()
+        .filter(|column| !expected_names.contains(column.name.as_str()))
+        .map(|column| column.name.clone())
+        .collect();
+
+    TableSchemaAssertion {
+        matches: missing_columns.is_empty() && extra_columns.is_empty() && mismatches.is_empty(),
+        missing_columns,
+        extra_columns,
+        mismatches,
+    }
+}
+
+/// Alternate spellings of built-in types and the `information_schema` name each stands for
+///
+/// Covers the internal names `pg_type` reports (`int4`, `bpchar`), the SQL shorthands accepted
+/// in DDL (`int`, `decimal`) and the serial pseudo-types, which are stored as plain integers.
+const TYPE_NAME_ALIASES: &[(&str, &str)] = &[
+    ("int", "integer"),
+    ("int4", "integer"),
+    ("serial", "integer"),
+    ("serial4", "integer"),
+    ("int2", "smallint"),
+    ("smallserial", "smallint"),
+    ("serial2", "smallint"),
+    ("int8", "bigint"),
+    ("bigserial", "bigint"),
+    ("serial8", "bigint"),
+    ("float4", "real"),
+    ("float", "double precision"),
+    ("float8", "double precision"),
+    ("decimal", "numeric"),
+    ("bool", "boolean"),
+    ("varchar", "character varying"),
+    ("bpchar", "character"),
+    // The single-byte internal type, spelled `"char"` by information_schema
+    ("\"char\"", "char"),
+    ("varbit", "bit varying"),
+    ("timestamp", "timestamp without time zone"),
+    ("timestamptz", "timestamp with time zone"),
+    ("time", "time without time zone"),
+    ("timetz", "time with time zone"),
+];
+
+/// Built-in types whose `pg_type` name is already their `information_schema` spelling
+const BUILT_IN_TYPE_NAMES: &[&str] = &[
+    "text", "bytea", "date", "interval", "uuid", "json", "jsonb", "xml", "money", "inet", "cidr",
+    "macaddr", "macaddr8", "tsvector", "tsquery", "point", "line", "lseg", "box", "path",
+    "polygon", "circle", "bit", "oid", "name", "citext", "char",
+];
+
+/// Report a type under its `information_schema` spelling, without length or precision
+///
+/// Both `int4` and `integer` become `integer`, and `int4[]` becomes `integer[]`. A `pg_type`
+/// array name such as `_int4` is left alone, since user types may start with `_` too; callers
+/// that know a type is an array normalize its element instead. Built-in names come back
+/// lowercase; names that are not built in, such as enums, keep their case.
+pub(crate) fn normalize_type_name(type_name: &str) -> String {
+    let trimmed = type_name.trim();
+    if let Some(element) = trimmed.strip_suffix("[]") {
+        return format!("{}[]", normalize_type_name(element.trim_end_matches("[]")));
+    }
+
+    // Keep anything after the modifiers, as in `time(3) with time zone`
+    let without_modifiers = match trimmed.find('(') {
+        Some(open) => {
+            let close = trimmed[open..].find(')').map_or(trimmed.len(), |pos| open + pos + 1);
+            format!("{} {}", &trimmed[..open], &trimmed[close..])
+        }
+        None => trimmed.to_string(),
+    };
+    let base = without_modifiers.split_whitespace().collect::<Vec<_>>().join(" ");
+    let lowered = base.to_lowercase();
+
+    if let Some((_, canonical)) = TYPE_NAME_ALIASES.iter().find(|(alias, _)| *alias == lowered) {
+        return canonical.to_string();
+    }
+    if TYPE_NAME_ALIASES.iter().any(|(_, canonical)| *canonical == lowered)
+        || BUILT_IN_TYPE_NAMES.contains(&lowered.as_str())
+    {
+        return lowered;
+    }
+    base
+}
+
+/// Numbers inside the first pair of parentheses of a type name, e.g. `[10, 2]` for
+/// `numeric(10,2)`
+fn type_modifiers(type_name: &str) -> Vec<i32> {
+    let Some(open) = type_name.find('(') else {
+        return Vec::new();
+    };
+    let close = type_name[open..].find(')').map_or(type_name.len(), |pos| open + pos);
+    type_name[open + 1..close].split(',').filter_map(|part| part.trim().parse().ok()).collect()
+}
+
+fn column_type_matches(expected: &str, column: &Column) -> bool {
+    let actual = normalize_type_name(&column.data_type);
+    if !normalize_type_name(expected).eq_ignore_ascii_case(&actual) {
+        return false;
+    }
+
+    let modifiers = type_modifiers(expected);
+    match actual.as_str() {
+        "character varying" | "character" | "bit" | "bit varying" => {
+            modifiers.first().is_none_or(|length| column.character_maximum_length == Some(*length))
+        }
+        "numeric" => {
+            modifiers.first().is_none_or(|precision| column.numeric_precision == Some(*precision))
+                && modifiers.get(1).is_none_or(|scale| column.numeric_scale == Some(*scale))
+        }
+        _ => true,
+    }
+}
+
+/// A column's type with its length, precision and scale, as it would be written in DDL
+fn describe_column_type(column: &Column) -> String {
+    match (column.data_type.as_str(), column.character_maximum_length) {
+        (_, Some(length)) => format!("{}({})", column.data_type, length),
+        ("numeric", _) => match (column.numeric_precision, column.numeric_scale) {
+            (Some(precision), Some(scale)) => format!("numeric({},{})", precision, scale),
+            _ => "numeric".to_string(),
+        },
+        _ => column.data_type.clone(),
+    }
+}
+
 /// Get primary keys for a table
 #[tauri::command]
 pub async fn get_primary_keys(
@@ -450,36 +802,41 @@ pub async fn get_table_stats(
 
     let client = state.get_client(&connection_id).await?;
 
+    // Counters are zero until the table has been scanned or modified, and for relations such as
+    // views that have no row in pg_stat_user_tables at all
     let query = r#"
         SELECT
-            schemaname,
-            relname,
-            n_tup_ins + n_tup_upd + n_tup_del AS total_modifications,
-            pg_size_pretty(pg_total_relation_size((schemaname || '.' || relname)::regclass)) AS total_size,
-            pg_size_pretty(pg_relation_size((schemaname || '.' || relname)::regclass)) AS table_size,
-            pg_size_pretty(pg_total_relation_size((schemaname || '.' || relname)::regclass) -
-                          pg_relation_size((schemaname || '.' || relname)::regclass)) AS indexes_size,
-            pg_size_pretty(COALESCE(pg_total_relation_size(reltoastrelid), 0)) AS toast_size,
-            seq_scan,
-            seq_tup_read,
-            idx_scan,
-            idx_tup_fetch,
-            n_tup_ins,
-            n_tup_upd,
-            n_tup_del,
-            n_live_tup,
-            n_dead_tup,
-            TO_CHAR(last_vacuum, 'YYYY-MM-DD HH24:MI:SS') AS last_vacuum,
-            TO_CHAR(last_autovacuum, 'YYYY-MM-DD HH24:MI:SS') AS last_autovacuum,
-            TO_CHAR(last_analyze, 'YYYY-MM-DD HH24:MI:SS') AS last_analyze,
-            TO_CHAR(last_autoanalyze, 'YYYY-MM-DD HH24:MI:SS') AS last_autoanalyze
-        FROM pg_stat_user_tables
-        LEFT JOIN pg_class c ON c.relname = relname
-        WHERE schemaname = $1
-            AND relname = $2
+            n.nspname::text,
+            c.relname::text,
+            COALESCE(s.n_tup_ins + s.n_tup_upd + s.n_tup_del, 0) AS total_modifications,
+            pg_size_pretty(pg_total_relation_size(c.oid)) AS total_size,
+            pg_size_pretty(pg_relation_size(c.oid)) AS table_size,
+            pg_size_pretty(pg_total_relation_size(c.oid) - pg_relation_size(c.oid)) AS indexes_size,
+            pg_size_pretty(COALESCE(pg_total_relation_size(NULLIF(c.reltoastrelid, 0)), 0)) AS toast_size,
+            COALESCE(s.seq_scan, 0),
+            COALESCE(s.seq_tup_read, 0),
+            COALESCE(s.idx_scan, 0),
+            COALESCE(s.idx_tup_fetch, 0),
+            COALESCE(s.n_tup_ins, 0),
+            COALESCE(s.n_tup_upd, 0),
+            COALESCE(s.n_tup_del, 0),
+            COALESCE(s.n_live_tup, 0),
+            COALESCE(s.n_dead_tup, 0),
+            TO_CHAR(s.last_vacuum, 'YYYY-MM-DD HH24:MI:SS') AS last_vacuum,
+            TO_CHAR(s.last_autovacuum, 'YYYY-MM-DD HH24:MI:SS') AS last_autovacuum,
+            TO_CHAR(s.last_analyze, 'YYYY-MM-DD HH24:MI:SS') AS last_analyze,
+            TO_CHAR(s.last_autoanalyze, 'YYYY-MM-DD HH24:MI:SS') AS last_autoanalyze
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+        WHERE n.nspname = $1
+            AND c.relname = $2
+            AND c.relkind IN ('r', 'p', 'm', 'v', 'f')
     "#;
 
-    let row = client.query_one(query, &[&schema, &table]).await?;
+    let row = client.query_opt(query, &[&schema, &table]).await?.ok_or_else(|| {
+        RowFlowError::SchemaError(format!("Table not found: {}.{}", schema, table))
+    })?;
 
     Ok(TableStats {
         schema: row.get(0),
@@ -505,6 +862,240 @@ pub async fn get_table_stats(
     })
 }
 
+/// Estimate table bloat from planner statistics (the widely used ioguix estimation query).
+/// `$1` is the table oid.
+const TABLE_BLOAT_QUERY: &str = r#"
+    SELECT
+        tblname::text,
+        (bs * tblpages)::bigint AS real_size,
+        CASE WHEN tblpages - est_tblpages_ff > 0
+            THEN ((tblpages - est_tblpages_ff) * bs)::bigint
+            ELSE 0
+        END AS bloat_size,
+        CASE WHEN tblpages > 0 AND tblpages - est_tblpages_ff > 0
+            THEN (100 * (tblpages - est_tblpages_ff) / tblpages)::float8
+            ELSE 0
+        END AS bloat_percent,
+        is_na
+    FROM (
+        SELECT
+            ceil(reltuples / ((bs - page_hdr) * fillfactor / (tpl_size * 100)))
+                + ceil(toasttuples / 4) AS est_tblpages_ff,
+            tblpages, bs, tblname, is_na
+        FROM (
+            SELECT
+                (4 + tpl_hdr_size + tpl_data_size + (2 * ma)
+                    - CASE WHEN tpl_hdr_size % ma = 0 THEN ma ELSE tpl_hdr_size % ma END
+                    - CASE WHEN ceil(tpl_data_size)::int % ma = 0 THEN ma ELSE ceil(tpl_data_size)::int % ma END
+                ) AS tpl_size,
+                (heappages + toastpages) AS tblpages,
+                reltuples, toasttuples, bs, page_hdr, tblname, fillfactor, is_na
+            FROM (
+                SELECT
+                    tbl.relname AS tblname,
+                    GREATEST(tbl.reltuples, 0) AS reltuples,
+                    tbl.relpages AS heappages,
+                    COALESCE(toast.relpages, 0) AS toastpages,
+                    COALESCE(GREATEST(toast.reltuples, 0), 0) AS toasttuples,
+                    COALESCE(substring(array_to_string(tbl.reloptions, ' ') FROM 'fillfactor=([0-9]+)')::smallint, 100) AS fillfactor,
+                    current_setting('block_size')::numeric AS bs,
+                    CASE WHEN version() ~ 'mingw32' OR version() ~ '64-bit|x86_64|ppc64|ia64|amd64' THEN 8 ELSE 4 END AS ma,
+                    24 AS page_hdr,
+                    23 + CASE WHEN MAX(COALESCE(s.null_frac, 0)) > 0 THEN (7 + count(s.attname)) / 8 ELSE 0::int END AS tpl_hdr_size,
+                    sum((1 - COALESCE(s.null_frac, 0)) * COALESCE(s.avg_width, 0)) AS tpl_data_size,
+                    bool_or(att.atttypid = 'pg_catalog.name'::regtype)
+                        OR sum(CASE WHEN att.attnum > 0 THEN 1 ELSE 0 END) <> count(s.attname) AS is_na
+                FROM pg_catalog.pg_attribute att
+                JOIN pg_catalog.pg_class tbl ON tbl.oid = att.attrelid
+                JOIN pg_catalog.pg_namespace ns ON ns.oid = tbl.relnamespace
+                LEFT JOIN pg_catalog.pg_stats s
+                    ON s.schemaname = ns.nspname
+                    AND s.tablename = tbl.relname
+                    AND NOT s.inherited
+                    AND s.attname = att.attname
+                LEFT JOIN pg_catalog.pg_class toast ON toast.oid = tbl.reltoastrelid
+                WHERE tbl.oid = $1
+                    AND att.attnum > 0
+                    AND NOT att.attisdropped
+                    AND tbl.relkind IN ('r', 'm')
+                GROUP BY tbl.relname, tbl.reltuples, tbl.relpages, toast.relpages, toast.reltuples, tbl.reloptions
+            ) AS attribute_stats
+        ) AS tuple_stats
+    ) AS page_estimates
+"#;
+
+/// Estimate bloat of the btree indexes on a table (the ioguix estimation query). `$1` is the
+/// table oid.
+const INDEX_BLOAT_QUERY: &str = r#"
+    SELECT
+        idxname::text,
+        (bs * relpages)::bigint AS real_size,
+        CASE WHEN relpages > est_pages_ff
+            THEN (bs * (relpages - est_pages_ff))::bigint
+            ELSE 0
+        END AS bloat_size,
+        CASE WHEN relpages > est_pages_ff
+            THEN (100 * (relpages - est_pages_ff)::float8 / relpages)
+            ELSE 0
+        END AS bloat_percent,
+        is_na
+    FROM (
+        SELECT
+            COALESCE(1 + ceil(reltuples / floor((bs - pageopqdata - pagehdr) * fillfactor / (100 * (4 + nulldatahdrwidth)::float8))), 0) AS est_pages_ff,
+            bs, idxname, relpages, is_na
+        FROM (
+            SELECT
+                bs, idxname, reltuples, relpages, fillfactor, pagehdr, pageopqdata, is_na,
+                (index_tuple_hdr_bm
+                    + maxalign - CASE WHEN index_tuple_hdr_bm % maxalign = 0 THEN maxalign ELSE index_tuple_hdr_bm % maxalign END
+                    + nulldatawidth + maxalign - CASE
+                        WHEN nulldatawidth = 0 THEN 0
+                        WHEN nulldatawidth::integer % maxalign = 0 THEN maxalign
+                        ELSE nulldatawidth::integer % maxalign
+                    END
+                )::numeric AS nulldatahdrwidth
+            FROM (
+                SELECT
+                    i.idxname, i.reltuples, i.relpages, i.fillfactor,
+                    current_setting('block_size')::numeric AS bs,
+                    CASE WHEN version() ~ 'mingw32' OR version() ~ '64-bit|x86_64|ppc64|ia64|amd64' THEN 8 ELSE 4 END AS maxalign,
+                    24 AS pagehdr,
+                    16 AS pageopqdata,
+                    CASE WHEN max(COALESCE(s.null_frac, 0)) = 0 THEN 8 ELSE 8 + ((32 + 8 - 1) / 8) END AS index_tuple_hdr_bm,
+                    sum((1 - COALESCE(s.null_frac, 0)) * COALESCE(s.avg_width, 1024)) AS nulldatawidth,
+                    max(CASE WHEN i.atttypid = 'pg_catalog.name'::regtype THEN 1 ELSE 0 END) > 0 AS is_na
+                FROM (
+                    SELECT
+                        ic.idxname, ic.reltuples, ic.relpages, ic.fillfactor, ct.relnamespace,
+                        COALESCE(a1.attname, a2.attname) AS attname,
+                        COALESCE(a1.atttypid, a2.atttypid) AS atttypid,
+                        CASE WHEN a1.attnum IS NULL THEN ic.idxname ELSE ct.relname END AS attrelname
+                    FROM (
+                        SELECT
+                            ci.relname AS idxname,
+                            GREATEST(ci.reltuples, 0) AS reltuples,
+                            ci.relpages,
+                            i.indrelid AS tbloid,
+                            i.indexrelid AS idxoid,
+                            COALESCE(substring(array_to_string(ci.reloptions, ' ') FROM 'fillfactor=([0-9]+)')::smallint, 90) AS fillfactor,
+                            string_to_array(textin(int2vectorout(i.indkey)), ' ')::int[] AS indkey,
+                            generate_series(1, i.indnatts) AS attpos
+                        FROM pg_catalog.pg_index i
+                        JOIN pg_catalog.pg_class ci ON ci.oid = i.indexrelid
+                        WHERE i.indrelid = $1
+                            AND ci.relam = (SELECT oid FROM pg_catalog.pg_am WHERE amname = 'btree')
+                            AND ci.relpages > 0
+                    ) AS ic
+                    JOIN pg_catalog.pg_class ct ON ct.oid = ic.tbloid
+                    LEFT JOIN pg_catalog.pg_attribute a1
+                        ON ic.indkey[ic.attpos] <> 0
+                        AND a1.attrelid = ic.tbloid
+                        AND a1.attnum = ic.indkey[ic.attpos]
+                    LEFT JOIN pg_catalog.pg_attribute a2
+                        ON ic.indkey[ic.attpos] = 0
+                        AND a2.attrelid = ic.idxoid
+                        AND a2.attnum = ic.attpos
+                ) AS i
+                JOIN pg_catalog.pg_namespace n ON n.oid = i.relnamespace
+                JOIN pg_catalog.pg_stats s
+                    ON s.schemaname = n.nspname
+                    AND s.tablename = i.attrelname
+                    AND s.attname = i.attname
+                GROUP BY i.idxname, i.reltuples, i.relpages, i.fillfactor
+            ) AS index_stats
+        ) AS tuple_widths
+    ) AS page_estimates
+    ORDER BY idxname
+"#;
+
+fn bloat_estimate_from_row(row: &tokio_postgres::Row) -> BloatEstimate {
+    let is_na: bool = row.get(4);
+    BloatEstimate {
+        name: row.get(0),
+        real_size: row.get(1),
+        bloat_size: row.get(2),
+        bloat_percent: row.get(3),
+        reliable: !is_na,
+    }
+}
+
+/// Estimate bloat of a table and its btree indexes
+///
+/// These are estimates derived from planner statistics (`pg_stats`, `reltuples`, `relpages`), so
+/// they are only as fresh as the last ANALYZE and can differ noticeably from the exact figures
+/// `pgstattuple` reports after scanning the relation. Estimates flagged as unreliable involve
+/// columns without statistics or of type `name`.
+#[tauri::command]
+pub async fn get_table_bloat(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+    table: String,
+) -> Result<TableBloat> {
+    log::info!("Estimating bloat for table: {}.{} on connection: {}", schema, table, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let table_oid: u32 = client
+        .query_opt(
+            r#"
+            SELECT c.oid
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE n.nspname = $1
+                AND c.relname = $2
+            "#,
+            &[&schema, &table],
+        )
+        .await?
+        .ok_or_else(|| RowFlowError::SchemaError(format!("Table not found: {}.{}", schema, table)))?
+        .get(0);
+
+    let table_bloat = client
+        .query_opt(TABLE_BLOAT_QUERY, &[&table_oid])
+        .await?
+        .as_ref()
+        .map(bloat_estimate_from_row);
+    let indexes = client
+        .query(INDEX_BLOAT_QUERY, &[&table_oid])
+        .await?
+        .iter()
+        .map(bloat_estimate_from_row)
+        .collect();
+
+    Ok(TableBloat { schema, table, table_bloat, indexes })
+}
+
+/// Run a read-only query against `pg_catalog` and `information_schema` only
+///
+/// An escape hatch for introspection the other commands don't cover. The query is checked by
+/// `catalog_query_violation` and then runs in a read-only transaction with only `pg_catalog` on
+/// the search path, so unqualified names can't reach user tables either.
+#[tauri::command]
+pub async fn run_catalog_query(
+    state: State<'_, AppState>,
+    connection_id: String,
+    sql: String,
+) -> Result<QueryResult> {
+    log::info!("Running catalog query on connection: {}", connection_id);
+
+    if let Some(reason) = catalog_query_violation(&sql) {
+        return Err(RowFlowError::InvalidInput(reason));
+    }
+
+    let mut client = state.get_client(&connection_id).await?;
+    let transaction = client.build_transaction().read_only(true).start().await?;
+    transaction.batch_execute("SET LOCAL search_path = pg_catalog").await?;
+
+    let start = Instant::now();
+    let statement = transaction.prepare(&sql).await?;
+    let rows = transaction.query(&statement, &[]).await?;
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    transaction.rollback().await?;
+
+    Ok(build_query_result(statement.columns(), &rows, execution_time, false))
+}
+
 /// Get foreign keys for a table
 #[tauri::command]
 pub async fn get_foreign_keys(
@@ -526,23 +1117,27 @@ pub async fn get_foreign_keys(
         SELECT
             tc.constraint_name,
             ARRAY_AGG(kcu.column_name ORDER BY kcu.ordinal_position) AS columns,
-            ccu.table_schema AS foreign_schema,
-            ccu.table_name AS foreign_table,
-            ARRAY_AGG(ccu.column_name ORDER BY kcu.ordinal_position) AS foreign_columns,
+            fkcu.table_schema AS foreign_schema,
+            fkcu.table_name AS foreign_table,
+            ARRAY_AGG(fkcu.column_name ORDER BY kcu.ordinal_position) AS foreign_columns,
             rc.delete_rule AS on_delete,
             rc.update_rule AS on_update
         FROM information_schema.table_constraints tc
         JOIN information_schema.key_column_usage kcu
             ON tc.constraint_name = kcu.constraint_name
             AND tc.table_schema = kcu.table_schema
-        JOIN information_schema.constraint_column_usage ccu
-            ON ccu.constraint_name = tc.constraint_name
         JOIN information_schema.referential_constraints rc
             ON rc.constraint_name = tc.constraint_name
+            AND rc.constraint_schema = tc.table_schema
+        -- Pair each local column with its referenced column so composite keys line up
+        JOIN information_schema.key_column_usage fkcu
+            ON fkcu.constraint_name = rc.unique_constraint_name
+            AND fkcu.constraint_schema = rc.unique_constraint_schema
+            AND fkcu.ordinal_position = kcu.position_in_unique_constraint
         WHERE tc.constraint_type = 'FOREIGN KEY'
             AND tc.table_schema = $1
             AND tc.table_name = $2
-        GROUP BY tc.constraint_name, ccu.table_schema, ccu.table_name, rc.delete_rule, rc.update_rule
+        GROUP BY tc.constraint_name, fkcu.table_schema, fkcu.table_name, rc.delete_rule, rc.update_rule
         ORDER BY tc.constraint_name
     "#;
 
@@ -585,6 +1180,57 @@ pub async fn get_foreign_keys(
     Ok(foreign_keys)
 }
 
+/// Load the foreign keys on other tables that reference `schema.table`
+pub(crate) async fn fetch_inbound_foreign_keys(
+    client: &deadpool_postgres::Client,
+    schema: &str,
+    table: &str,
+) -> Result<Vec<InboundForeignKey>> {
+    let query = r#"
+        SELECT
+            tc.constraint_name,
+            tc.table_schema,
+            tc.table_name,
+            ARRAY_AGG(kcu.column_name ORDER BY kcu.ordinal_position) AS columns,
+            ARRAY_AGG(fkcu.column_name ORDER BY kcu.ordinal_position) AS referenced_columns,
+            rc.delete_rule AS on_delete,
+            rc.update_rule AS on_update
+        FROM information_schema.table_constraints tc
+        JOIN information_schema.key_column_usage kcu
+            ON tc.constraint_name = kcu.constraint_name
+            AND tc.table_schema = kcu.table_schema
+        JOIN information_schema.referential_constraints rc
+            ON rc.constraint_name = tc.constraint_name
+            AND rc.constraint_schema = tc.table_schema
+        JOIN information_schema.key_column_usage fkcu
+            ON fkcu.constraint_name = rc.unique_constraint_name
+            AND fkcu.constraint_schema = rc.unique_constraint_schema
+            AND fkcu.ordinal_position = kcu.position_in_unique_constraint
+        WHERE tc.constraint_type = 'FOREIGN KEY'
+            AND fkcu.table_schema = $1
+            AND fkcu.table_name = $2
+        GROUP BY tc.constraint_name, tc.table_schema, tc.table_name, rc.delete_rule, rc.update_rule
+        ORDER BY tc.table_schema, tc.table_name, tc.constraint_name
+    "#;
+
+    let rows = client.query(query, &[&schema, &table]).await?;
+
+    let foreign_keys = rows
+        .iter()
+        .map(|row| InboundForeignKey {
+            name: row.get(0),
+            schema: row.get(1),
+            table: row.get(2),
+            columns: row.get(3),
+            referenced_columns: row.get(4),
+            on_delete: row.get(5),
+            on_update: row.get(6),
+        })
+        .collect();
+
+    Ok(foreign_keys)
+}
+
 /// Get constraints for a table
 #[tauri::command]
 pub async fn get_constraints(
@@ -635,146 +1281,1025 @@ pub async fn get_constraints(
     Ok(constraints)
 }
 
-/// Create a new schema in the database
+/// Get triggers defined on a table
 #[tauri::command]
-pub async fn create_schema(
+pub async fn get_triggers(
     state: State<'_, AppState>,
     connection_id: String,
-    request: CreateSchemaRequest,
-) -> Result<()> {
-    log::info!("Creating schema: {} on connection: {}", request.name, connection_id);
+    schema: String,
+    table: String,
+) -> Result<Vec<Trigger>> {
+    log::info!("Getting triggers for table: {}.{} on connection: {}", schema, table, connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.name, "schema")?;
+    // tgtype is a bitmask: 1 = ROW, 2 = BEFORE, 64 = INSTEAD OF,
+    // 4 = INSERT, 8 = DELETE, 16 = UPDATE, 32 = TRUNCATE
+    let query = r#"
+        SELECT
+            t.tgname AS name,
+            CASE
+                WHEN t.tgtype & 2 = 2 THEN 'BEFORE'
+                WHEN t.tgtype & 64 = 64 THEN 'INSTEAD OF'
+                ELSE 'AFTER'
+            END AS timing,
+            ARRAY_REMOVE(ARRAY[
+                CASE WHEN t.tgtype & 4 = 4 THEN 'INSERT' END,
+                CASE WHEN t.tgtype & 16 = 16 THEN 'UPDATE' END,
+                CASE WHEN t.tgtype & 8 = 8 THEN 'DELETE' END,
+                CASE WHEN t.tgtype & 32 = 32 THEN 'TRUNCATE' END
+            ], NULL) AS events,
+            CASE WHEN t.tgtype & 1 = 1 THEN 'ROW' ELSE 'STATEMENT' END AS level,
+            pn.nspname AS function_schema,
+            p.proname AS function_name,
+            t.tgenabled <> 'D' AS enabled,
+            pg_catalog.pg_get_triggerdef(t.oid) AS definition
+        FROM pg_catalog.pg_trigger t
+        JOIN pg_catalog.pg_class c ON c.oid = t.tgrelid
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        JOIN pg_catalog.pg_proc p ON p.oid = t.tgfoid
+        JOIN pg_catalog.pg_namespace pn ON pn.oid = p.pronamespace
+        WHERE n.nspname = $1
+            AND c.relname = $2
+            AND NOT t.tgisinternal
+        ORDER BY t.tgname
+    "#;
 
-    let if_not_exists = if request.if_not_exists { "IF NOT EXISTS " } else { "" };
-    let sql = format!("CREATE SCHEMA {}{};", if_not_exists, quote_identifier(&request.name));
+    let rows = client.query(query, &[&schema, &table]).await?;
 
-    client.batch_execute(&sql).await?;
+    let triggers = rows
+        .iter()
+        .map(|row| Trigger {
+            name: row.get(0),
+            timing: row.get(1),
+            events: row.get(2),
+            level: row.get(3),
+            function_schema: row.get(4),
+            function_name: row.get(5),
+            enabled: row.get(6),
+            definition: row.get(7),
+        })
+        .collect();
 
-    Ok(())
+    Ok(triggers)
 }
 
-/// Drop an existing schema
+/// List extensions installed in the database
 #[tauri::command]
-pub async fn drop_schema(
+pub async fn list_extensions(
     state: State<'_, AppState>,
     connection_id: String,
-    request: DropSchemaRequest,
-) -> Result<()> {
-    log::info!("Dropping schema: {} on connection: {}", request.name, connection_id);
+) -> Result<Vec<Extension>> {
+    log::info!("Listing extensions on connection: {}", connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.name, "schema")?;
+    fetch_extensions(&client).await
+}
 
-    let if_exists = if request.if_exists { "IF EXISTS " } else { "" };
-    let cascade = if request.cascade { " CASCADE" } else { "" };
-    let sql = format!("DROP SCHEMA {}{}{};", if_exists, quote_identifier(&request.name), cascade);
+pub(crate) async fn fetch_extensions(client: &deadpool_postgres::Client) -> Result<Vec<Extension>> {
+    let query = r#"
+        SELECT
+            e.extname::text,
+            e.extversion,
+            n.nspname::text,
+            pg_catalog.obj_description(e.oid, 'pg_extension') AS description
+        FROM pg_catalog.pg_extension e
+        JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+        ORDER BY e.extname
+    "#;
 
-    client.batch_execute(&sql).await?;
+    let rows = client.query(query, &[]).await?;
 
-    Ok(())
+    let extensions = rows
+        .iter()
+        .map(|row| Extension {
+            name: row.get(0),
+            version: row.get(1),
+            schema: row.get(2),
+            description: row.get(3),
+        })
+        .collect();
+
+    Ok(extensions)
 }
 
-/// Rename an existing schema
+/// List functions and procedures in a schema
 #[tauri::command]
-pub async fn rename_schema(
+pub async fn list_functions(
     state: State<'_, AppState>,
     connection_id: String,
-    request: RenameSchemaRequest,
-) -> Result<()> {
-    log::info!(
-        "Renaming schema: {} -> {} on connection: {}",
-        request.current_name,
-        request.new_name,
-        connection_id
-    );
+    schema: String,
+    include_internal: Option<bool>,
+) -> Result<Vec<DatabaseFunction>> {
+    log::info!("Listing functions in schema: {} on connection: {}", schema, connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.current_name, "schema")?;
-    validate_identifier(&request.new_name, "schema")?;
+    // By default hide C/internal functions and anything installed by an extension
+    let query = r#"
+        SELECT
+            p.oid,
+            n.nspname AS schema,
+            p.proname AS name,
+            pg_catalog.pg_get_function_identity_arguments(p.oid) AS arguments,
+            ARRAY(
+                SELECT pg_catalog.format_type(a.type_oid, NULL)
+                FROM unnest(p.proargtypes::oid[]) WITH ORDINALITY AS a(type_oid, ordinality)
+                ORDER BY a.ordinality
+            ) AS argument_types,
+            pg_catalog.pg_get_function_result(p.oid) AS return_type,
+            l.lanname AS language,
+            CASE p.provolatile
+                WHEN 'i' THEN 'IMMUTABLE'
+                WHEN 's' THEN 'STABLE'
+                ELSE 'VOLATILE'
+            END AS volatility,
+            CASE p.prokind
+                WHEN 'p' THEN 'PROCEDURE'
+                WHEN 'a' THEN 'AGGREGATE'
+                WHEN 'w' THEN 'WINDOW'
+                ELSE 'FUNCTION'
+            END AS kind,
+            pg_catalog.obj_description(p.oid, 'pg_proc') AS description
+        FROM pg_catalog.pg_proc p
+        JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
+        JOIN pg_catalog.pg_language l ON l.oid = p.prolang
+        WHERE n.nspname = $1
+            AND (
+                $2::boolean
+                OR (
+                    l.lanname NOT IN ('internal', 'c')
+                    AND NOT EXISTS (
+                        SELECT 1
+                        FROM pg_catalog.pg_depend d
+                        WHERE d.classid = 'pg_catalog.pg_proc'::regclass
+                            AND d.objid = p.oid
+                            AND d.deptype = 'e'
+                    )
+                )
+            )
+        ORDER BY p.proname, arguments
+    "#;
 
-    let sql = format!(
-        "ALTER SCHEMA {} RENAME TO {};",
-        quote_identifier(&request.current_name),
-        quote_identifier(&request.new_name)
-    );
+    let include_internal = include_internal.unwrap_or(false);
+    let rows = client.query(query, &[&schema, &include_internal]).await?;
 
-    client.batch_execute(&sql).await?;
+    let functions = rows
+        .iter()
+        .map(|row| DatabaseFunction {
+            oid: row.get(0),
+            schema: row.get(1),
+            name: row.get(2),
+            arguments: row.get(3),
+            argument_types: row.get(4),
+            return_type: row.get(5),
+            language: row.get(6),
+            volatility: row.get(7),
+            kind: row.get(8),
+            description: row.get(9),
+        })
+        .collect();
 
-    Ok(())
+    Ok(functions)
 }
 
-/// Create a new table using the provided column definitions
+/// List composite types in a schema with their attributes
+///
+/// Only standalone types from `CREATE TYPE ... AS (...)` are listed, not the row types every
+/// table and view also gets.
 #[tauri::command]
-pub async fn create_table(
+pub async fn list_composite_types(
     state: State<'_, AppState>,
     connection_id: String,
-    request: CreateTableRequest,
-) -> Result<()> {
-    log::info!(
-        "Creating table: {}.{} on connection: {}",
-        request.schema,
-        request.table_name,
-        connection_id
-    );
+    schema: String,
+) -> Result<Vec<CompositeType>> {
+    log::info!("Listing composite types in schema: {} on connection: {}", schema, connection_id);
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.schema, "schema")?;
-    validate_identifier(&request.table_name, "table")?;
+    let query = r#"
+        SELECT
+            t.oid,
+            n.nspname::text,
+            t.typname::text,
+            ARRAY(
+                SELECT a.attname::text
+                FROM pg_catalog.pg_attribute a
+                WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                ORDER BY a.attnum
+            ) AS attribute_names,
+            ARRAY(
+                SELECT pg_catalog.format_type(a.atttypid, a.atttypmod)
+                FROM pg_catalog.pg_attribute a
+                WHERE a.attrelid = t.typrelid AND a.attnum > 0 AND NOT a.attisdropped
+                ORDER BY a.attnum
+            ) AS attribute_types,
+            pg_catalog.obj_description(t.oid, 'pg_type') AS description
+        FROM pg_catalog.pg_type t
+        JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+        JOIN pg_catalog.pg_class c ON c.oid = t.typrelid
+        WHERE n.nspname = $1
+            AND t.typtype = 'c'
+            AND c.relkind = 'c'
+        ORDER BY t.typname
+    "#;
 
-    if request.columns.is_empty() {
-        return Err(RowFlowError::SchemaError("Cannot create a table without columns".to_string()));
-    }
+    let rows = client.query(query, &[&schema]).await?;
 
-    let mut seen_columns = HashSet::new();
-    let primary_key_columns: Vec<String> = request
-        .columns
+    let types = rows
         .iter()
-        .filter(|col| col.is_primary_key)
-        .map(|col| col.name.clone())
+        .map(|row| {
+            let names: Vec<String> = row.get(3);
+            let data_types: Vec<String> = row.get(4);
+            CompositeType {
+                oid: row.get(0),
+                schema: row.get(1),
+                name: row.get(2),
+                attributes: names
+                    .into_iter()
+                    .zip(data_types)
+                    .map(|(name, data_type)| CompositeTypeAttribute { name, data_type })
+                    .collect(),
+                description: row.get(5),
+            }
+        })
         .collect();
 
-    let inline_primary_key = primary_key_columns.len() <= 1;
+    Ok(types)
+}
 
-    let mut column_definitions = Vec::with_capacity(request.columns.len());
-    for column in &request.columns {
-        let lowered = column.name.to_lowercase();
-        if !seen_columns.insert(lowered) {
-            return Err(RowFlowError::SchemaError(format!(
-                "Duplicate column name '{}' in create table request",
-                column.name
-            )));
-        }
+/// List domains in a schema with their base type, default and constraints
+#[tauri::command]
+pub async fn list_domains(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+) -> Result<Vec<Domain>> {
+    log::info!("Listing domains in schema: {} on connection: {}", schema, connection_id);
 
-        column_definitions.push(build_column_definition(column, inline_primary_key)?);
-    }
+    let client = state.get_client(&connection_id).await?;
 
-    if !primary_key_columns.is_empty() && !inline_primary_key {
-        let pk_clause = format!(
-            "PRIMARY KEY ({})",
-            primary_key_columns
-                .iter()
-                .map(|name| quote_identifier(name))
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
-        column_definitions.push(pk_clause);
-    }
+    let query = r#"
+        SELECT
+            t.oid,
+            n.nspname::text,
+            t.typname::text,
+            pg_catalog.format_type(t.typbasetype, t.typtypmod) AS base_type,
+            t.typnotnull,
+            t.typdefault,
+            ARRAY(
+                SELECT con.conname::text
+                FROM pg_catalog.pg_constraint con
+                WHERE con.contypid = t.oid AND con.contype = 'c'
+                ORDER BY con.conname
+            ) AS constraint_names,
+            ARRAY(
+                SELECT pg_catalog.pg_get_constraintdef(con.oid, true)
+                FROM pg_catalog.pg_constraint con
+                WHERE con.contypid = t.oid AND con.contype = 'c'
+                ORDER BY con.conname
+            ) AS constraint_definitions,
+            pg_catalog.obj_description(t.oid, 'pg_type') AS description
+        FROM pg_catalog.pg_type t
+        JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+        WHERE n.nspname = $1
+            AND t.typtype = 'd'
+        ORDER BY t.typname
+    "#;
+
+    let rows = client.query(query, &[&schema]).await?;
+
+    let domains = rows
+        .iter()
+        .map(|row| {
+            let names: Vec<String> = row.get(6);
+            let definitions: Vec<String> = row.get(7);
+            Domain {
+                oid: row.get(0),
+                schema: row.get(1),
+                name: row.get(2),
+                base_type: row.get(3),
+                not_null: row.get(4),
+                default: row.get(5),
+                constraints: names
+                    .into_iter()
+                    .zip(definitions)
+                    .map(|(name, definition)| DomainConstraint { name, definition })
+                    .collect(),
+                description: row.get(8),
+            }
+        })
+        .collect();
+
+    Ok(domains)
+}
+
+/// Get the full `CREATE FUNCTION` source for a function by oid
+#[tauri::command]
+pub async fn get_function_definition(
+    state: State<'_, AppState>,
+    connection_id: String,
+    oid: u32,
+) -> Result<String> {
+    log::info!("Getting definition for function oid {} on connection: {}", oid, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let row = client
+        .query_opt("SELECT pg_catalog.pg_get_functiondef(p.oid) FROM pg_catalog.pg_proc p WHERE p.oid = $1", &[&oid])
+        .await?
+        .ok_or_else(|| RowFlowError::SchemaError(format!("Function with oid {} not found", oid)))?;
+
+    Ok(row.get(0))
+}
+
+/// Get the DDL that recreates a constraint, index, view or sequence, looked up by name
+///
+/// Constraint names are only unique per table, so a constraint name shared by several tables
+/// returns one `ALTER TABLE ... ADD CONSTRAINT` statement for each.
+#[tauri::command]
+pub async fn get_object_definition(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+    kind: ObjectKind,
+    name: String,
+) -> Result<String> {
+    let label = match kind {
+        ObjectKind::Constraint => "constraint",
+        ObjectKind::Index => "index",
+        ObjectKind::View => "view",
+        ObjectKind::Sequence => "sequence",
+    };
+    validate_identifier(&name, label)?;
+    let qualified_name = qualified_table_name(&schema, &name)?;
+    log::info!(
+        "Getting definition of {} {} on connection: {}",
+        label,
+        qualified_name,
+        connection_id
+    );
+
+    let client = state.get_client(&connection_id).await?;
+
+    let statements: Vec<String> = match kind {
+        ObjectKind::Constraint => {
+            let query = r#"
+                SELECT c.relname::text, pg_catalog.pg_get_constraintdef(con.oid, true)
+                FROM pg_catalog.pg_constraint con
+                JOIN pg_catalog.pg_class c ON c.oid = con.conrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = con.connamespace
+                WHERE n.nspname = $1
+                    AND con.conname = $2
+                ORDER BY c.relname
+            "#;
+            let mut statements = Vec::new();
+            for row in client.query(query, &[&schema, &name]).await? {
+                let table: String = row.get(0);
+                let definition: String = row.get(1);
+                statements.push(format!(
+                    "ALTER TABLE {} ADD CONSTRAINT {} {}",
+                    qualified_table_name(&schema, &table)?,
+                    quote_identifier(&name),
+                    definition
+                ));
+            }
+            statements
+        }
+        ObjectKind::Index => {
+            let query = r#"
+                SELECT pg_catalog.pg_get_indexdef(c.oid)
+                FROM pg_catalog.pg_class c
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE n.nspname = $1
+                    AND c.relname = $2
+                    AND c.relkind IN ('i', 'I')
+            "#;
+            client.query(query, &[&schema, &name]).await?.iter().map(|row| row.get(0)).collect()
+        }
+        ObjectKind::View => {
+            let query = r#"
+                SELECT c.relkind = 'm', pg_catalog.pg_get_viewdef(c.oid, true)
+                FROM pg_catalog.pg_class c
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE n.nspname = $1
+                    AND c.relname = $2
+                    AND c.relkind IN ('v', 'm')
+            "#;
+            client
+                .query(query, &[&schema, &name])
+                .await?
+                .iter()
+                .map(|row| {
+                    let materialized: bool = row.get(0);
+                    let definition: String = row.get(1);
+                    let definition = definition.trim().trim_end_matches(';');
+                    if materialized {
+                        format!("CREATE MATERIALIZED VIEW {} AS\n{}", qualified_name, definition)
+                    } else {
+                        format!("CREATE OR REPLACE VIEW {} AS\n{}", qualified_name, definition)
+                    }
+                })
+                .collect()
+        }
+        ObjectKind::Sequence => {
+            let query = r#"
+                SELECT
+                    pg_catalog.format_type(s.seqtypid, NULL),
+                    s.seqstart,
+                    s.seqincrement,
+                    s.seqmin,
+                    s.seqmax,
+                    s.seqcache,
+                    s.seqcycle
+                FROM pg_catalog.pg_sequence s
+                JOIN pg_catalog.pg_class c ON c.oid = s.seqrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE n.nspname = $1
+                    AND c.relname = $2
+            "#;
+            client
+                .query(query, &[&schema, &name])
+                .await?
+                .iter()
+                .map(|row| {
+                    let cycle: bool = row.get(6);
+                    format!(
+                        "CREATE SEQUENCE {} AS {} START WITH {} INCREMENT BY {} MINVALUE {} \
+                         MAXVALUE {} CACHE {}{}",
+                        qualified_name,
+                        row.get::<_, String>(0),
+                        row.get::<_, i64>(1),
+                        row.get::<_, i64>(2),
+                        row.get::<_, i64>(3),
+                        row.get::<_, i64>(4),
+                        row.get::<_, i64>(5),
+                        if cycle { " CYCLE" } else { "" }
+                    )
+                })
+                .collect()
+        }
+    };
+
+    if statements.is_empty() {
+        return Err(RowFlowError::SchemaError(format!("No {} named {}", label, qualified_name)));
+    }
+
+    Ok(statements.iter().map(|statement| format!("{};", statement)).collect::<Vec<_>>().join("\n"))
+}
+
+/// List objects that depend on a table and would be affected by dropping it
+#[tauri::command]
+pub async fn get_table_dependencies(
+    state: State<'_, AppState>,
+    connection_id: String,
+    schema: String,
+    table: String,
+) -> Result<Vec<Dependency>> {
+    log::info!(
+        "Getting dependencies for table: {}.{} on connection: {}",
+        schema,
+        table,
+        connection_id
+    );
+
+    let client = state.get_client(&connection_id).await?;
+
+    let query = r#"
+        WITH target AS (
+            SELECT c.oid
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE n.nspname = $1
+                AND c.relname = $2
+        )
+        SELECT DISTINCT
+            CASE dc.relkind WHEN 'm' THEN 'MATERIALIZED VIEW' ELSE 'VIEW' END AS kind,
+            dn.nspname AS schema,
+            dc.relname AS name,
+            NULL::text AS detail
+        FROM pg_catalog.pg_depend d
+        JOIN pg_catalog.pg_rewrite r ON r.oid = d.objid
+        JOIN pg_catalog.pg_class dc ON dc.oid = r.ev_class
+        JOIN pg_catalog.pg_namespace dn ON dn.oid = dc.relnamespace
+        WHERE d.classid = 'pg_catalog.pg_rewrite'::regclass
+            AND d.refobjid = (SELECT oid FROM target)
+            AND dc.oid <> (SELECT oid FROM target)
+        UNION ALL
+        SELECT
+            'TRIGGER',
+            $1,
+            t.tgname,
+            pn.nspname || '.' || p.proname || '()'
+        FROM pg_catalog.pg_trigger t
+        JOIN pg_catalog.pg_proc p ON p.oid = t.tgfoid
+        JOIN pg_catalog.pg_namespace pn ON pn.oid = p.pronamespace
+        WHERE t.tgrelid = (SELECT oid FROM target)
+            AND NOT t.tgisinternal
+        UNION ALL
+        SELECT DISTINCT
+            'FUNCTION',
+            pn.nspname,
+            p.proname,
+            pg_catalog.pg_get_function_identity_arguments(p.oid)
+        FROM pg_catalog.pg_depend d
+        JOIN pg_catalog.pg_proc p ON p.oid = d.objid
+        JOIN pg_catalog.pg_namespace pn ON pn.oid = p.pronamespace
+        WHERE d.classid = 'pg_catalog.pg_proc'::regclass
+            AND d.refobjid = (SELECT oid FROM target)
+        UNION ALL
+        SELECT
+            'SEQUENCE',
+            sn.nspname,
+            s.relname,
+            'owned by column ' || a.attname
+        FROM pg_catalog.pg_depend d
+        JOIN pg_catalog.pg_class s ON s.oid = d.objid AND s.relkind = 'S'
+        JOIN pg_catalog.pg_namespace sn ON sn.oid = s.relnamespace
+        JOIN pg_catalog.pg_attribute a ON a.attrelid = d.refobjid AND a.attnum = d.refobjsubid
+        WHERE d.classid = 'pg_catalog.pg_class'::regclass
+            AND d.refobjid = (SELECT oid FROM target)
+            AND d.deptype IN ('a', 'i')
+        UNION ALL
+        SELECT
+            'FOREIGN KEY',
+            cn.nspname,
+            con.conname,
+            cn.nspname || '.' || cc.relname
+        FROM pg_catalog.pg_constraint con
+        JOIN pg_catalog.pg_class cc ON cc.oid = con.conrelid
+        JOIN pg_catalog.pg_namespace cn ON cn.oid = cc.relnamespace
+        WHERE con.contype = 'f'
+            AND con.confrelid = (SELECT oid FROM target)
+            AND con.conrelid <> (SELECT oid FROM target)
+        ORDER BY 1, 2, 3
+    "#;
+
+    let rows = client.query(query, &[&schema, &table]).await?;
+
+    let dependencies = rows
+        .iter()
+        .map(|row| Dependency {
+            kind: row.get(0),
+            schema: row.get(1),
+            name: row.get(2),
+            detail: row.get(3),
+        })
+        .collect();
+
+    Ok(dependencies)
+}
+
+/// Generate the SQL for a DDL operation without executing it
+///
+/// Adding a primary key column depends on the table's current key, so that case needs a
+/// connection to preview the exact statements that would run.
+#[tauri::command]
+pub async fn preview_ddl(
+    state: State<'_, AppState>,
+    operation: DdlOperation,
+    connection_id: Option<String>,
+) -> Result<String> {
+    match &operation {
+        DdlOperation::CreateSchema(request) => build_create_schema_sql(request),
+        DdlOperation::DropSchema(request) => build_drop_schema_sql(request),
+        DdlOperation::RenameSchema(request) => build_rename_schema_sql(request),
+        DdlOperation::CreateTable(request) => build_create_table_sql(request),
+        DdlOperation::DropTable(request) => build_drop_table_sql(request),
+        DdlOperation::AddTableColumn(request) => {
+            let existing_primary_key =
+                match (&connection_id, request.column.is_primary_key) {
+                    (Some(connection_id), true) => {
+                        let client = state.get_client(connection_id).await?;
+                        fetch_primary_key_constraint(&client, &request.schema, &request.table_name)
+                            .await?
+                    }
+                    (None, true) => return Err(RowFlowError::InvalidInput(
+                        "Previewing a primary key column needs a connection to read the table's \
+                         current key"
+                            .to_string(),
+                    )),
+                    (_, false) => None,
+                };
+            build_add_table_column_sql(request, existing_primary_key.as_ref())
+        }
+        DdlOperation::DropTableColumn(request) => build_drop_table_column_sql(request),
+        DdlOperation::AlterTable(request) => build_alter_table_sql(request),
+    }
+}
+
+fn build_create_schema_sql(request: &CreateSchemaRequest) -> Result<String> {
+    validate_identifier(&request.name, "schema")?;
 
     let if_not_exists = if request.if_not_exists { "IF NOT EXISTS " } else { "" };
-    let sql = format!(
+    Ok(format!("CREATE SCHEMA {}{};", if_not_exists, quote_identifier(&request.name)))
+}
+
+fn build_drop_schema_sql(request: &DropSchemaRequest) -> Result<String> {
+    validate_identifier(&request.name, "schema")?;
+
+    let if_exists = if request.if_exists { "IF EXISTS " } else { "" };
+    let cascade = if request.cascade { " CASCADE" } else { "" };
+    Ok(format!("DROP SCHEMA {}{}{};", if_exists, quote_identifier(&request.name), cascade))
+}
+
+fn build_rename_schema_sql(request: &RenameSchemaRequest) -> Result<String> {
+    validate_identifier(&request.current_name, "schema")?;
+    validate_identifier(&request.new_name, "schema")?;
+
+    Ok(format!(
+        "ALTER SCHEMA {} RENAME TO {};",
+        quote_identifier(&request.current_name),
+        quote_identifier(&request.new_name)
+    ))
+}
+
+fn build_create_table_sql(request: &CreateTableRequest) -> Result<String> {
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table_name, "table")?;
+
+    if request.columns.is_empty() {
+        return Err(RowFlowError::SchemaError("Cannot create a table without columns".to_string()));
+    }
+
+    let mut seen_columns = HashSet::new();
+    let primary_key_columns: Vec<String> = request
+        .columns
+        .iter()
+        .filter(|col| col.is_primary_key)
+        .map(|col| col.name.clone())
+        .collect();
+
+    let inline_primary_key = primary_key_columns.len() <= 1;
+
+    let mut column_definitions = Vec::with_capacity(request.columns.len());
+    for column in &request.columns {
+        let lowered = column.name.to_lowercase();
+        if !seen_columns.insert(lowered) {
+            return Err(RowFlowError::SchemaError(format!(
+                "Duplicate column name '{}' in create table request",
+                column.name
+            )));
+        }
+
+        column_definitions.push(build_column_definition(column, inline_primary_key)?);
+    }
+
+    if !primary_key_columns.is_empty() && !inline_primary_key {
+        let pk_clause = format!(
+            "PRIMARY KEY ({})",
+            primary_key_columns
+                .iter()
+                .map(|name| quote_identifier(name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        column_definitions.push(pk_clause);
+    }
+
+    for constraint in &request.table_constraints {
+        if let Some(missing) =
+            constraint.columns.iter().find(|column| !seen_columns.contains(&column.to_lowercase()))
+        {
+            return Err(RowFlowError::SchemaError(format!(
+                "Table constraint references unknown column '{}'",
+                missing
+            )));
+        }
+        column_definitions.push(build_table_constraint(constraint)?);
+    }
+
+    let if_not_exists = if request.if_not_exists { "IF NOT EXISTS " } else { "" };
+    Ok(format!(
         "CREATE TABLE {}{}.{} (\n    {}\n);",
         if_not_exists,
         quote_identifier(&request.schema),
         quote_identifier(&request.table_name),
         column_definitions.join(",\n    ")
+    ))
+}
+
+fn build_drop_table_sql(request: &DropTableRequest) -> Result<String> {
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table_name, "table")?;
+
+    let if_exists = if request.if_exists { "IF EXISTS " } else { "" };
+    let cascade = if request.cascade { " CASCADE" } else { "" };
+    Ok(format!(
+        "DROP TABLE {}{}.{}{};",
+        if_exists,
+        quote_identifier(&request.schema),
+        quote_identifier(&request.table_name),
+        cascade
+    ))
+}
+
+fn build_add_table_column_sql(
+    request: &AddTableColumnRequest,
+    existing_primary_key: Option<&PrimaryKeyConstraint>,
+) -> Result<String> {
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table_name, "table")?;
+
+    let table = qualified_table_name(&request.schema, &request.table_name)?;
+    let if_not_exists = if request.if_not_exists { "IF NOT EXISTS " } else { "" };
+
+    // A skipped ADD COLUMN would still drop and re-create the primary key
+    if request.column.is_primary_key && request.if_not_exists {
+        return Err(RowFlowError::InvalidInput(
+            "IF NOT EXISTS cannot be combined with adding a primary key column".to_string(),
+        ));
+    }
+
+    if !request.column.is_primary_key {
+        let column_definition = build_column_definition(&request.column, true)?;
+        return Ok(format!(
+            "ALTER TABLE {} ADD COLUMN {}{};",
+            table, if_not_exists, column_definition
+        ));
+    }
+
+    // Primary key columns are always NOT NULL; the key itself is (re)created separately
+    let mut column = request.column.clone();
+    column.is_nullable = false;
+    let column_definition = build_column_definition(&column, false)?;
+
+    // Sent as one batch, so the statements run in a single implicit transaction
+    let mut statements =
+        vec![format!("ALTER TABLE {} ADD COLUMN {}{};", table, if_not_exists, column_definition)];
+
+    match existing_primary_key {
+        Some(primary_key) => {
+            let mut key_columns = primary_key.columns.clone();
+            key_columns.push(column.name.clone());
+            statements.push(format!(
+                "ALTER TABLE {} DROP CONSTRAINT {};",
+                table,
+                quote_identifier(&primary_key.name)
+            ));
+            statements.push(format!(
+                "ALTER TABLE {} ADD CONSTRAINT {} PRIMARY KEY ({});",
+                table,
+                quote_identifier(&primary_key.name),
+                key_columns
+                    .iter()
+                    .map(|name| quote_identifier(name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        None => {
+            statements.push(format!(
+                "ALTER TABLE {} ADD PRIMARY KEY ({});",
+                table,
+                quote_identifier(&column.name)
+            ));
+        }
+    }
+
+    Ok(statements.join("\n"))
+}
+
+/// Name and ordered columns of a table's primary key constraint
+struct PrimaryKeyConstraint {
+    name: String,
+    columns: Vec<String>,
+}
+
+async fn fetch_primary_key_constraint(
+    client: &deadpool_postgres::Client,
+    schema: &str,
+    table: &str,
+) -> Result<Option<PrimaryKeyConstraint>> {
+    let query = r#"
+        SELECT con.conname::text, array_agg(a.attname::text ORDER BY k.ord)
+        FROM pg_catalog.pg_constraint con
+        JOIN pg_catalog.pg_class c ON c.oid = con.conrelid
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        CROSS JOIN LATERAL unnest(con.conkey) WITH ORDINALITY AS k(attnum, ord)
+        JOIN pg_catalog.pg_attribute a ON a.attrelid = c.oid AND a.attnum = k.attnum
+        WHERE con.contype = 'p'
+            AND n.nspname = $1
+            AND c.relname = $2
+        GROUP BY con.conname
+    "#;
+
+    let row = client.query_opt(query, &[&schema, &table]).await?;
+
+    Ok(row.map(|row| PrimaryKeyConstraint { name: row.get(0), columns: row.get(1) }))
+}
+
+fn build_drop_table_column_sql(request: &DropTableColumnRequest) -> Result<String> {
+    validate_identifier(&request.schema, "schema")?;
+    validate_identifier(&request.table_name, "table")?;
+    validate_identifier(&request.column_name, "column")?;
+
+    let if_exists = if request.if_exists { "IF EXISTS " } else { "" };
+    let cascade = if request.cascade { " CASCADE" } else { "" };
+    Ok(format!(
+        "ALTER TABLE {} DROP COLUMN {}{}{};",
+        qualified_table_name(&request.schema, &request.table_name)?,
+        if_exists,
+        quote_identifier(&request.column_name),
+        cascade
+    ))
+}
+
+/// Compose an `alter_table` request into as few `ALTER TABLE` statements as possible
+///
+/// Renames can't be combined with other actions, so each one ends the current statement and
+/// gets its own; everything between renames shares a single comma-separated statement.
+fn build_alter_table_sql(request: &AlterTableRequest) -> Result<String> {
+    let table = qualified_table_name(&request.schema, &request.table_name)?;
+    if request.operations.is_empty() {
+        return Err(RowFlowError::SchemaError(
+            "Alter table request must include at least one operation".to_string(),
+        ));
+    }
+
+    let mut statements = Vec::new();
+    let mut actions = Vec::new();
+    for operation in &request.operations {
+        let action = build_alter_table_action(operation)?;
+        if matches!(operation, AlterTableOperation::RenameColumn(_)) {
+            if !actions.is_empty() {
+                statements.push(format!("ALTER TABLE {} {};", table, actions.join(", ")));
+                actions.clear();
+            }
+            statements.push(format!("ALTER TABLE {} {};", table, action));
+        } else {
+            actions.push(action);
+        }
+    }
+    if !actions.is_empty() {
+        statements.push(format!("ALTER TABLE {} {};", table, actions.join(", ")));
+    }
+
+    Ok(statements.join("\n"))
+}
+
+fn build_alter_table_action(operation: &AlterTableOperation) -> Result<String> {
+    let non_empty = |value: &str, label: &str| -> Result<String> {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return Err(RowFlowError::SchemaError(format!("{label} cannot be empty")));
+        }
+        Ok(trimmed.to_string())
+    };
+    let column = |name: &str| -> Result<String> {
+        validate_identifier(name, "column")?;
+        Ok(quote_identifier(name))
+    };
+
+    Ok(match operation {
+        AlterTableOperation::AddColumn(definition) => {
+            format!("ADD COLUMN {}", build_column_definition(definition, true)?)
+        }
+        AlterTableOperation::DropColumn(drop) => format!(
+            "DROP COLUMN {}{}",
+            column(&drop.column_name)?,
+            if drop.cascade { " CASCADE" } else { "" }
+        ),
+        AlterTableOperation::RenameColumn(rename) => format!(
+            "RENAME COLUMN {} TO {}",
+            column(&rename.column_name)?,
+            column(&rename.new_name)?
+        ),
+        AlterTableOperation::AlterColumnType(change) => {
+            let mut action = format!(
+                "ALTER COLUMN {} TYPE {}",
+                column(&change.column_name)?,
+                non_empty(&change.data_type, "Data type")?
+            );
+            if let Some(using) = change.using.as_deref().filter(|using| !using.trim().is_empty()) {
+                action.push_str(&format!(" USING {}", using.trim()));
+            }
+            action
+        }
+        AlterTableOperation::SetDefault(default) => format!(
+            "ALTER COLUMN {} SET DEFAULT {}",
+            column(&default.column_name)?,
+            non_empty(&default.default_expression, "Default expression")?
+        ),
+        AlterTableOperation::DropDefault(name) => {
+            format!("ALTER COLUMN {} DROP DEFAULT", column(name)?)
+        }
+        AlterTableOperation::SetNotNull(name) => {
+            format!("ALTER COLUMN {} SET NOT NULL", column(name)?)
+        }
+        AlterTableOperation::DropNotNull(name) => {
+            format!("ALTER COLUMN {} DROP NOT NULL", column(name)?)
+        }
+    })
+}
+
+/// Apply several column changes to a table atomically, returning the SQL that was generated
+///
+/// With `dry_run` the SQL is only returned. Otherwise all statements are sent as one batch, so
+/// they run in a single implicit transaction and either all apply or none do.
+#[tauri::command]
+pub async fn alter_table(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: AlterTableRequest,
+) -> Result<String> {
+    log::info!(
+        "Altering table {}.{} with {} operation(s) on connection: {}",
+        request.schema,
+        request.table_name,
+        request.operations.len(),
+        connection_id
+    );
+
+    let sql = build_alter_table_sql(&request)?;
+    if request.dry_run {
+        return Ok(sql);
+    }
+
+    let client = state.get_client(&connection_id).await?;
+    client.batch_execute(&sql).await?;
+
+    Ok(sql)
+}
+
+/// Create a new schema in the database
+#[tauri::command]
+pub async fn create_schema(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: CreateSchemaRequest,
+) -> Result<()> {
+    log::info!("Creating schema: {} on connection: {}", request.name, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let sql = build_create_schema_sql(&request)?;
+
+    client.batch_execute(&sql).await?;
+
+    Ok(())
+}
+
+/// Drop an existing schema
+#[tauri::command]
+pub async fn drop_schema(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: DropSchemaRequest,
+) -> Result<()> {
+    log::info!("Dropping schema: {} on connection: {}", request.name, connection_id);
+
+    let client = state.get_client(&connection_id).await?;
+
+    let sql = build_drop_schema_sql(&request)?;
+
+    client.batch_execute(&sql).await?;
+
+    Ok(())
+}
+
+/// Rename an existing schema
+#[tauri::command]
+pub async fn rename_schema(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: RenameSchemaRequest,
+) -> Result<()> {
+    log::info!(
+        "Renaming schema: {} -> {} on connection: {}",
+        request.current_name,
+        request.new_name,
+        connection_id
     );
 
+    let client = state.get_client(&connection_id).await?;
+
+    let sql = build_rename_schema_sql(&request)?;
+
+    client.batch_execute(&sql).await?;
+
+    Ok(())
+}
+
+/// Create a new table using the provided column definitions
+#[tauri::command]
+pub async fn create_table(
+    state: State<'_, AppState>,
+    connection_id: String,
+    request: CreateTableRequest,
+) -> Result<()> {
+    log::info!(
+        "Creating table: {}.{} on connection: {}",
+        request.schema,
+        request.table_name,
+        connection_id
+    );
+
+    let client = state.get_client(&connection_id).await?;
+
+    let sql = build_create_table_sql(&request)?;
+
     client.batch_execute(&sql).await?;
 
     Ok(())
@@ -796,18 +2321,7 @@ pub async fn drop_table(
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.schema, "schema")?;
-    validate_identifier(&request.table_name, "table")?;
-
-    let if_exists = if request.if_exists { "IF EXISTS " } else { "" };
-    let cascade = if request.cascade { " CASCADE" } else { "" };
-    let sql = format!(
-        "DROP TABLE {}{}.{}{};",
-        if_exists,
-        quote_identifier(&request.schema),
-        quote_identifier(&request.table_name),
-        cascade
-    );
+    let sql = build_drop_table_sql(&request)?;
 
     client.batch_execute(&sql).await?;
 
@@ -831,23 +2345,40 @@ pub async fn add_table_column(
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.schema, "schema")?;
-    validate_identifier(&request.table_name, "table")?;
+    let existing_primary_key = if request.column.is_primary_key {
+        let has_rows: bool = client
+            .query_one(
+                &format!(
+                    "SELECT EXISTS (SELECT 1 FROM {})",
+                    qualified_table_name(&request.schema, &request.table_name)?
+                ),
+                &[],
+            )
+            .await?
+            .get(0);
+
+        let has_default = request
+            .column
+            .default_expression
+            .as_deref()
+            .is_some_and(|default| !default.trim().is_empty());
+
+        if has_rows && !has_default {
+            return Err(RowFlowError::SchemaError(format!(
+                "Cannot add primary key column '{}' to a non-empty table without a default: \
+                 primary key columns must be NOT NULL and unique for every existing row. \
+                 Provide a default such as a sequence or gen_random_uuid(), or add the column \
+                 first, backfill it, then create the key.",
+                request.column.name
+            )));
+        }
 
-    if request.column.is_primary_key {
-        return Err(RowFlowError::SchemaError(
-            "Adding primary key columns via this operation is not supported".to_string(),
-        ));
-    }
+        fetch_primary_key_constraint(&client, &request.schema, &request.table_name).await?
+    } else {
+        None
+    };
 
-    let column_definition = build_column_definition(&request.column, true)?;
-    let if_not_exists = if request.if_not_exists { "IF NOT EXISTS " } else { "" };
-    let sql = format!(
-        "ALTER TABLE {} ADD COLUMN {}{};",
-        qualified_table_name(&request.schema, &request.table_name)?,
-        if_not_exists,
-        column_definition
-    );
+    let sql = build_add_table_column_sql(&request, existing_primary_key.as_ref())?;
 
     client.batch_execute(&sql).await?;
 
@@ -871,19 +2402,7 @@ pub async fn drop_table_column(
 
     let client = state.get_client(&connection_id).await?;
 
-    validate_identifier(&request.schema, "schema")?;
-    validate_identifier(&request.table_name, "table")?;
-    validate_identifier(&request.column_name, "column")?;
-
-    let if_exists = if request.if_exists { "IF EXISTS " } else { "" };
-    let cascade = if request.cascade { " CASCADE" } else { "" };
-    let sql = format!(
-        "ALTER TABLE {} DROP COLUMN {}{}{};",
-        qualified_table_name(&request.schema, &request.table_name)?,
-        if_exists,
-        quote_identifier(&request.column_name),
-        cascade
-    );
+    let sql = build_drop_table_column_sql(&request)?;
 
     client.batch_execute(&sql).await?;
 
@@ -893,6 +2412,7 @@ pub async fn drop_table_column(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{ColumnDrop, ColumnRename, ColumnTypeChange, TableConstraintReference};
 
     fn base_column() -> TableColumnDefinition {
         TableColumnDefinition {
@@ -947,4 +2467,239 @@ mod tests {
             "\"customer_id\" INTEGER NOT NULL REFERENCES \"billing\".\"accounts\"(\"id\") ON DELETE SET NULL ON UPDATE RESTRICT"
         );
     }
+
+    #[test]
+    fn adds_column_to_existing_primary_key() {
+        let mut column = base_column();
+        column.name = "tenant_id".to_string();
+        column.is_nullable = true;
+        column.is_primary_key = true;
+        column.default_expression = Some("0".to_string());
+
+        let request = AddTableColumnRequest {
+            schema: "public".to_string(),
+            table_name: "orders".to_string(),
+            column,
+            if_not_exists: false,
+        };
+        let existing = PrimaryKeyConstraint {
+            name: "orders_pkey".to_string(),
+            columns: vec!["id".to_string()],
+        };
+
+        let sql = build_add_table_column_sql(&request, Some(&existing)).expect("add column sql");
+        assert_eq!(
+            sql,
+            "ALTER TABLE \"public\".\"orders\" ADD COLUMN \"tenant_id\" INTEGER NOT NULL DEFAULT 0;\n\
+             ALTER TABLE \"public\".\"orders\" DROP CONSTRAINT \"orders_pkey\";\n\
+             ALTER TABLE \"public\".\"orders\" ADD CONSTRAINT \"orders_pkey\" PRIMARY KEY (\"id\", \"tenant_id\");"
+        );
+
+        let request = AddTableColumnRequest { if_not_exists: true, ..request };
+        assert!(build_add_table_column_sql(&request, Some(&existing)).is_err());
+    }
+
+    fn constraint_test_request(constraint: TableConstraintDefinition) -> CreateTableRequest {
+        let mut tenant = base_column();
+        tenant.name = "tenant_id".to_string();
+        let mut customer = base_column();
+        customer.name = "customer_id".to_string();
+
+        CreateTableRequest {
+            schema: "public".to_string(),
+            table_name: "orders".to_string(),
+            columns: vec![tenant, customer],
+            if_not_exists: false,
+            table_constraints: vec![constraint],
+        }
+    }
+
+    #[test]
+    fn creates_table_with_composite_unique_constraint() {
+        let request = constraint_test_request(TableConstraintDefinition {
+            name: Some("orders_tenant_customer_key".to_string()),
+            constraint_type: "unique".to_string(),
+            columns: vec!["tenant_id".to_string(), "customer_id".to_string()],
+            check_expression: None,
+            references: None,
+        });
+
+        let sql = build_create_table_sql(&request).expect("create table sql");
+        assert_eq!(
+            sql,
+            "CREATE TABLE \"public\".\"orders\" (\n    \
+             \"tenant_id\" INTEGER NOT NULL,\n    \
+             \"customer_id\" INTEGER NOT NULL,\n    \
+             CONSTRAINT \"orders_tenant_customer_key\" UNIQUE (\"tenant_id\", \"customer_id\")\n);"
+        );
+    }
+
+    #[test]
+    fn creates_table_with_composite_foreign_key() {
+        let request = constraint_test_request(TableConstraintDefinition {
+            name: None,
+            constraint_type: "FOREIGN KEY".to_string(),
+            columns: vec!["tenant_id".to_string(), "customer_id".to_string()],
+            check_expression: None,
+            references: Some(TableConstraintReference {
+                schema: Some("billing".to_string()),
+                table: "customers".to_string(),
+                columns: vec!["tenant_id".to_string(), "id".to_string()],
+                on_delete: Some("CASCADE".to_string()),
+                on_update: None,
+            }),
+        });
+
+        let sql = build_create_table_sql(&request).expect("create table sql");
+        assert!(sql.contains(
+            "FOREIGN KEY (\"tenant_id\", \"customer_id\") REFERENCES \"billing\".\"customers\"(\"tenant_id\", \"id\") ON DELETE CASCADE"
+        ));
+    }
+
+    #[test]
+    fn rejects_foreign_key_with_mismatched_columns() {
+        let request = constraint_test_request(TableConstraintDefinition {
+            name: None,
+            constraint_type: "FOREIGN KEY".to_string(),
+            columns: vec!["tenant_id".to_string(), "customer_id".to_string()],
+            check_expression: None,
+            references: Some(TableConstraintReference {
+                schema: None,
+                table: "customers".to_string(),
+                columns: vec!["id".to_string()],
+                on_delete: None,
+                on_update: None,
+            }),
+        });
+
+        assert!(build_create_table_sql(&request).is_err());
+    }
+
+    fn actual_column(name: &str, data_type: &str, length: Option<i32>) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: true,
+            column_default: None,
+            is_identity: false,
+            identity_generation: None,
+            is_generated: false,
+            generation_expression: None,
+            character_maximum_length: length,
+            numeric_precision: None,
+            numeric_scale: None,
+            is_primary_key: false,
+            is_unique: false,
+            is_foreign_key: false,
+            foreign_key_schema: None,
+            foreign_key_table: None,
+            foreign_key_column: None,
+            description: None,
+        }
+    }
+
+    fn expected_column(name: &str, data_type: &str, is_nullable: Option<bool>) -> ExpectedColumn {
+        ExpectedColumn { name: name.to_string(), data_type: data_type.to_string(), is_nullable }
+    }
+
+    #[test]
+    fn compares_table_schema_with_type_aliases() {
+        let columns = vec![
+            actual_column("id", "integer", None),
+            actual_column("email", "character varying", Some(255)),
+            actual_column("created_at", "timestamp with time zone", None),
+            actual_column("tags", "text[]", None),
+            actual_column("legacy", "text", None),
+        ];
+        let matching = vec![
+            expected_column("id", "int4", Some(true)),
+            expected_column("email", "varchar(255)", None),
+            expected_column("created_at", "timestamptz", None),
+            expected_column("tags", "_text", None),
+            expected_column("legacy", "TEXT", None),
+        ];
+        assert!(compare_table_schema(&matching, &columns).matches);
+
+        let drifted = vec![
+            expected_column("id", "bigint", Some(false)),
+            expected_column("email", "varchar(100)", None),
+            expected_column("name", "text", None),
+        ];
+        let assertion = compare_table_schema(&drifted, &columns);
+        assert!(!assertion.matches);
+        assert_eq!(assertion.missing_columns, vec!["name"]);
+        assert_eq!(assertion.extra_columns, vec!["created_at", "tags", "legacy"]);
+        let fields: Vec<(&str, &str)> = assertion
+            .mismatches
+            .iter()
+            .map(|mismatch| (mismatch.column.as_str(), mismatch.field.as_str()))
+            .collect();
+        assert_eq!(fields, vec![("id", "dataType"), ("id", "isNullable"), ("email", "dataType")]);
+        assert_eq!(assertion.mismatches[2].actual, "character varying(255)");
+    }
+
+    #[test]
+    fn normalizes_type_names() {
+        let cases = [
+            ("int4", "integer"),
+            ("INTEGER", "integer"),
+            ("serial", "integer"),
+            ("int8", "bigint"),
+            ("float8", "double precision"),
+            ("varchar(255)", "character varying"),
+            ("character varying", "character varying"),
+            ("bpchar", "character"),
+            ("char", "char"),
+            ("\"char\"", "char"),
+            ("timestamptz", "timestamp with time zone"),
+            ("time(3) with time zone", "time with time zone"),
+            ("numeric(10, 2)", "numeric"),
+            ("jsonb", "jsonb"),
+            ("int4[]", "integer[]"),
+            ("_status", "_status"),
+            ("varchar[]", "character varying[]"),
+            ("Mood", "Mood"),
+        ];
+        for (type_name, expected) in cases {
+            assert_eq!(normalize_type_name(type_name), expected, "{}", type_name);
+        }
+    }
+
+    #[test]
+    fn builds_alter_table_with_renames_split_out() {
+        let request = AlterTableRequest {
+            schema: "public".to_string(),
+            table_name: "orders".to_string(),
+            operations: vec![
+                AlterTableOperation::AddColumn(base_column()),
+                AlterTableOperation::AlterColumnType(ColumnTypeChange {
+                    column_name: "total".to_string(),
+                    data_type: "numeric(12,2)".to_string(),
+                    using: Some("total::numeric".to_string()),
+                }),
+                AlterTableOperation::RenameColumn(ColumnRename {
+                    column_name: "note".to_string(),
+                    new_name: "notes".to_string(),
+                }),
+                AlterTableOperation::DropNotNull("notes".to_string()),
+                AlterTableOperation::DropColumn(ColumnDrop {
+                    column_name: "legacy".to_string(),
+                    cascade: true,
+                }),
+            ],
+            dry_run: true,
+        };
+
+        assert_eq!(
+            build_alter_table_sql(&request).expect("alter table sql"),
+            "ALTER TABLE \"public\".\"orders\" ADD COLUMN \"customer_id\" INTEGER NOT NULL, \
+             ALTER COLUMN \"total\" TYPE numeric(12,2) USING total::numeric;\n\
+             ALTER TABLE \"public\".\"orders\" RENAME COLUMN \"note\" TO \"notes\";\n\
+             ALTER TABLE \"public\".\"orders\" ALTER COLUMN \"notes\" DROP NOT NULL, \
+             DROP COLUMN \"legacy\" CASCADE;"
+        );
+
+        let empty = AlterTableRequest { operations: Vec::new(), ..request };
+        assert!(build_alter_table_sql(&empty).is_err());
+    }
 }
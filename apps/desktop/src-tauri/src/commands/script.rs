@@ -0,0 +1,862 @@
+use crate::copy_progress::CopyProgressReporter;
+use crate::error::{Result, RowFlowError};
+use crate::state::AppState;
+use crate::types::SqlImportResult;
+use bytes::Bytes;
+use futures_util::SinkExt;
+use std::time::Instant;
+use tauri::{Emitter, State};
+
+/// Emit a progress event after this many executed items
+const IMPORT_PROGRESS_INTERVAL: usize = 50;
+
+/// Size of the chunks `COPY ... FROM stdin` data is sent in, so its progress can be reported
+const COPY_CHUNK_SIZE: usize = 256 * 1024;
+
+/// One executable unit of a SQL script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptItem {
+    /// A statement without its terminating semicolon
+    Statement(String),
+    /// A `COPY ... FROM stdin` statement with the inline data that followed it
+    CopyFromStdin { statement: String, data: String },
+    /// A psql meta-command such as `\connect`, which the server cannot run
+    MetaCommand(String),
+}
+
+/// Split a SQL script into statements
+///
+/// Semicolons inside quoted strings, quoted identifiers, comments, dollar-quoted bodies and
+/// parentheses do not end a statement. Inline data after `COPY ... FROM stdin;` is collected up
+/// to the `\.` terminator line, as written by pg_dump.
+pub fn split_sql_script(script: &str) -> Vec<ScriptItem> {
+    let bytes = script.as_bytes();
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut paren_depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = line_end(bytes, i);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_block_comment(bytes, i);
+                continue;
+            }
+            b'\'' => {
+                let escapes = i > 0
+                    && matches!(bytes[i - 1], b'E' | b'e')
+                    && (i < 2 || !is_identifier_byte(bytes[i - 2]));
+                i = skip_quoted(bytes, i, b'\'', escapes);
+                continue;
+            }
+            b'"' => {
+                i = skip_quoted(bytes, i, b'"', false);
+                continue;
+            }
+            b'$' => {
+                if let Some(tag_end) = dollar_tag_end(bytes, i) {
+                    let tag = &script[i..=tag_end];
+                    i = match script[tag_end + 1..].find(tag) {
+                        Some(pos) => tag_end + 1 + pos + tag.len(),
+                        None => bytes.len(),
+                    };
+                    continue;
+                }
+            }
+            b'(' => paren_depth += 1,
+            b')' => paren_depth = paren_depth.saturating_sub(1),
+            b'\\'
+                if (i == 0 || bytes[i - 1] == b'\n') && is_blank_or_comments(&script[start..i]) =>
+            {
+                let end = line_end(bytes, i);
+                items.push(ScriptItem::MetaCommand(script[i..end].trim().to_string()));
+                i = end;
+                start = end;
+                continue;
+            }
+            b';' if paren_depth == 0 => {
+                let statement = script[start..i].trim();
+                i += 1;
+                start = i;
+
+                if statement.is_empty() {
+                    continue;
+                }
+
+                if is_copy_from_stdin(statement) {
+                    let (data, next) = read_copy_data(script, i);
+                    items.push(ScriptItem::CopyFromStdin {
+                        statement: statement.to_string(),
+                        data: data.to_string(),
+                    });
+                    i = next;
+                    start = next;
+                } else {
+                    items.push(ScriptItem::Statement(statement.to_string()));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let trailing = script[start..].trim();
+    if !is_blank_or_comments(trailing) {
+        items.push(ScriptItem::Statement(trailing.to_string()));
+    }
+
+    items
+}
+
+/// Keywords that make a statement starting with SELECT, WITH or EXPLAIN write, e.g. a
+/// data-modifying CTE, `SELECT ... INTO` or `EXPLAIN ANALYZE DELETE`
+const WRITE_KEYWORDS: [&str; 12] = [
+    "insert", "update", "delete", "merge", "into", "create", "drop", "alter", "truncate", "grant",
+    "revoke", "execute",
+];
+
+/// Whether every statement in `sql` only reads data
+///
+/// Statements must start with SELECT, WITH, VALUES, TABLE, SHOW or EXPLAIN, contain no write
+/// keyword outside of strings, quoted identifiers and comments, and call none of the functions
+/// `catalog_query_violation` denies or the `dblink` family. This errs on the side of treating
+/// SQL as a write, e.g. `SELECT ... FOR UPDATE` counts as one.
+pub fn is_read_only_sql(sql: &str) -> bool {
+    let items = split_sql_script(sql);
+    !items.is_empty()
+        && items.iter().all(|item| match item {
+            ScriptItem::Statement(statement) => {
+                let words = code_words(statement);
+                matches!(
+                    words.first().map(String::as_str),
+                    Some("select" | "with" | "values" | "table" | "show" | "explain")
+                ) && !words.iter().any(|word| WRITE_KEYWORDS.contains(&word.as_str()))
+                    && !calls_denied_function(statement)
+            }
+            ScriptItem::CopyFromStdin { .. } | ScriptItem::MetaCommand(_) => false,
+        })
+}
+
+/// Whether `statement` calls a denied catalog function or a `dblink` function, which can run
+/// arbitrary SQL on another connection
+fn calls_denied_function(statement: &str) -> bool {
+    let tokens = code_tokens(statement);
+    tokens.windows(2).any(|pair| match pair {
+        [SqlToken::Word(name) | SqlToken::Quoted(name), SqlToken::Symbol(b'(')] => {
+            is_denied_catalog_function(name) || name.to_lowercase().starts_with("dblink")
+        }
+        _ => false,
+    })
+}
+
+/// Catalog functions that read user data or files, or change state, despite living in
+/// `pg_catalog`
+const CATALOG_QUERY_DENIED_FUNCTIONS: [&str; 17] = [
+    "ts_stat",
+    "ts_rewrite",
+    "loread",
+    "lowrite",
+    "pg_stat_file",
+    "pg_terminate_backend",
+    "pg_cancel_backend",
+    "pg_signal_backend",
+    "pg_reload_conf",
+    "pg_rotate_logfile",
+    "pg_promote",
+    "pg_switch_wal",
+    "pg_notify",
+    "pg_sleep",
+    "set_config",
+    "nextval",
+    "setval",
+];
+
+/// Prefixes of catalog function families denied the same way, e.g. `query_to_xml` and
+/// `pg_ls_dir`
+const CATALOG_QUERY_DENIED_FUNCTION_PREFIXES: [&str; 16] = [
+    "query_to_",
+    "cursor_to_",
+    "table_to_",
+    "schema_to_",
+    "database_to_",
+    "pg_read_",
+    "pg_ls_",
+    "lo_",
+    "pg_advisory_",
+    "pg_try_advisory_",
+    "pg_stat_reset",
+    "pg_create_",
+    "pg_drop_replication_slot",
+    "pg_replication_origin_",
+    "pg_logical_",
+    "pg_log_backend_",
+];
+
+fn is_denied_catalog_function(name: &str) -> bool {
+    let name = name.to_lowercase();
+    CATALOG_QUERY_DENIED_FUNCTIONS.contains(&name.as_str())
+        || CATALOG_QUERY_DENIED_FUNCTION_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Functions that return a new value on every call when used as a column default
+const VOLATILE_DEFAULT_FUNCTIONS: [&str; 15] = [
+    "nextval",
+    "now",
+    "clock_timestamp",
+    "statement_timestamp",
+    "transaction_timestamp",
+    "timeofday",
+    "random",
+    "gen_random_uuid",
+    "uuid_generate_v1",
+    "uuid_generate_v1mc",
+    "uuid_generate_v4",
+    "uuidv4",
+    "uuidv7",
+    "txid_current",
+    "pg_current_xact_id",
+];
+
+/// SQL value functions written without parentheses, e.g. `DEFAULT CURRENT_TIMESTAMP`
+const VOLATILE_DEFAULT_KEYWORDS: [&str; 5] =
+    ["current_timestamp", "current_date", "current_time", "localtimestamp", "localtime"];
+
+/// Clause keywords that end a FROM list
+const FROM_LIST_END_KEYWORDS: [&str; 13] = [
+    "select",
+    "where",
+    "group",
+    "having",
+    "window",
+    "order",
+    "limit",
+    "offset",
+    "fetch",
+    "for",
+    "union",
+    "intersect",
+    "except",
+];
+
+/// Why `sql` is not a read-only query limited to `pg_catalog` and `information_schema`, or
+/// `None` when it is
+///
+/// Schema-qualified relations in FROM and JOIN and schema-qualified function calls must name
+/// one of the two catalog schemas. Unqualified names are left to the caller, which runs the
+/// query with only `pg_catalog` on the search path. Functions that read user data through a
+/// query string or a table OID are rejected by name.
+pub fn catalog_query_violation(sql: &str) -> Option<String> {
+    let items = split_sql_script(sql);
+    let [ScriptItem::Statement(statement)] = items.as_slice() else {
+        return Some("Run exactly one statement".to_string());
+    };
+    if !is_read_only_sql(statement) {
+        return Some("Only read-only queries are allowed".to_string());
+    }
+
+    // One entry per open parenthesis: (is a query level, inside its FROM list)
+    let tokens = code_tokens(statement);
+    let mut levels = vec![(true, false)];
+    let mut expect_relation = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match &tokens[i] {
+            SqlToken::Symbol(b'(') => {
+                let starts_query = matches!(
+                    tokens.get(i + 1),
+                    Some(SqlToken::Word(word))
+                        if matches!(word.as_str(), "select" | "with" | "values" | "table")
+                );
+                // A parenthesized join keeps listing relations
+                levels.push((starts_query || expect_relation, expect_relation));
+                i += 1;
+                continue;
+            }
+            SqlToken::Symbol(b')') => {
+                if levels.len() > 1 {
+                    levels.pop();
+                }
+                expect_relation = false;
+                i += 1;
+                continue;
+            }
+            SqlToken::Symbol(b',') => {
+                let (is_query, in_from) = levels[levels.len() - 1];
+                expect_relation = is_query && in_from;
+                i += 1;
+                continue;
+            }
+            SqlToken::Word(word) if matches!(word.as_str(), "lateral" | "only") => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let (parts, next) = name_chain(&tokens, i);
+        if parts.is_empty() {
+            expect_relation = false;
+            i += 1;
+            continue;
+        }
+
+        let level = levels.len() - 1;
+        let is_call = tokens.get(next) == Some(&SqlToken::Symbol(b'('));
+        let is_catalog_schema =
+            |schema: &str| matches!(schema, "pg_catalog" | "information_schema");
+        let schema = parts.len().checked_sub(2).map(|index| parts[index].as_str());
+        let name = parts[parts.len() - 1].as_str();
+
+        if is_call && is_denied_catalog_function(name) {
+            return Some(format!("Function {} is not allowed in catalog queries", name));
+        }
+        // `OPERATOR(schema.op)` names its schema inside the parentheses
+        if is_call && parts.len() == 1 && name == "operator" {
+            if let (
+                Some(SqlToken::Word(schema) | SqlToken::Quoted(schema)),
+                Some(SqlToken::Symbol(b'.')),
+            ) = (tokens.get(next + 1), tokens.get(next + 2))
+            {
+                if !is_catalog_schema(schema) {
+                    return Some(format!(
+                        "Operator in schema {} is outside pg_catalog and information_schema",
+                        schema
+                    ));
+                }
+            }
+        }
+        if let Some(schema) = schema {
+            if (is_call || expect_relation) && !is_catalog_schema(schema) {
+                let kind = if is_call { "Function" } else { "Relation" };
+                return Some(format!(
+                    "{} {}.{} is outside pg_catalog and information_schema",
+                    kind, schema, name
+                ));
+            }
+        }
+
+        if parts.len() == 1 && matches!(&tokens[i], SqlToken::Word(_)) {
+            let (is_query, _) = levels[level];
+            match name {
+                "from" | "join" if is_query => {
+                    levels[level].1 = true;
+                    expect_relation = true;
+                    i = next;
+                    continue;
+                }
+                // `TABLE name` can start any query, including a subquery, CTE or set operand
+                "table" => {
+                    expect_relation = true;
+                    i = next;
+                    continue;
+                }
+                keyword if FROM_LIST_END_KEYWORDS.contains(&keyword) => levels[level].1 = false,
+                _ => {}
+            }
+        }
+
+        expect_relation = false;
+        i = next;
+    }
+
+    None
+}
+
+/// Dotted name parts starting at `from`, and the index after them; empty when no name starts
+/// there
+fn name_chain(tokens: &[SqlToken], from: usize) -> (Vec<String>, usize) {
+    let mut parts = Vec::new();
+    let mut i = from;
+    loop {
+        match tokens.get(i) {
+            Some(SqlToken::Word(part) | SqlToken::Quoted(part)) => parts.push(part.clone()),
+            _ => return (parts, i),
+        }
+        i += 1;
+        if tokens.get(i) != Some(&SqlToken::Symbol(b'.')) {
+            return (parts, i);
+        }
+        i += 1;
+    }
+}
+
+/// A lexical token of a statement, with comments dropped
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SqlToken {
+    /// A keyword or unquoted identifier, lowercased
+    Word(String),
+    /// A double-quoted identifier, unquoted
+    Quoted(String),
+    /// A string or dollar-quoted literal
+    Literal,
+    Symbol(u8),
+}
+
+/// Whether a column default expression produces a fresh value per row, e.g. `nextval(...)` or
+/// `CURRENT_TIMESTAMP`
+///
+/// Only calls to a known set of volatile functions count, with or without a schema; text inside
+/// string literals is ignored, so `'nextval'::text` is a constant.
+pub fn is_volatile_default(default: &str) -> bool {
+    let tokens = code_tokens(default);
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        SqlToken::Word(name) | SqlToken::Quoted(name)
+            if tokens.get(i + 1) == Some(&SqlToken::Symbol(b'(')) =>
+        {
+            VOLATILE_DEFAULT_FUNCTIONS.contains(&name.as_str())
+        }
+        SqlToken::Word(word) => {
+            VOLATILE_DEFAULT_KEYWORDS.contains(&word.as_str())
+                && (i == 0 || tokens[i - 1] != SqlToken::Symbol(b'.'))
+        }
+        _ => false,
+    })
+}
+
+/// Tokens of a statement, skipping comments and whitespace
+fn code_tokens(statement: &str) -> Vec<SqlToken> {
+    let bytes = statement.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = line_end(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            b'\'' => {
+                let escapes = i > 0 && matches!(bytes[i - 1], b'E' | b'e');
+                i = skip_quoted(bytes, i, b'\'', escapes);
+                tokens.push(SqlToken::Literal);
+            }
+            b'"' => {
+                let start = i;
+                i = skip_quoted(bytes, i, b'"', false);
+                let inner =
+                    statement[start + 1..i].strip_suffix('"').unwrap_or(&statement[start + 1..i]);
+                tokens.push(SqlToken::Quoted(inner.replace("\"\"", "\"")));
+            }
+            b'$' => match dollar_tag_end(bytes, i) {
+                Some(tag_end) => {
+                    let tag = &statement[i..=tag_end];
+                    i = match statement[tag_end + 1..].find(tag) {
+                        Some(pos) => tag_end + 1 + pos + tag.len(),
+                        None => bytes.len(),
+                    };
+                    tokens.push(SqlToken::Literal);
+                }
+                None => i += 1,
+            },
+            byte if is_identifier_byte(byte) => {
+                let start = i;
+                while i < bytes.len() && is_identifier_byte(bytes[i]) {
+                    i += 1;
+                }
+                tokens.push(SqlToken::Word(statement[start..i].to_lowercase()));
+            }
+            byte if byte.is_ascii_whitespace() => i += 1,
+            byte => {
+                tokens.push(SqlToken::Symbol(byte));
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Lowercased words of a statement, skipping strings, quoted identifiers and comments
+fn code_words(statement: &str) -> Vec<String> {
+    code_tokens(statement)
+        .into_iter()
+        .filter_map(|token| match token {
+            SqlToken::Word(word) => Some(word),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+/// Index of the newline ending the line containing `from`, or the end of input
+fn line_end(bytes: &[u8], from: usize) -> usize {
+    bytes[from..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |pos| from + pos)
+}
+
+/// Skip a (possibly nested) block comment starting at `from`
+fn skip_block_comment(bytes: &[u8], from: usize) -> usize {
+    let mut depth = 0;
+    let mut i = from;
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            depth += 1;
+            i += 2;
+        } else if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// Skip a quoted string or identifier; a doubled quote is an escaped quote
+fn skip_quoted(bytes: &[u8], from: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = from + 1;
+    while i < bytes.len() {
+        if backslash_escapes && bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// Index of the closing `$` if a dollar-quote tag like `$$` or `$body$` starts at `from`
+fn dollar_tag_end(bytes: &[u8], from: usize) -> Option<usize> {
+    if from > 0 && (is_identifier_byte(bytes[from - 1]) || bytes[from - 1] == b'$') {
+        return None;
+    }
+
+    let mut i = from + 1;
+    match bytes.get(i) {
+        Some(b'$') => return Some(i),
+        Some(&b) if b.is_ascii_alphabetic() || b == b'_' || b >= 0x80 => {}
+        _ => return None,
+    }
+    while i < bytes.len() && is_identifier_byte(bytes[i]) {
+        i += 1;
+    }
+    (bytes.get(i) == Some(&b'$')).then_some(i)
+}
+
+fn is_blank_or_comments(text: &str) -> bool {
+    text.lines().all(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with("--")
+    })
+}
+
+fn is_copy_from_stdin(statement: &str) -> bool {
+    let code: Vec<String> = statement
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .flat_map(str::split_whitespace)
+        .map(str::to_uppercase)
+        .collect();
+
+    code.first().map(String::as_str) == Some("COPY")
+        && code.windows(2).any(|pair| pair[0] == "FROM" && pair[1] == "STDIN")
+}
+
+/// Read COPY data starting on the line after `from`, returning it and the offset after `\.`
+fn read_copy_data(script: &str, from: usize) -> (&str, usize) {
+    let bytes = script.as_bytes();
+    let data_start = (line_end(bytes, from) + 1).min(bytes.len());
+
+    let mut pos = data_start;
+    while pos < bytes.len() {
+        let end = line_end(bytes, pos);
+        if script[pos..end].trim_end_matches('\r') == "\\." {
+            return (&script[data_start..pos], (end + 1).min(bytes.len()));
+        }
+        pos = end + 1;
+    }
+
+    (&script[data_start..], bytes.len())
+}
+
+/// Import a `.sql` file such as a pg_dump plain-format dump
+///
+/// Statements run in order; `COPY ... FROM stdin` blocks are streamed through `copy_in` and psql
+/// meta-commands are skipped. Progress is emitted as `sql-import-progress` events, and as
+/// `copy-progress` events within each COPY block.
+#[tauri::command]
+pub async fn import_sql_file(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    connection_id: String,
+    path: String,
+    single_transaction: Option<bool>,
+) -> Result<SqlImportResult> {
+    log::info!("Importing SQL file {} on connection: {}", path, connection_id);
+
+    let script = tokio::fs::read_to_string(&path).await?;
+    let items = split_sql_script(&script);
+    let total = items.len();
+    let single_transaction = single_transaction.unwrap_or(false);
+
+    let client = state.get_client(&connection_id).await?;
+    let start = Instant::now();
+
+    if single_transaction {
+        client.batch_execute("BEGIN").await?;
+    }
+
+    let mut result = SqlImportResult {
+        statements_executed: 0,
+        copy_blocks: 0,
+        rows_copied: 0,
+        skipped_meta_commands: Vec::new(),
+        execution_time: 0.0,
+    };
+
+    let mut outcome = Ok(());
+    for (idx, item) in items.iter().enumerate() {
+        outcome = run_script_item(&app, &client, item, &mut result).await.map_err(|e| match e {
+            RowFlowError::QueryError(message) => {
+                RowFlowError::QueryError(format!("Statement {}: {}", idx + 1, message))
+            }
+            other => other,
+        });
+        if outcome.is_err() {
+            break;
+        }
+
+        let processed = idx + 1;
+        if processed % IMPORT_PROGRESS_INTERVAL == 0 || processed == total {
+            let _ = app.emit(
+                "sql-import-progress",
+                serde_json::json!({
+                    "connectionId": connection_id,
+                    "path": path,
+                    "processed": processed,
+                    "total": total,
+                }),
+            );
+        }
+    }
+
+    if single_transaction {
+        let finish = if outcome.is_ok() { "COMMIT" } else { "ROLLBACK" };
+        if let Err(e) = client.batch_execute(finish).await {
+            if outcome.is_ok() {
+                outcome = Err(e.into());
+            }
+        }
+    }
+
+    // Dumps change session settings such as search_path; don't leak them into the pool
+    if let Err(e) = client.batch_execute("RESET ALL").await {
+        log::warn!("Failed to reset session after SQL import: {}", e);
+    }
+
+    outcome?;
+
+    result.execution_time = start.elapsed().as_secs_f64() * 1000.0;
+    log::info!(
+        "SQL import completed: {} statements, {} COPY blocks ({} rows) in {:.2}ms",
+        result.statements_executed,
+        result.copy_blocks,
+        result.rows_copied,
+        result.execution_time
+    );
+
+    Ok(result)
+}
+
+async fn run_script_item(
+    app: &tauri::AppHandle,
+    client: &deadpool_postgres::Client,
+    item: &ScriptItem,
+    result: &mut SqlImportResult,
+) -> Result<()> {
+    match item {
+        ScriptItem::Statement(statement) => {
+            client.batch_execute(statement).await?;
+            result.statements_executed += 1;
+        }
+        ScriptItem::CopyFromStdin { statement, data } => {
+            let mut progress = CopyProgressReporter::new(
+                app.clone(),
+                "import_sql_file",
+                statement.clone(),
+                Some(data.len() as u64),
+                None,
+            );
+            let sink = client.copy_in::<_, Bytes>(statement.as_str()).await?;
+            futures_util::pin_mut!(sink);
+            let data = Bytes::from(data.clone());
+            for start in (0..data.len()).step_by(COPY_CHUNK_SIZE) {
+                let chunk = data.slice(start..(start + COPY_CHUNK_SIZE).min(data.len()));
+                progress.record(&chunk);
+                sink.send(chunk).await?;
+            }
+            let rows = sink.as_mut().finish().await?;
+            progress.finish(Some(rows));
+            result.rows_copied += rows;
+            result.copy_blocks += 1;
+        }
+        ScriptItem::MetaCommand(command) => {
+            log::warn!("Skipping psql meta-command during SQL import: {}", command);
+            result.skipped_meta_commands.push(command.clone());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        catalog_query_violation, is_read_only_sql, is_volatile_default, split_sql_script,
+        ScriptItem,
+    };
+
+    fn statement(sql: &str) -> ScriptItem {
+        ScriptItem::Statement(sql.to_string())
+    }
+
+    #[test]
+    fn ignores_semicolons_in_strings_and_comments() {
+        let items = split_sql_script(
+            "SELECT 'a;b', \"c;d\"; -- trailing; comment\nSELECT E'\\';' /* x; /* y; */ */;",
+        );
+        assert_eq!(
+            items,
+            vec![
+                statement("SELECT 'a;b', \"c;d\""),
+                statement("-- trailing; comment\nSELECT E'\\';' /* x; /* y; */ */"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_dollar_quoted_bodies_together() {
+        let body =
+            "CREATE FUNCTION f() RETURNS int AS $fn$ BEGIN RETURN 1; END; $fn$ LANGUAGE plpgsql";
+        let items = split_sql_script(&format!("{};\nSELECT $1::text;\nSELECT 2", body));
+        assert_eq!(
+            items,
+            vec![statement(body), statement("SELECT $1::text"), statement("SELECT 2")]
+        );
+    }
+
+    #[test]
+    fn collects_copy_data_and_meta_commands() {
+        let script = "\\connect shop\nCOPY public.items (id, name) FROM stdin;\n1\tfoo;bar\n2\tbaz\n\\.\nSELECT 1;\n";
+        assert_eq!(
+            split_sql_script(script),
+            vec![
+                ScriptItem::MetaCommand("\\connect shop".to_string()),
+                ScriptItem::CopyFromStdin {
+                    statement: "COPY public.items (id, name) FROM stdin".to_string(),
+                    data: "1\tfoo;bar\n2\tbaz\n".to_string(),
+                },
+                statement("SELECT 1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn classifies_read_only_sql() {
+        assert!(is_read_only_sql("SELECT * FROM items WHERE note = 'delete me'; -- drop"));
+        assert!(is_read_only_sql("WITH t AS (SELECT 1) SELECT \"update\" FROM t"));
+        assert!(is_read_only_sql("EXPLAIN SELECT 1"));
+
+        assert!(!is_read_only_sql(""));
+        assert!(!is_read_only_sql("SELECT 1; DELETE FROM items"));
+        assert!(!is_read_only_sql("SELECT 1; DROP TABLE items"));
+        assert!(!is_read_only_sql(
+            "WITH gone AS (DELETE FROM items RETURNING *) SELECT * FROM gone"
+        ));
+        assert!(!is_read_only_sql("SELECT * FROM items FOR UPDATE"));
+        assert!(!is_read_only_sql("UPDATE items SET name = 'x'"));
+
+        assert!(is_read_only_sql("SELECT count(*), 'setval(1)' FROM items"));
+        for call in [
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity",
+            "SELECT pg_catalog.setval('items_id_seq', 1)",
+            "SELECT nextval('items_id_seq')",
+            "SELECT set_config('search_path', 'x', false)",
+            "SELECT lo_unlink(16403)",
+            "SELECT dblink_exec('dbname=shop', 'DROP TABLE items')",
+            "SELECT * FROM items WHERE id = (SELECT \"SETVAL\"('s', 1))",
+        ] {
+            assert!(!is_read_only_sql(call), "{}", call);
+        }
+    }
+
+    #[test]
+    fn limits_catalog_queries_to_catalog_schemas() {
+        assert_eq!(
+            catalog_query_violation(
+                "SELECT n.nspname, c.relname, extract(epoch FROM now()) \
+                 FROM pg_catalog.pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace, \
+                 LATERAL (SELECT * FROM information_schema.columns) cols \
+                 WHERE c.relname = 'public.users'"
+            ),
+            None
+        );
+        assert_eq!(catalog_query_violation("TABLE pg_catalog.pg_roles"), None);
+        assert_eq!(
+            catalog_query_violation("SELECT 1 WHERE EXISTS (TABLE pg_catalog.pg_roles)"),
+            None
+        );
+
+        assert!(catalog_query_violation("SELECT * FROM public.users").is_some());
+        assert!(catalog_query_violation("SELECT * FROM pg_class, \"Public\".users").is_some());
+        assert!(catalog_query_violation("SELECT (SELECT count(*) FROM app.t)").is_some());
+        for denied in [
+            "WITH x AS (TABLE public.users) SELECT * FROM x",
+            "SELECT * FROM (TABLE public.users) t",
+            "SELECT 1 WHERE EXISTS (TABLE public.users)",
+            "TABLE pg_catalog.pg_roles UNION ALL TABLE public.users",
+        ] {
+            assert!(catalog_query_violation(denied).is_some(), "{}", denied);
+        }
+        assert!(catalog_query_violation("SELECT app.secret()").is_some());
+        assert!(
+            catalog_query_violation("SELECT query_to_xml('SELECT 1', true, true, '')").is_some()
+        );
+        for denied in [
+            "SELECT ts_stat('SELECT tsv FROM public.users')",
+            "SELECT loread(lo_open(16384, 262144), 100)",
+            "SELECT pg_catalog.pg_ls_dir('.')",
+            "SELECT pg_stat_file('postgresql.conf')",
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity",
+            "SELECT pg_cancel_backend(12345)",
+            "SELECT database_to_xml(true, true, '')",
+        ] {
+            assert!(catalog_query_violation(denied).is_some(), "{}", denied);
+        }
+        assert!(catalog_query_violation("SELECT 1 OPERATOR(public.===) 2").is_some());
+        assert_eq!(catalog_query_violation("SELECT 1 OPERATOR(pg_catalog.+) 2"), None);
+        assert!(catalog_query_violation("SELECT 1; SELECT 2").is_some());
+        assert!(catalog_query_violation("DELETE FROM pg_catalog.pg_class").is_some());
+    }
+
+    #[test]
+    fn detects_volatile_defaults() {
+        assert!(is_volatile_default("nextval('\"Order Items_id_seq\"'::regclass)"));
+        assert!(is_volatile_default("clock_timestamp()"));
+        assert!(is_volatile_default("CURRENT_TIMESTAMP"));
+        assert!(is_volatile_default("(now() AT TIME ZONE 'utc'::text)"));
+        assert!(is_volatile_default("extensions.uuid_generate_v4()"));
+        assert!(is_volatile_default("date_trunc('day'::text, now())"));
+
+        assert!(!is_volatile_default("'nextval'::text"));
+        assert!(!is_volatile_default("'now()'::character varying"));
+        assert!(!is_volatile_default("'2024-01-01 00:00:00'::timestamp without time zone"));
+        assert!(!is_volatile_default("0"));
+        assert!(!is_volatile_default("'{}'::jsonb"));
+        assert!(!is_volatile_default("\"NOW\"()"));
+    }
+}
@@ -0,0 +1,231 @@
+use super::database::{
+    build_query_result, convert_params, is_retryable_transaction_error, retries_exhausted_error,
+    retry_backoff, retry_limit, sanitize_sql_for_wrapping, ConvertedParam,
+};
+use crate::error::{Result, RowFlowError};
+use crate::state::{abandon_transaction, AppState, TransactionSession};
+use crate::types::QueryResult;
+use serde_json::Value;
+use std::time::Instant;
+use tauri::State;
+use tokio_postgres::types::ToSql;
+
+/// Map a requested isolation level onto the allowlisted SQL keyword form
+fn parse_isolation_level(level: &str) -> Result<&'static str> {
+    let normalized = level.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase();
+    match normalized.replace('_', " ").as_str() {
+        "READ COMMITTED" => Ok("READ COMMITTED"),
+        "REPEATABLE READ" => Ok("REPEATABLE READ"),
+        "SERIALIZABLE" => Ok("SERIALIZABLE"),
+        _ => Err(RowFlowError::InvalidInput(format!(
+            "Unsupported isolation level '{}'; expected READ COMMITTED, REPEATABLE READ or SERIALIZABLE",
+            level
+        ))),
+    }
+}
+
+/// Begin a transaction on a dedicated client and return its transaction ID
+#[tauri::command]
+pub async fn begin_transaction(
+    state: State<'_, AppState>,
+    connection_id: String,
+    isolation_level: Option<String>,
+) -> Result<String> {
+    log::info!("Beginning transaction on connection: {}", connection_id);
+
+    let isolation_level = isolation_level.as_deref().map(parse_isolation_level).transpose()?;
+
+    let client = state.get_client(&connection_id).await?;
+
+    if let Err(err) = start_transaction(&client, isolation_level).await {
+        if client.batch_execute("ROLLBACK").await.is_err() {
+            // The connection's state is unknown, so keep it out of the pool
+            drop(deadpool_postgres::Object::take(client));
+        }
+        return Err(err.into());
+    }
+
+    let session =
+        TransactionSession { client, isolation_level, replay_log: Vec::new(), replayable: true };
+
+    Ok(state.store_transaction(&connection_id, session).await)
+}
+
+async fn start_transaction(
+    client: &deadpool_postgres::Client,
+    isolation_level: Option<&'static str>,
+) -> std::result::Result<(), tokio_postgres::Error> {
+    client.batch_execute("BEGIN").await?;
+
+    if let Some(level) = isolation_level {
+        client.batch_execute(&format!("SET TRANSACTION ISOLATION LEVEL {}", level)).await?;
+    }
+
+    Ok(())
+}
+
+/// Commit an open transaction and release its client
+#[tauri::command]
+pub async fn commit_transaction(state: State<'_, AppState>, transaction_id: String) -> Result<()> {
+    log::info!("Committing transaction: {}", transaction_id);
+
+    let session = state.take_transaction(&transaction_id).await?;
+    let commit = session.lock().await.client.batch_execute("COMMIT").await;
+
+    if let Err(err) = commit {
+        // Never hand a client with an open transaction back to the pool
+        let _ = abandon_transaction(session).await;
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+/// Roll back an open transaction and release its client
+#[tauri::command]
+pub async fn rollback_transaction(
+    state: State<'_, AppState>,
+    transaction_id: String,
+) -> Result<()> {
+    log::info!("Rolling back transaction: {}", transaction_id);
+
+    let session = state.take_transaction(&transaction_id).await?;
+    abandon_transaction(session).await?;
+
+    Ok(())
+}
+
+/// Execute a query inside an open transaction
+#[tauri::command]
+pub async fn execute_transaction_query(
+    state: State<'_, AppState>,
+    transaction_id: String,
+    sql: String,
+    params: Vec<Value>,
+) -> Result<QueryResult> {
+    log::info!("Executing query in transaction: {}", transaction_id);
+
+    let session = state.get_transaction(&transaction_id).await?;
+    let mut session = session.lock().await;
+
+    let start = Instant::now();
+
+    let statement = session.client.prepare(&sql).await?;
+    let converted_params = convert_params(&params, statement.params())?;
+    let param_refs: Vec<&(dyn ToSql + Sync)> =
+        converted_params.iter().map(ConvertedParam::as_sql).collect();
+    let rows = session.client.query(&statement, &param_refs).await?;
+
+    // The caller may act on what it read, so replaying the transaction is no longer safe
+    session.replayable = false;
+
+    let execution_time = start.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(build_query_result(statement.columns(), &rows, execution_time, false))
+}
+
+/// Execute a data-modifying statement inside an open transaction
+///
+/// With `max_retries` set, a serialization failure or deadlock rolls the transaction back,
+/// begins it again and replays the earlier updates before retrying the statement. This is
+/// only done while the transaction has not returned query results to the caller.
+#[tauri::command]
+pub async fn execute_transaction_update(
+    state: State<'_, AppState>,
+    transaction_id: String,
+    sql: String,
+    max_retries: Option<u32>,
+) -> Result<u64> {
+    log::info!("Executing update in transaction: {}", transaction_id);
+
+    let session = state.get_transaction(&transaction_id).await?;
+    let mut session = session.lock().await;
+
+    let sql = sanitize_sql_for_wrapping(&sql);
+    let retries = retry_limit(max_retries);
+    let mut attempt = 0;
+
+    loop {
+        let outcome = if attempt == 0 {
+            run_update(&session.client, &sql).await
+        } else {
+            match replay_transaction(&session).await {
+                Ok(()) => run_update(&session.client, &sql).await,
+                Err(err) => Err(err),
+            }
+        };
+
+        match outcome {
+            Ok(affected) => {
+                session.replay_log.push(sql);
+                return Ok(affected);
+            }
+            Err(err) if is_retryable_transaction_error(&err) && attempt < retries => {
+                if !session.replayable {
+                    return Err(RowFlowError::QueryError(format!(
+                        "{}. The transaction has already returned query results, so it \
+                         cannot be retried automatically; roll back and start again",
+                        RowFlowError::from(err)
+                    )));
+                }
+                attempt += 1;
+                log::warn!(
+                    "Retrying transaction {} after {} (attempt {}/{})",
+                    transaction_id,
+                    err,
+                    attempt,
+                    retries
+                );
+                retry_backoff(attempt).await;
+            }
+            Err(err) if attempt > 0 => return Err(retries_exhausted_error(attempt, err)),
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+async fn run_update(
+    client: &deadpool_postgres::Client,
+    sql: &str,
+) -> std::result::Result<u64, tokio_postgres::Error> {
+    let statement = client.prepare(sql).await?;
+    client.execute(&statement, &[]).await
+}
+
+/// Restart an aborted transaction and re-run the updates it had already applied
+async fn replay_transaction(
+    session: &TransactionSession,
+) -> std::result::Result<(), tokio_postgres::Error> {
+    session.client.batch_execute("ROLLBACK").await?;
+    start_transaction(&session.client, session.isolation_level).await?;
+
+    for sql in &session.replay_log {
+        run_update(&session.client, sql).await?;
+    }
+
+    Ok(())
+}
+
+/// Get the isolation level in effect for a transaction, or the connection default
+#[tauri::command]
+pub async fn get_transaction_isolation(
+    state: State<'_, AppState>,
+    connection_id: String,
+    transaction_id: Option<String>,
+) -> Result<String> {
+    log::info!("Getting transaction isolation on connection: {}", connection_id);
+
+    let row = match transaction_id {
+        Some(transaction_id) => {
+            let session = state.get_transaction(&transaction_id).await?;
+            let session = session.lock().await;
+            session.client.query_one("SHOW transaction_isolation", &[]).await?
+        }
+        None => {
+            let client = state.get_client(&connection_id).await?;
+            client.query_one("SHOW transaction_isolation", &[]).await?
+        }
+    };
+
+    Ok(row.get(0))
+}
@@ -0,0 +1,195 @@
+use crate::types::CopyProgress;
+
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+/// Bytes moved between two `copy-progress` events
+const PROGRESS_BYTES_INTERVAL: u64 = 4 * 1024 * 1024;
+
+/// Rows moved between two `copy-progress` events
+const PROGRESS_ROWS_INTERVAL: u64 = 50_000;
+
+/// Counts the bytes and rows of a text-format COPY and emits `copy-progress` events
+///
+/// Rows are counted by line, which is exact for the text and CSV formats RowFlow uses unless a
+/// CSV field contains a quoted line break.
+pub struct CopyProgressReporter {
+    app: AppHandle,
+    tracker: ProgressTracker,
+    started: Instant,
+}
+
+impl CopyProgressReporter {
+    /// Start tracking a COPY; totals, when known, give the events an ETA
+    pub fn new(
+        app: AppHandle,
+        operation: &str,
+        target: String,
+        total_bytes: Option<u64>,
+        total_rows: Option<u64>,
+    ) -> Self {
+        Self {
+            app,
+            tracker: ProgressTracker::new(operation, target, total_bytes, total_rows),
+            started: Instant::now(),
+        }
+    }
+
+    /// Count a chunk of COPY data, emitting an event once enough has moved since the last one
+    pub fn record(&mut self, chunk: &[u8]) {
+        if self.tracker.record(chunk) {
+            self.emit();
+        }
+    }
+
+    /// Bytes counted so far
+    pub fn bytes(&self) -> u64 {
+        self.tracker.progress.bytes
+    }
+
+    /// Rows counted so far
+    pub fn rows(&self) -> u64 {
+        self.tracker.progress.rows
+    }
+
+    /// Emit the final event; `rows` is the count the server reported, when there is one
+    pub fn finish(mut self, rows: Option<u64>) {
+        self.tracker.finish(rows);
+        self.emit();
+    }
+
+    fn emit(&mut self) {
+        let progress = self.tracker.report(self.started.elapsed().as_secs_f64());
+        let _ = self.app.emit("copy-progress", progress);
+    }
+}
+
+/// The counting, throttling and rate arithmetic behind `CopyProgressReporter`, with the elapsed
+/// time passed in
+struct ProgressTracker {
+    progress: CopyProgress,
+    reported_bytes: u64,
+    reported_rows: u64,
+}
+
+impl ProgressTracker {
+    fn new(
+        operation: &str,
+        target: String,
+        total_bytes: Option<u64>,
+        total_rows: Option<u64>,
+    ) -> Self {
+        Self {
+            progress: CopyProgress {
+                operation: operation.to_string(),
+                target,
+                bytes: 0,
+                rows: 0,
+                total_bytes,
+                total_rows,
+                bytes_per_second: 0.0,
+                eta_seconds: None,
+                done: false,
+            },
+            reported_bytes: 0,
+            reported_rows: 0,
+        }
+    }
+
+    /// Count a chunk, returning whether enough has moved since the last report for another
+    fn record(&mut self, chunk: &[u8]) -> bool {
+        self.progress.bytes += chunk.len() as u64;
+        self.progress.rows += chunk.iter().filter(|&&byte| byte == b'\n').count() as u64;
+
+        self.progress.bytes - self.reported_bytes >= PROGRESS_BYTES_INTERVAL
+            || self.progress.rows - self.reported_rows >= PROGRESS_ROWS_INTERVAL
+    }
+
+    fn finish(&mut self, rows: Option<u64>) {
+        if let Some(rows) = rows {
+            self.progress.rows = rows;
+        }
+        self.progress.done = true;
+    }
+
+    /// Update the rate and ETA for `elapsed` seconds into the COPY and mark the counts reported
+    fn report(&mut self, elapsed: f64) -> &CopyProgress {
+        let progress = &mut self.progress;
+        progress.bytes_per_second =
+            if elapsed > 0.0 { progress.bytes as f64 / elapsed } else { 0.0 };
+
+        // Prefer bytes for the estimate since row totals usually come from planner statistics
+        let fraction = match (progress.total_bytes, progress.total_rows) {
+            (Some(total), _) if total > 0 => Some(progress.bytes as f64 / total as f64),
+            (_, Some(total)) if total > 0 => Some(progress.rows as f64 / total as f64),
+            _ => None,
+        };
+        progress.eta_seconds = match fraction {
+            _ if progress.done => Some(0.0),
+            Some(fraction) if fraction > 0.0 => {
+                Some((elapsed / fraction.min(1.0) - elapsed).max(0.0))
+            }
+            _ => None,
+        };
+
+        self.reported_bytes = progress.bytes;
+        self.reported_rows = progress.rows;
+        progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(count: usize) -> Vec<u8> {
+        b"1\tada\n".repeat(count)
+    }
+
+    #[test]
+    fn reports_once_enough_rows_or_bytes_have_moved() {
+        let mut tracker = ProgressTracker::new("copy_out", "users".to_string(), None, None);
+        assert!(!tracker.record(&rows(PROGRESS_ROWS_INTERVAL as usize - 1)));
+        assert!(tracker.record(&rows(1)));
+        tracker.report(1.0);
+
+        // Counts restart from the last report
+        assert!(!tracker.record(&rows(10)));
+        assert!(tracker.record(&vec![b'x'; PROGRESS_BYTES_INTERVAL as usize]));
+        tracker.report(2.0);
+        assert!(!tracker.record(&rows(1)));
+    }
+
+    #[test]
+    fn estimates_time_left_from_bytes_before_rows() {
+        let mut tracker =
+            ProgressTracker::new("copy_in", "users".to_string(), Some(1_000), Some(10));
+        tracker.record(&vec![b'x'; 250]);
+        let progress = tracker.report(5.0);
+        assert_eq!(progress.bytes_per_second, 50.0);
+        assert_eq!(progress.eta_seconds, Some(15.0));
+
+        let mut tracker = ProgressTracker::new("copy_in", "users".to_string(), None, Some(4));
+        tracker.record(&rows(1));
+        assert_eq!(tracker.report(2.0).eta_seconds, Some(6.0));
+
+        // A row estimate the copy has already passed doesn't give a negative ETA
+        tracker.record(&rows(9));
+        assert_eq!(tracker.report(4.0).eta_seconds, Some(0.0));
+    }
+
+    #[test]
+    fn leaves_time_left_unknown_without_totals_until_done() {
+        let mut tracker = ProgressTracker::new("copy_out", "users".to_string(), None, Some(0));
+        tracker.record(&rows(3));
+        let progress = tracker.report(0.0);
+        assert_eq!(progress.bytes_per_second, 0.0);
+        assert_eq!(progress.eta_seconds, None);
+
+        tracker.finish(Some(7));
+        let progress = tracker.report(1.0);
+        assert!(progress.done);
+        assert_eq!(progress.rows, 7);
+        assert_eq!(progress.eta_seconds, Some(0.0));
+    }
+}
@@ -16,6 +16,9 @@ pub enum RowFlowError {
     #[error("Query cancelled")]
     QueryCancelled,
 
+    #[error("Job cancelled: {0}")]
+    JobCancelled(String),
+
     #[error("Schema introspection error: {0}")]
     SchemaError(String),
 
@@ -46,6 +49,9 @@ pub enum RowFlowError {
     #[error("IO error: {0}")]
     IoError(String),
 
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
     #[error("HTTP error: {0}")]
     HttpError(String),
 
@@ -1,7 +1,9 @@
 // Re-export modules for library usage
 pub mod ai;
 pub mod commands;
+pub mod copy_progress;
 pub mod error;
+pub mod metrics;
 pub mod state;
 pub mod types;
 
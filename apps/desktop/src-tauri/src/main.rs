@@ -15,8 +15,9 @@ fn main() {
     tauri::Builder::default()
         // Initialize application state
         .setup(|app| {
-            let state = AppState::new();
+            let state = AppState::new().with_app_handle(app.handle().clone());
             app.manage(state);
+            rowflow_lib::state::spawn_idle_client_reaper(app.handle().clone());
 
             let data_dir = app.path().app_data_dir().unwrap_or_else(|_| {
                 let mut path = std::env::temp_dir();
@@ -51,25 +52,64 @@ fn main() {
             // Database connection commands
             rowflow_lib::commands::database::connect_database,
             rowflow_lib::commands::database::disconnect_database,
+            rowflow_lib::commands::database::get_connection_status,
+            rowflow_lib::commands::database::reset_all,
+            rowflow_lib::commands::database::clone_connection,
             rowflow_lib::commands::database::test_connection,
+            rowflow_lib::commands::database::diagnose_tls,
+            rowflow_lib::commands::database::diagnose_connection,
             rowflow_lib::commands::database::execute_query,
+            rowflow_lib::commands::database::get_query_metrics,
+            rowflow_lib::commands::database::create_session,
+            rowflow_lib::commands::database::close_session,
             rowflow_lib::commands::database::execute_update,
+            rowflow_lib::commands::database::execute_batch_params,
             rowflow_lib::commands::database::execute_query_stream,
+            rowflow_lib::commands::database::stream_query,
+            rowflow_lib::commands::database::ack_query_stream,
+            rowflow_lib::commands::database::cancel_query_stream,
+            rowflow_lib::commands::database::call_function,
+            rowflow_lib::commands::database::aggregate_query,
             rowflow_lib::commands::database::cancel_query,
             rowflow_lib::commands::database::get_backend_pid,
+            rowflow_lib::commands::database::terminate_backend,
+            rowflow_lib::commands::database::get_blocking_locks,
+            rowflow_lib::commands::database::get_server_settings,
+            rowflow_lib::commands::database::get_replication_status,
+            rowflow_lib::commands::database::get_top_statements,
             rowflow_lib::commands::database::insert_table_row,
             rowflow_lib::commands::database::search_foreign_key_targets,
+            rowflow_lib::commands::database::get_column_value_suggestions,
+            rowflow_lib::commands::database::get_filter_options,
+            rowflow_lib::commands::database::get_row_by_key,
+            rowflow_lib::commands::database::get_cell_bytes,
+            rowflow_lib::commands::database::get_referenced_row,
+            rowflow_lib::commands::database::get_referencing_rows,
+            rowflow_lib::commands::database::find_orphaned_rows,
+            rowflow_lib::commands::database::preview_delete_cascade,
             rowflow_lib::commands::database::delete_table_rows,
+            rowflow_lib::commands::database::delete_in_batches,
             rowflow_lib::commands::database::list_mcp_profiles,
             // Schema introspection commands
             rowflow_lib::commands::schema::list_schemas,
             rowflow_lib::commands::schema::list_tables,
             rowflow_lib::commands::schema::get_table_columns,
+            rowflow_lib::commands::schema::assert_table_schema,
             rowflow_lib::commands::schema::get_primary_keys,
             rowflow_lib::commands::schema::get_indexes,
             rowflow_lib::commands::schema::get_table_stats,
+            rowflow_lib::commands::schema::get_table_bloat,
+            rowflow_lib::commands::schema::list_extensions,
             rowflow_lib::commands::schema::get_foreign_keys,
             rowflow_lib::commands::schema::get_constraints,
+            rowflow_lib::commands::schema::get_table_dependencies,
+            rowflow_lib::commands::schema::get_triggers,
+            rowflow_lib::commands::schema::list_functions,
+            rowflow_lib::commands::schema::get_function_definition,
+            rowflow_lib::commands::schema::list_composite_types,
+            rowflow_lib::commands::schema::list_domains,
+            rowflow_lib::commands::schema::get_object_definition,
+            rowflow_lib::commands::schema::preview_ddl,
             rowflow_lib::commands::schema::create_schema,
             rowflow_lib::commands::schema::drop_schema,
             rowflow_lib::commands::schema::rename_schema,
@@ -77,14 +117,47 @@ fn main() {
             rowflow_lib::commands::schema::drop_table,
             rowflow_lib::commands::schema::add_table_column,
             rowflow_lib::commands::schema::drop_table_column,
+            rowflow_lib::commands::schema::alter_table,
+            rowflow_lib::commands::schema::run_catalog_query,
+            // Export commands
+            rowflow_lib::commands::export::export_schema_ddl,
+            rowflow_lib::commands::export::export_schema_json,
+            rowflow_lib::commands::export::get_schema_drop_order,
+            rowflow_lib::commands::export::transfer_table,
+            rowflow_lib::commands::export::export_query_to_s3,
+            rowflow_lib::commands::export::import_s3_object_to_table,
+            rowflow_lib::commands::export::table_checksum,
+            rowflow_lib::commands::export::format_query_result,
+            // Script commands
+            rowflow_lib::commands::script::import_sql_file,
+            // Transaction commands
+            rowflow_lib::commands::transaction::begin_transaction,
+            rowflow_lib::commands::transaction::commit_transaction,
+            rowflow_lib::commands::transaction::rollback_transaction,
+            rowflow_lib::commands::transaction::execute_transaction_query,
+            rowflow_lib::commands::transaction::execute_transaction_update,
+            rowflow_lib::commands::transaction::get_transaction_isolation,
+            // Notification commands
+            rowflow_lib::commands::notify::listen_channel,
+            rowflow_lib::commands::notify::unlisten_channel,
+            rowflow_lib::commands::notify::close_listener,
+            rowflow_lib::commands::notify::send_notification,
             // S3 commands
             rowflow_lib::commands::s3::connect_s3,
             rowflow_lib::commands::s3::disconnect_s3,
             rowflow_lib::commands::s3::test_s3_connection,
             rowflow_lib::commands::s3::list_s3_objects,
+            rowflow_lib::commands::s3::list_s3_object_versions,
             rowflow_lib::commands::s3::get_s3_object,
+            rowflow_lib::commands::s3::head_s3_object,
+            rowflow_lib::commands::s3::s3_object_exists,
             rowflow_lib::commands::s3::put_s3_object,
+            rowflow_lib::commands::s3::get_s3_object_tags,
+            rowflow_lib::commands::s3::put_s3_object_tags,
             rowflow_lib::commands::s3::delete_s3_objects,
+            rowflow_lib::commands::s3::rename_s3_prefix,
+            rowflow_lib::commands::s3::get_s3_prefix_stats,
+            rowflow_lib::commands::s3::cancel_s3_operation,
             rowflow_lib::commands::s3::get_s3_presigned_url,
             // AI + embeddings
             rowflow_lib::commands::ai::check_ollama_status,
@@ -95,11 +168,26 @@ fn main() {
             rowflow_lib::commands::ai::pull_ollama_model,
             rowflow_lib::commands::ai::embed_table,
             rowflow_lib::commands::ai::search_embeddings,
+            rowflow_lib::commands::ai::find_similar_rows,
             rowflow_lib::commands::ai::get_embedding_metadata,
             rowflow_lib::commands::ai::generate_sql_from_question,
             rowflow_lib::commands::ai::classify_user_message,
+            rowflow_lib::commands::ai::generate_agent_sql,
+            rowflow_lib::commands::ai::classify_and_chat,
+            rowflow_lib::commands::ai::record_agent_result,
+            rowflow_lib::commands::ai::clear_agent_session,
             rowflow_lib::commands::ai::delete_table_embeddings,
+            rowflow_lib::commands::ai::export_embeddings,
+            rowflow_lib::commands::ai::import_embeddings,
             rowflow_lib::commands::ai::generate_test_data,
+            rowflow_lib::commands::ai::generate_and_insert_test_data,
+            rowflow_lib::commands::ai::preview_test_data_prompt,
+            rowflow_lib::commands::ai::infer_column_semantics,
+            rowflow_lib::commands::ai::cancel_ai_job,
+            rowflow_lib::commands::ai::list_ai_jobs,
+            rowflow_lib::commands::ai::get_ollama_request_limit,
+            rowflow_lib::commands::ai::set_ollama_request_limit,
+            rowflow_lib::commands::ai::set_ollama_auto_pull,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,121 @@
+use crate::types::QueryMetrics;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Samples kept per connection; the oldest are dropped first
+const MAX_SAMPLES_PER_CONNECTION: usize = 1000;
+
+struct QuerySample {
+    at: Instant,
+    latency_ms: f64,
+    ok: bool,
+}
+
+/// Recent query latencies per connection, bounded by a ring buffer per connection
+///
+/// Recording only pushes onto a `VecDeque` under a short lock; percentiles are computed when
+/// the metrics are read. Cloning is cheap and every clone shares the same samples.
+#[derive(Clone, Default)]
+pub struct QueryMetricsCollector {
+    samples: Arc<Mutex<HashMap<String, VecDeque<QuerySample>>>>,
+}
+
+impl QueryMetricsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one query's latency and whether it succeeded
+    pub fn record(&self, connection_id: &str, latency_ms: f64, ok: bool) {
+        let mut samples = self.samples.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let ring = match samples.get_mut(connection_id) {
+            Some(ring) => ring,
+            None => samples
+                .entry(connection_id.to_string())
+                .or_insert_with(|| VecDeque::with_capacity(MAX_SAMPLES_PER_CONNECTION)),
+        };
+        if ring.len() == MAX_SAMPLES_PER_CONNECTION {
+            ring.pop_front();
+        }
+        ring.push_back(QuerySample { at: Instant::now(), latency_ms, ok });
+    }
+
+    /// Summarize the samples recorded in the last `window`, or all kept samples without one
+    ///
+    /// Percentiles cover successful queries only, since failures often return early.
+    pub fn summary(&self, connection_id: &str, window: Option<Duration>) -> QueryMetrics {
+        let (mut latencies, count) = {
+            let samples = self.samples.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let cutoff = window.and_then(|window| Instant::now().checked_sub(window));
+            let recent: Vec<&QuerySample> = samples
+                .get(connection_id)
+                .into_iter()
+                .flatten()
+                .filter(|sample| cutoff.is_none_or(|cutoff| sample.at >= cutoff))
+                .collect();
+            let latencies: Vec<f64> =
+                recent.iter().filter(|sample| sample.ok).map(|sample| sample.latency_ms).collect();
+            (latencies, recent.len())
+        };
+        latencies.sort_by(f64::total_cmp);
+
+        let error_count = count - latencies.len();
+        QueryMetrics {
+            connection_id: connection_id.to_string(),
+            count,
+            error_count,
+            error_rate: if count == 0 { 0.0 } else { error_count as f64 / count as f64 },
+            p50: percentile(&latencies, 0.50),
+            p95: percentile(&latencies, 0.95),
+            p99: percentile(&latencies, 0.99),
+        }
+    }
+
+    /// Forget a connection's samples
+    pub fn remove(&self, connection_id: &str) {
+        let mut samples = self.samples.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        samples.remove(connection_id);
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], quantile: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryMetricsCollector, MAX_SAMPLES_PER_CONNECTION};
+
+    #[test]
+    fn summary_reports_percentiles_and_error_rate() {
+        let metrics = QueryMetricsCollector::new();
+        for latency in 1..=100 {
+            metrics.record("a", latency as f64, true);
+        }
+        metrics.record("a", 5000.0, false);
+
+        let summary = metrics.summary("a", None);
+        assert_eq!(summary.count, 101);
+        assert_eq!(summary.error_count, 1);
+        assert_eq!(summary.p50, Some(50.0));
+        assert_eq!(summary.p95, Some(95.0));
+        assert_eq!(summary.p99, Some(99.0));
+
+        assert_eq!(metrics.summary("b", None).p50, None);
+
+        for _ in 0..MAX_SAMPLES_PER_CONNECTION {
+            metrics.record("a", 1.0, true);
+        }
+        assert_eq!(metrics.summary("a", None).count, MAX_SAMPLES_PER_CONNECTION);
+
+        metrics.remove("a");
+        assert_eq!(metrics.summary("a", None).count, 0);
+    }
+}
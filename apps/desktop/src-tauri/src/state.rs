@@ -1,18 +1,50 @@
+use crate::commands::schema::{quote_identifier, validate_identifier};
 use crate::error::{Result, RowFlowError};
-use crate::types::{ConnectionProfile, S3ConnectionProfile};
+use crate::metrics::QueryMetricsCollector;
+use crate::types::{
+    ChannelBindingMode, ConnectionProfile, ConnectionStatus, ConnectionStatusUpdate,
+    PgNotification, PoolRecyclingMethod, S3ConnectionProfile, SslNegotiationMode, TlsConfig,
+};
 use aws_sdk_s3::Client as S3Client;
 use deadpool_postgres::{Manager, ManagerConfig, Object, Pool, RecyclingMethod};
+use futures_util::StreamExt;
 use postgres_native_tls::MakeTlsConnector;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tokio_postgres::NoTls;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager as _};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{Mutex, Semaphore};
+use tokio_postgres::config::{ChannelBinding, SslNegotiation};
+use tokio_postgres::{AsyncMessage, NoTls, Statement};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// Maximum number of distinct SQL texts kept prepared per connection
+const STATEMENT_CACHE_CAPACITY: usize = 100;
+
+/// How long a managed transaction may go unused before it is rolled back and its client released
+const TRANSACTION_IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// How long a session may go unused before its client is reset and returned to the pool
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How often abandoned transactions and sessions are looked for
+const IDLE_CLIENT_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Application state managing database and S3 connections
 pub struct AppState {
     connections: Arc<Mutex<HashMap<String, ConnectionPool>>>,
     s3_connections: Arc<Mutex<HashMap<String, S3ConnectionPool>>>,
+    active_backends: Arc<Mutex<HashMap<String, i32>>>,
+    transactions: Arc<Mutex<HashMap<String, ManagedTransaction>>>,
+    query_streams: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+    s3_operations: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    sessions: Arc<Mutex<HashMap<String, PinnedSession>>>,
+    notification_listeners: Arc<Mutex<HashMap<String, NotificationListener>>>,
+    query_metrics: QueryMetricsCollector,
+    connection_statuses: Arc<Mutex<HashMap<String, ConnectionStatusUpdate>>>,
+    app_handle: Option<AppHandle>,
 }
 
 impl AppState {
@@ -20,19 +52,110 @@ impl AppState {
         Self {
             connections: Arc::new(Mutex::new(HashMap::new())),
             s3_connections: Arc::new(Mutex::new(HashMap::new())),
+            active_backends: Arc::new(Mutex::new(HashMap::new())),
+            transactions: Arc::new(Mutex::new(HashMap::new())),
+            query_streams: Arc::new(Mutex::new(HashMap::new())),
+            s3_operations: Arc::new(Mutex::new(HashMap::new())),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            notification_listeners: Arc::new(Mutex::new(HashMap::new())),
+            query_metrics: QueryMetricsCollector::new(),
+            connection_statuses: Arc::new(Mutex::new(HashMap::new())),
+            app_handle: None,
+        }
+    }
+
+    /// Emit `connection-status` events through the app whenever a connection's status changes
+    ///
+    /// Without an app handle, as for the temporary state `test_connection` uses, statuses are
+    /// still tracked but nothing is emitted.
+    pub fn with_app_handle(mut self, app_handle: AppHandle) -> Self {
+        self.app_handle = Some(app_handle);
+        self
+    }
+
+    /// Record a connection's status and tell the frontend about it
+    async fn set_connection_status(
+        &self,
+        connection_id: &str,
+        status: ConnectionStatus,
+        error: Option<String>,
+    ) {
+        let update = ConnectionStatusUpdate {
+            connection_id: connection_id.to_string(),
+            status,
+            error,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        };
+        if let Some(ref app_handle) = self.app_handle {
+            let _ = app_handle.emit("connection-status", &update);
+        }
+
+        let mut statuses = self.connection_statuses.lock().await;
+        match status {
+            // Forget connections that no longer exist once the frontend has been told
+            ConnectionStatus::Disconnected => {
+                statuses.remove(connection_id);
+            }
+            _ => {
+                statuses.insert(connection_id.to_string(), update);
+            }
         }
     }
 
+    /// The last status recorded for a connection
+    pub async fn connection_status(&self, connection_id: &str) -> Result<ConnectionStatusUpdate> {
+        let statuses = self.connection_statuses.lock().await;
+        statuses
+            .get(connection_id)
+            .cloned()
+            .ok_or_else(|| RowFlowError::ConnectionNotFound(connection_id.to_string()))
+    }
+
     /// Create a new database connection pool
     pub async fn create_connection(&self, profile: ConnectionProfile) -> Result<String> {
         let connection_id = Uuid::new_v4().to_string();
+        self.set_connection_status(&connection_id, ConnectionStatus::Connecting, None).await;
 
+        match self.open_connection(&connection_id, profile).await {
+            Ok(()) => {
+                self.set_connection_status(&connection_id, ConnectionStatus::Connected, None).await;
+                Ok(connection_id)
+            }
+            Err(err) => {
+                self.set_connection_status(
+                    &connection_id,
+                    ConnectionStatus::Error,
+                    Some(err.to_string()),
+                )
+                .await;
+                // The ID was never returned, so nothing will ask about it again
+                self.connection_statuses.lock().await.remove(&connection_id);
+                Err(err)
+            }
+        }
+    }
+
+    async fn open_connection(&self, connection_id: &str, profile: ConnectionProfile) -> Result<()> {
         // Build the connection pool
         let pool = Self::build_pool(&profile).await?;
 
         // Test the connection
         let client = pool.get().await.map_err(|e| {
-            RowFlowError::ConnectionError(format!("Failed to get connection from pool: {}", e))
+            let message = e.to_string();
+            if profile.channel_binding == ChannelBindingMode::Require
+                && message.contains("channel binding")
+            {
+                RowFlowError::ConnectionError(format!(
+                    "Channel binding is required but the server did not authenticate with \
+                     SCRAM-SHA-256-PLUS: {}",
+                    message
+                ))
+            } else {
+                RowFlowError::ConnectionError(format!(
+                    "Failed to get connection from pool: {}",
+                    message
+                ))
+            }
         })?;
 
         // Verify connection is working
@@ -40,15 +163,38 @@ impl AppState {
             RowFlowError::ConnectionError(format!("Connection test query failed: {}", e))
         })?;
 
+        // Reject timezone names the server doesn't know before they fail every checkout
+        if let Some(ref timezone) = profile.session_timezone {
+            let known: bool = client
+                .query_one(
+                    "SELECT EXISTS (SELECT 1 FROM pg_catalog.pg_timezone_names WHERE name = $1)",
+                    &[timezone],
+                )
+                .await?
+                .get(0);
+            if !known {
+                return Err(RowFlowError::InvalidProfile(format!(
+                    "Unknown session timezone: {}",
+                    timezone
+                )));
+            }
+        }
+
         // Set session parameters
         Self::set_session_parameters(&client, &profile).await?;
 
         // Store the connection pool
         let mut connections = self.connections.lock().await;
-        connections
-            .insert(connection_id.clone(), ConnectionPool { pool, profile: profile.clone() });
+        connections.insert(
+            connection_id.to_string(),
+            ConnectionPool {
+                pool,
+                profile: profile.clone(),
+                statement_lru: StatementLru::new(STATEMENT_CACHE_CAPACITY),
+            },
+        );
 
-        Ok(connection_id)
+        Ok(())
     }
 
     /// Get an existing connection pool
@@ -61,6 +207,9 @@ impl AppState {
     }
 
     /// Acquire a client from the pool with session parameters applied
+    ///
+    /// A failed checkout marks the connection as errored; the next checkout reports it as
+    /// reconnecting while the pool opens a fresh server connection, then as connected again.
     pub async fn get_client(&self, connection_id: &str) -> Result<Object> {
         let (pool, profile) = {
             let connections = self.connections.lock().await;
@@ -70,7 +219,31 @@ impl AppState {
                 .ok_or_else(|| RowFlowError::ConnectionNotFound(connection_id.to_string()))?
         };
 
-        let client = pool.get().await?;
+        let reconnecting = matches!(
+            self.connection_status(connection_id).await.map(|update| update.status),
+            Ok(ConnectionStatus::Error | ConnectionStatus::Reconnecting)
+        );
+        if reconnecting {
+            self.set_connection_status(connection_id, ConnectionStatus::Reconnecting, None).await;
+        }
+
+        let client = match pool.get().await {
+            Ok(client) => client,
+            Err(err) => {
+                let err = RowFlowError::from(err);
+                self.set_connection_status(
+                    connection_id,
+                    ConnectionStatus::Error,
+                    Some(err.to_string()),
+                )
+                .await;
+                return Err(err);
+            }
+        };
+        if reconnecting {
+            self.set_connection_status(connection_id, ConnectionStatus::Connected, None).await;
+        }
+
         Self::set_session_parameters(&client, &profile).await?;
         Ok(client)
     }
@@ -84,15 +257,103 @@ impl AppState {
             .ok_or_else(|| RowFlowError::ConnectionNotFound(connection_id.to_string()))
     }
 
-    /// Remove a connection pool
+    /// Close and remove a connection pool along with its pinned clients
+    ///
+    /// Closing the pool stops new checkouts and drops its idle clients; clients still checked out
+    /// by a running command are released when that command finishes.
     pub async fn remove_connection(&self, connection_id: &str) -> Result<()> {
         let mut connections = self.connections.lock().await;
-        connections
+        let cp = connections
             .remove(connection_id)
             .ok_or_else(|| RowFlowError::ConnectionNotFound(connection_id.to_string()))?;
+        cp.pool.close();
+        self.active_backends.lock().await.remove(connection_id);
+        self.transactions.lock().await.retain(|_, tx| tx.connection_id != connection_id);
+        self.sessions.lock().await.retain(|_, session| session.connection_id != connection_id);
+        self.notification_listeners
+            .lock()
+            .await
+            .retain(|_, listener| listener.connection_id != connection_id);
+        self.query_metrics.remove(connection_id);
+        drop(connections);
+        self.set_connection_status(connection_id, ConnectionStatus::Disconnected, None).await;
         Ok(())
     }
 
+    /// Remove every database connection and stop all open query streams, returning how many
+    /// connections were closed
+    pub async fn remove_all_connections(&self) -> usize {
+        let connection_ids = self.list_connections().await;
+        let mut removed = 0;
+        for connection_id in &connection_ids {
+            if self.remove_connection(connection_id).await.is_ok() {
+                removed += 1;
+            }
+        }
+        for (_, credits) in self.query_streams.lock().await.drain() {
+            credits.close();
+        }
+        removed
+    }
+
+    /// Latencies of recent queries, per connection
+    pub fn query_metrics(&self) -> &QueryMetricsCollector {
+        &self.query_metrics
+    }
+
+    /// Record the backend running a long query so it can be cancelled by connection
+    pub async fn set_active_backend(&self, connection_id: &str, backend_pid: i32) {
+        let mut active_backends = self.active_backends.lock().await;
+        active_backends.insert(connection_id.to_string(), backend_pid);
+    }
+
+    /// Forget the active backend, unless a newer query has already replaced it
+    pub async fn clear_active_backend(&self, connection_id: &str, backend_pid: i32) {
+        let mut active_backends = self.active_backends.lock().await;
+        if active_backends.get(connection_id) == Some(&backend_pid) {
+            active_backends.remove(connection_id);
+        }
+    }
+
+    /// Get the backend currently running a long query on a connection
+    pub async fn active_backend(&self, connection_id: &str) -> Option<i32> {
+        let active_backends = self.active_backends.lock().await;
+        active_backends.get(connection_id).copied()
+    }
+
+    /// Prepare a statement through the client's statement cache, tracking its use per connection
+    ///
+    /// Cached statements live on each pooled client (and so on its server session), which keeps
+    /// them valid across recycling; even `RecyclingMethod::Clean` doesn't deallocate them. The SQL
+    /// text that falls out of the connection's LRU is dropped from every client in the pool,
+    /// bounding the cache size.
+    pub async fn prepare_cached(
+        &self,
+        connection_id: &str,
+        client: &Object,
+        sql: &str,
+    ) -> Result<Statement> {
+        let statement = client.prepare_cached(sql).await?;
+
+        let mut connections = self.connections.lock().await;
+        if let Some(cp) = connections.get_mut(connection_id) {
+            if let Some(evicted) = cp.statement_lru.touch(sql) {
+                cp.pool.manager().statement_caches.remove(&evicted, &[]);
+            }
+        }
+
+        Ok(statement)
+    }
+
+    /// Drop a cached statement from every client, e.g. after DDL changed its result type
+    pub async fn invalidate_cached_statement(&self, connection_id: &str, sql: &str) {
+        let mut connections = self.connections.lock().await;
+        if let Some(cp) = connections.get_mut(connection_id) {
+            cp.statement_lru.remove(sql);
+            cp.pool.manager().statement_caches.remove(sql, &[]);
+        }
+    }
+
     /// List all active connection IDs
     pub async fn list_connections(&self) -> Vec<String> {
         let connections = self.connections.lock().await;
@@ -101,52 +362,39 @@ impl AppState {
 
     /// Build a connection pool from a profile
     async fn build_pool(profile: &ConnectionProfile) -> Result<Pool> {
-        // Build tokio_postgres::Config
-        let mut pg_config = tokio_postgres::Config::new();
-        pg_config.host(&profile.host);
-        pg_config.port(profile.port);
-        pg_config.dbname(&profile.database);
-        pg_config.user(&profile.username);
+        let pg_config = build_pg_config(profile);
 
-        if let Some(ref password) = profile.password {
-            pg_config.password(password);
+        // Channel binding and direct TLS both need TLS, so fail early rather than at connect time
+        let tls_enabled = profile.tls_config.as_ref().is_some_and(|tls| tls.enabled);
+        if profile.channel_binding == ChannelBindingMode::Require && !tls_enabled {
+            return Err(RowFlowError::InvalidProfile(
+                "Channel binding 'require' needs TLS to be enabled".to_string(),
+            ));
         }
-
-        // Connection timeout
-        if let Some(timeout) = profile.connection_timeout {
-            pg_config.connect_timeout(std::time::Duration::from_secs(timeout));
+        if profile.ssl_negotiation == SslNegotiationMode::Direct && !tls_enabled {
+            return Err(RowFlowError::InvalidProfile(
+                "Direct SSL negotiation needs TLS to be enabled".to_string(),
+            ));
         }
 
         // Manager configuration
-        let manager_config = ManagerConfig { recycling_method: RecyclingMethod::Fast };
+        let recycling_method = match profile.recycling_method {
+            PoolRecyclingMethod::Fast => RecyclingMethod::Fast,
+            PoolRecyclingMethod::Verified => RecyclingMethod::Verified,
+            PoolRecyclingMethod::Clean => RecyclingMethod::Clean,
+        };
+        let manager_config = ManagerConfig { recycling_method };
 
         // TLS configuration
         if let Some(ref tls_config) = profile.tls_config {
             if tls_config.enabled {
-                let mut builder = native_tls::TlsConnector::builder();
-
-                // Verify CA
-                builder.danger_accept_invalid_certs(!tls_config.verify_ca);
-
-                // Load CA certificate if provided
-                if let Some(ref ca_path) = tls_config.ca_cert_path {
-                    let ca_cert = std::fs::read(ca_path)?;
-                    let cert = native_tls::Certificate::from_pem(&ca_cert)?;
-                    builder.add_root_certificate(cert);
-                }
-
-                // Load client certificate if provided
-                if let (Some(ref cert_path), Some(ref key_path)) =
-                    (&tls_config.client_cert_path, &tls_config.client_key_path)
-                {
-                    let cert = std::fs::read(cert_path)?;
-                    let key = std::fs::read(key_path)?;
-                    let identity = native_tls::Identity::from_pkcs8(&cert, &key)?;
-                    builder.identity(identity);
-                }
-
-                let connector = builder.build()?;
-                let tls_connector = MakeTlsConnector::new(connector);
+                // Verifying the CA also verifies the host name, as native-tls does by default
+                let verification = if tls_config.verify_ca {
+                    CertVerification::Full
+                } else {
+                    CertVerification::None
+                };
+                let tls_connector = build_tls_connector(profile, Some(tls_config), verification)?;
 
                 let manager = Manager::from_config(pg_config, tls_connector, manager_config);
 
@@ -187,8 +435,22 @@ impl AppState {
             client.execute(&query, &[]).await?;
         }
 
-        // Set timezone to UTC for consistency
-        client.execute("SET timezone = 'UTC'", &[]).await?;
+        // Set search path so unqualified names resolve to the preferred schemas
+        if let Some(ref search_path) = profile.search_path {
+            if !search_path.is_empty() {
+                let mut schemas = Vec::with_capacity(search_path.len());
+                for schema in search_path {
+                    validate_identifier(schema, "Schema")?;
+                    schemas.push(quote_identifier(schema));
+                }
+                let query = format!("SET search_path = {}", schemas.join(", "));
+                client.execute(&query, &[]).await?;
+            }
+        }
+
+        // Use the profile's timezone for timestamptz values, UTC unless configured otherwise
+        let timezone = profile.session_timezone.as_deref().unwrap_or("UTC");
+        client.execute("SELECT set_config('TimeZone', $1, false)", &[&timezone]).await?;
 
         Ok(())
     }
@@ -200,10 +462,154 @@ impl Default for AppState {
     }
 }
 
+/// How much of the server certificate a TLS connection checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CertVerification {
+    /// Accept any certificate, as sslmode `prefer` and `require`
+    None,
+    /// Check the certificate chain but not the host name, as sslmode `verify-ca`
+    Ca,
+    /// Check the chain and that it was issued for the host, as sslmode `verify-full`
+    Full,
+}
+
+/// Connection settings from a profile, without TLS
+pub(crate) fn build_pg_config(profile: &ConnectionProfile) -> tokio_postgres::Config {
+    let mut pg_config = tokio_postgres::Config::new();
+    pg_config.host(&profile.host);
+    pg_config.port(profile.port);
+    pg_config.dbname(&profile.database);
+    pg_config.user(&profile.username);
+
+    if let Some(ref password) = profile.password {
+        pg_config.password(password);
+    }
+
+    // Connection timeout
+    if let Some(timeout) = profile.connection_timeout {
+        pg_config.connect_timeout(std::time::Duration::from_secs(timeout));
+    }
+
+    pg_config.channel_binding(match profile.channel_binding {
+        ChannelBindingMode::Disable => ChannelBinding::Disable,
+        ChannelBindingMode::Prefer => ChannelBinding::Prefer,
+        ChannelBindingMode::Require => ChannelBinding::Require,
+    });
+    pg_config.ssl_negotiation(match profile.ssl_negotiation {
+        SslNegotiationMode::Postgres => SslNegotiation::Postgres,
+        SslNegotiationMode::Direct => SslNegotiation::Direct,
+    });
+
+    pg_config
+}
+
+/// Build a TLS connector using the profile's CA and client certificates, if any
+pub(crate) fn build_tls_connector(
+    profile: &ConnectionProfile,
+    tls_config: Option<&TlsConfig>,
+    verification: CertVerification,
+) -> Result<MakeTlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+    builder.danger_accept_invalid_certs(verification == CertVerification::None);
+    builder.danger_accept_invalid_hostnames(verification != CertVerification::Full);
+
+    // Servers only accept direct TLS connections that negotiate the postgresql ALPN
+    if profile.ssl_negotiation == SslNegotiationMode::Direct {
+        builder.request_alpns(&["postgresql"]);
+    }
+
+    if let Some(tls_config) = tls_config {
+        // Load CA certificate if provided
+        if let Some(ref ca_path) = tls_config.ca_cert_path {
+            let ca_cert = std::fs::read(ca_path)?;
+            let cert = native_tls::Certificate::from_pem(&ca_cert)?;
+            builder.add_root_certificate(cert);
+        }
+
+        // Load client certificate if provided
+        if let (Some(ref cert_path), Some(ref key_path)) =
+            (&tls_config.client_cert_path, &tls_config.client_key_path)
+        {
+            let cert = std::fs::read(cert_path)?;
+            let key = std::fs::read(key_path)?;
+            let identity = native_tls::Identity::from_pkcs8(&cert, &key)?;
+            builder.identity(identity);
+        }
+    }
+
+    Ok(MakeTlsConnector::new(builder.build()?))
+}
+
 /// Wrapper for a connection pool with its profile
 struct ConnectionPool {
     pool: Pool,
     profile: ConnectionProfile,
+    statement_lru: StatementLru,
+}
+
+/// Recency order of the SQL texts in a connection's prepared statement cache
+struct StatementLru {
+    capacity: usize,
+    order: VecDeque<String>,
+}
+
+impl StatementLru {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, order: VecDeque::with_capacity(capacity) }
+    }
+
+    /// Mark SQL as most recently used, returning the least recently used SQL if it was evicted
+    fn touch(&mut self, sql: &str) -> Option<String> {
+        if let Some(pos) = self.order.iter().position(|cached| cached == sql) {
+            let cached = self.order.remove(pos)?;
+            self.order.push_back(cached);
+            return None;
+        }
+
+        self.order.push_back(sql.to_string());
+        if self.order.len() > self.capacity {
+            self.order.pop_front()
+        } else {
+            None
+        }
+    }
+
+    fn remove(&mut self, sql: &str) {
+        self.order.retain(|cached| cached != sql);
+    }
+}
+
+/// A pooled client held out of the pool for a transaction spanning several commands
+struct ManagedTransaction {
+    connection_id: String,
+    session: Arc<Mutex<TransactionSession>>,
+    last_used: Instant,
+}
+
+/// A pooled client held out of the pool so session state survives between commands
+struct PinnedSession {
+    connection_id: String,
+    client: Arc<Mutex<Object>>,
+    last_used: Instant,
+}
+
+/// A connection outside the pool kept open for LISTEN, and the channels it listens on
+///
+/// Dropping the client closes the connection, which ends the task forwarding its notifications.
+struct NotificationListener {
+    connection_id: String,
+    client: Arc<tokio_postgres::Client>,
+    channels: BTreeSet<String>,
+}
+
+/// Client and bookkeeping for an open managed transaction
+pub struct TransactionSession {
+    pub client: Object,
+    pub isolation_level: Option<&'static str>,
+    /// Updates executed so far, replayed when the transaction is retried
+    pub replay_log: Vec<String>,
+    /// Cleared once a query has returned data the caller may have acted on
+    pub replayable: bool,
 }
 
 /// Wrapper for an S3 client with its profile
@@ -212,6 +618,431 @@ struct S3ConnectionPool {
     profile: S3ConnectionProfile,
 }
 
+impl AppState {
+    /// Keep a client with an open transaction checked out and return the transaction ID
+    pub async fn store_transaction(
+        &self,
+        connection_id: &str,
+        session: TransactionSession,
+    ) -> String {
+        let transaction_id = Uuid::new_v4().to_string();
+
+        let mut transactions = self.transactions.lock().await;
+        transactions.insert(
+            transaction_id.clone(),
+            ManagedTransaction {
+                connection_id: connection_id.to_string(),
+                session: Arc::new(Mutex::new(session)),
+                last_used: Instant::now(),
+            },
+        );
+
+        transaction_id
+    }
+
+    /// Get the session of an open transaction
+    pub async fn get_transaction(
+        &self,
+        transaction_id: &str,
+    ) -> Result<Arc<Mutex<TransactionSession>>> {
+        let mut transactions = self.transactions.lock().await;
+        let transaction = transactions.get_mut(transaction_id).ok_or_else(|| {
+            RowFlowError::InvalidInput(format!("Transaction not found: {}", transaction_id))
+        })?;
+        transaction.last_used = Instant::now();
+        Ok(transaction.session.clone())
+    }
+
+    /// Remove an open transaction, returning its session so it can be committed or rolled back
+    pub async fn take_transaction(
+        &self,
+        transaction_id: &str,
+    ) -> Result<Arc<Mutex<TransactionSession>>> {
+        let mut transactions = self.transactions.lock().await;
+        transactions.remove(transaction_id).map(|tx| tx.session).ok_or_else(|| {
+            RowFlowError::InvalidInput(format!("Transaction not found: {}", transaction_id))
+        })
+    }
+
+    /// Roll back transactions left unused for longer than `TRANSACTION_IDLE_TIMEOUT`,
+    /// returning how many were reaped
+    ///
+    /// A transaction whose session is locked is running a statement and is left alone.
+    pub async fn reap_idle_transactions(&self) -> usize {
+        let expired: Vec<(String, ManagedTransaction)> = {
+            let mut transactions = self.transactions.lock().await;
+            let expired_ids: Vec<String> = transactions
+                .iter()
+                .filter(|(_, tx)| {
+                    tx.last_used.elapsed() >= TRANSACTION_IDLE_TIMEOUT
+                        && tx.session.try_lock().is_ok()
+                })
+                .map(|(transaction_id, _)| transaction_id.clone())
+                .collect();
+            expired_ids
+                .into_iter()
+                .filter_map(|transaction_id| {
+                    transactions.remove(&transaction_id).map(|tx| (transaction_id, tx))
+                })
+                .collect()
+        };
+
+        for (transaction_id, transaction) in &expired {
+            log::warn!(
+                "Rolling back transaction {} on connection {} after {:?} idle",
+                transaction_id,
+                transaction.connection_id,
+                TRANSACTION_IDLE_TIMEOUT
+            );
+        }
+        let count = expired.len();
+        for (_, transaction) in expired {
+            let _ = abandon_transaction(transaction.session).await;
+        }
+        count
+    }
+}
+
+/// Roll back a transaction that will not be committed, discarding its client instead of
+/// returning it to the pool when the rollback fails
+pub async fn abandon_transaction(
+    session: Arc<Mutex<TransactionSession>>,
+) -> std::result::Result<(), tokio_postgres::Error> {
+    let rollback = session.lock().await.client.batch_execute("ROLLBACK").await;
+    if let Err(err) = &rollback {
+        log::warn!("Rollback failed, discarding the client: {}", err);
+        discard_transaction_client(session);
+    }
+    rollback
+}
+
+/// Detach a transaction's client from its pool so its connection closes when dropped
+fn discard_transaction_client(session: Arc<Mutex<TransactionSession>>) {
+    match Arc::try_unwrap(session) {
+        Ok(session) => drop(Object::take(session.into_inner().client)),
+        Err(_) => log::warn!("Transaction client is still in use and could not be discarded"),
+    }
+}
+
+/// Periodically release clients held by abandoned transactions and sessions
+pub fn spawn_idle_client_reaper(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(IDLE_CLIENT_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let state = app.state::<AppState>();
+            state.reap_idle_transactions().await;
+            state.reap_idle_sessions().await;
+        }
+    });
+}
+
+impl AppState {
+    /// Keep a client checked out as a session and return the session ID
+    pub async fn store_session(&self, connection_id: &str, client: Object) -> String {
+        let session_id = Uuid::new_v4().to_string();
+
+        let mut sessions = self.sessions.lock().await;
+        sessions.insert(
+            session_id.clone(),
+            PinnedSession {
+                connection_id: connection_id.to_string(),
+                client: Arc::new(Mutex::new(client)),
+                last_used: Instant::now(),
+            },
+        );
+
+        session_id
+    }
+
+    /// Get the client of a session, checking it belongs to the given connection
+    pub async fn get_session(
+        &self,
+        session_id: &str,
+        connection_id: &str,
+    ) -> Result<Arc<Mutex<Object>>> {
+        let mut sessions = self.sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .filter(|session| session.connection_id == connection_id)
+            .ok_or_else(|| {
+                RowFlowError::InvalidInput(format!("Session not found: {}", session_id))
+            })?;
+        session.last_used = Instant::now();
+        Ok(session.client.clone())
+    }
+
+    /// Remove a session, returning its client
+    pub async fn take_session(&self, session_id: &str) -> Result<Arc<Mutex<Object>>> {
+        let mut sessions = self.sessions.lock().await;
+        sessions
+            .remove(session_id)
+            .map(|session| session.client)
+            .ok_or_else(|| RowFlowError::InvalidInput(format!("Session not found: {}", session_id)))
+    }
+
+    /// Release sessions left unused for longer than `SESSION_IDLE_TIMEOUT`, returning how many
+    /// were reaped
+    ///
+    /// A session whose client is locked is running a query and is left alone.
+    pub async fn reap_idle_sessions(&self) -> usize {
+        let expired: Vec<(String, PinnedSession)> = {
+            let mut sessions = self.sessions.lock().await;
+            let expired_ids: Vec<String> = sessions
+                .iter()
+                .filter(|(_, session)| {
+                    session.last_used.elapsed() >= SESSION_IDLE_TIMEOUT
+                        && session.client.try_lock().is_ok()
+                })
+                .map(|(session_id, _)| session_id.clone())
+                .collect();
+            expired_ids
+                .into_iter()
+                .filter_map(|session_id| {
+                    sessions.remove(&session_id).map(|session| (session_id, session))
+                })
+                .collect()
+        };
+
+        for (session_id, session) in &expired {
+            log::warn!(
+                "Closing session {} on connection {} after {:?} idle",
+                session_id,
+                session.connection_id,
+                SESSION_IDLE_TIMEOUT
+            );
+        }
+        let count = expired.len();
+        for (_, session) in expired {
+            let _ = release_session(session.client).await;
+        }
+        count
+    }
+}
+
+/// Reset a session's client before it goes back to the pool, discarding it instead when the
+/// reset fails
+///
+/// The statement cache is cleared either way: DISCARD ALL deallocates every prepared statement,
+/// and a failed reset may have left it half done.
+pub async fn release_session(
+    client: Arc<Mutex<Object>>,
+) -> std::result::Result<(), tokio_postgres::Error> {
+    let reset = {
+        let client = client.lock().await;
+        client.statement_cache.clear();
+        // DISCARD ALL can't run inside a transaction block, so end any the session left open first
+        match client.batch_execute("ROLLBACK").await {
+            Ok(()) => client.batch_execute("DISCARD ALL").await,
+            Err(err) => Err(err),
+        }
+    };
+    if let Err(err) = &reset {
+        log::warn!("Session reset failed, discarding the client: {}", err);
+        match Arc::try_unwrap(client) {
+            Ok(client) => drop(Object::take(client.into_inner())),
+            Err(_) => log::warn!("Session client is still in use and could not be discarded"),
+        }
+    }
+    reset
+}
+
+impl AppState {
+    /// Register a streamed query with a window of batches it may send before being acknowledged
+    pub async fn register_query_stream(&self, stream_id: &str, window: usize) -> Arc<Semaphore> {
+        let credits = Arc::new(Semaphore::new(window));
+        let mut query_streams = self.query_streams.lock().await;
+        query_streams.insert(stream_id.to_string(), credits.clone());
+        credits
+    }
+
+    /// Acknowledge a received batch, allowing the stream to send another
+    pub async fn ack_query_stream(&self, stream_id: &str) -> Result<()> {
+        let query_streams = self.query_streams.lock().await;
+        let credits = query_streams.get(stream_id).ok_or_else(|| {
+            RowFlowError::InvalidInput(format!("Query stream not found: {}", stream_id))
+        })?;
+        credits.add_permits(1);
+        Ok(())
+    }
+
+    /// Stop a streamed query; any pending wait for credits fails immediately
+    pub async fn close_query_stream(&self, stream_id: &str) {
+        let mut query_streams = self.query_streams.lock().await;
+        if let Some(credits) = query_streams.remove(stream_id) {
+            credits.close();
+        }
+    }
+}
+
+impl AppState {
+    /// Register a long-running S3 operation under an id the caller can cancel it by
+    pub async fn register_s3_operation(&self, operation_id: &str) -> Result<CancellationToken> {
+        let mut operations = self.s3_operations.lock().await;
+        if operations.contains_key(operation_id) {
+            return Err(RowFlowError::InvalidInput(format!(
+                "An S3 operation with id '{}' is already running",
+                operation_id
+            )));
+        }
+        let token = CancellationToken::new();
+        operations.insert(operation_id.to_string(), token.clone());
+        Ok(token)
+    }
+
+    /// Signal an S3 operation to stop; returns false when no such operation is running
+    pub async fn cancel_s3_operation(&self, operation_id: &str) -> bool {
+        let operations = self.s3_operations.lock().await;
+        match operations.get(operation_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget an S3 operation once it has finished
+    pub async fn finish_s3_operation(&self, operation_id: &str) {
+        self.s3_operations.lock().await.remove(operation_id);
+    }
+}
+
+impl AppState {
+    /// Open a dedicated connection for LISTEN and return its listener ID
+    ///
+    /// Pooled clients can't be used since the pool drops the notifications their connections
+    /// receive. Notifications are sent as `pg-notification` events until the listener is closed
+    /// or the server ends the connection, which also forgets the listener.
+    pub async fn open_notification_listener(&self, connection_id: &str) -> Result<String> {
+        let profile = self.get_profile(connection_id).await?;
+        let listener_id = Uuid::new_v4().to_string();
+        let config = build_pg_config(&profile);
+
+        let client = match profile.tls_config.as_ref().filter(|tls| tls.enabled) {
+            Some(tls_config) => {
+                let verification = if tls_config.verify_ca {
+                    CertVerification::Full
+                } else {
+                    CertVerification::None
+                };
+                let tls = build_tls_connector(&profile, Some(tls_config), verification)?;
+                let (client, connection) = config.connect(tls).await?;
+                tokio::spawn(self.forward_notifications(connection, listener_id.clone()));
+                client
+            }
+            None => {
+                let (client, connection) = config.connect(NoTls).await?;
+                tokio::spawn(self.forward_notifications(connection, listener_id.clone()));
+                client
+            }
+        };
+
+        let mut listeners = self.notification_listeners.lock().await;
+        listeners.insert(
+            listener_id.clone(),
+            NotificationListener {
+                connection_id: connection_id.to_string(),
+                client: Arc::new(client),
+                channels: BTreeSet::new(),
+            },
+        );
+
+        Ok(listener_id)
+    }
+
+    /// Emit every notification a listener's connection receives until the connection closes
+    fn forward_notifications<S, T>(
+        &self,
+        mut connection: tokio_postgres::Connection<S, T>,
+        listener_id: String,
+    ) -> impl std::future::Future<Output = ()> + Send + 'static
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+        T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let app_handle = self.app_handle.clone();
+        let listeners = self.notification_listeners.clone();
+
+        async move {
+            let mut messages = futures_util::stream::poll_fn(|cx| connection.poll_message(cx));
+            while let Some(message) = messages.next().await {
+                match message {
+                    Ok(AsyncMessage::Notification(notification)) => {
+                        let event = PgNotification {
+                            listener_id: listener_id.clone(),
+                            channel: notification.channel().to_string(),
+                            payload: notification.payload().to_string(),
+                            process_id: notification.process_id(),
+                        };
+                        if let Some(ref app_handle) = app_handle {
+                            let _ = app_handle.emit("pg-notification", &event);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        log::warn!("Notification listener {} closed: {}", listener_id, err);
+                        break;
+                    }
+                }
+            }
+            listeners.lock().await.remove(&listener_id);
+        }
+    }
+
+    /// Subscribe a listener to a channel, returning every channel it is now subscribed to
+    ///
+    /// Subscribing to a channel twice is a no-op.
+    pub async fn listen(&self, listener_id: &str, channel: &str) -> Result<Vec<String>> {
+        let client = self.notification_listener_client(listener_id).await?;
+        client.batch_execute(&format!("LISTEN {}", quote_identifier(channel))).await?;
+
+        let mut listeners = self.notification_listeners.lock().await;
+        let listener = listeners.get_mut(listener_id).ok_or_else(|| {
+            RowFlowError::ConnectionError(format!("Listener closed: {}", listener_id))
+        })?;
+        listener.channels.insert(channel.to_string());
+        Ok(listener.channels.iter().cloned().collect())
+    }
+
+    /// Unsubscribe a listener from one channel, closing the listener once it has none left
+    ///
+    /// Returns the channels the listener is still subscribed to.
+    pub async fn unlisten(&self, listener_id: &str, channel: &str) -> Result<Vec<String>> {
+        let client = self.notification_listener_client(listener_id).await?;
+        client.batch_execute(&format!("UNLISTEN {}", quote_identifier(channel))).await?;
+
+        let mut listeners = self.notification_listeners.lock().await;
+        let Some(listener) = listeners.get_mut(listener_id) else {
+            return Ok(Vec::new());
+        };
+        listener.channels.remove(channel);
+        let remaining: Vec<String> = listener.channels.iter().cloned().collect();
+        if remaining.is_empty() {
+            listeners.remove(listener_id);
+        }
+        Ok(remaining)
+    }
+
+    /// Close a listener and its connection, dropping all of its subscriptions
+    pub async fn close_notification_listener(&self, listener_id: &str) -> Result<()> {
+        let mut listeners = self.notification_listeners.lock().await;
+        listeners.remove(listener_id).map(|_| ()).ok_or_else(|| {
+            RowFlowError::InvalidInput(format!("Listener not found: {}", listener_id))
+        })
+    }
+
+    async fn notification_listener_client(
+        &self,
+        listener_id: &str,
+    ) -> Result<Arc<tokio_postgres::Client>> {
+        let listeners = self.notification_listeners.lock().await;
+        listeners.get(listener_id).map(|listener| listener.client.clone()).ok_or_else(|| {
+            RowFlowError::InvalidInput(format!("Listener not found: {}", listener_id))
+        })
+    }
+}
+
 impl AppState {
     /// Create a new S3 connection
     pub async fn create_s3_connection(
@@ -248,9 +1079,97 @@ impl AppState {
         Ok(())
     }
 
+    /// Remove every S3 connection and cancel running S3 operations, returning how many
+    /// connections were removed
+    pub async fn remove_all_s3_connections(&self) -> usize {
+        let connection_ids = self.list_s3_connections().await;
+        let mut removed = 0;
+        for connection_id in &connection_ids {
+            if self.remove_s3_connection(connection_id).await.is_ok() {
+                removed += 1;
+            }
+        }
+        // Each operation removes its own token when it winds down
+        for token in self.s3_operations.lock().await.values() {
+            token.cancel();
+        }
+        removed
+    }
+
     /// List all active S3 connection IDs
     pub async fn list_s3_connections(&self) -> Vec<String> {
         let connections = self.s3_connections.lock().await;
         connections.keys().cloned().collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AppState, StatementLru};
+    use std::time::Instant;
+
+    #[test]
+    fn statement_lru_evicts_least_recently_used() {
+        let mut lru = StatementLru::new(2);
+        assert_eq!(lru.touch("SELECT 1"), None);
+        assert_eq!(lru.touch("SELECT 2"), None);
+        assert_eq!(lru.touch("SELECT 1"), None);
+        assert_eq!(lru.touch("SELECT 3"), Some("SELECT 2".to_string()));
+        assert_eq!(lru.touch("SELECT 4"), Some("SELECT 1".to_string()));
+    }
+
+    /// Times a loop of identical parameterized queries with and without the statement cache
+    ///
+    /// Needs a server, e.g. `ROWFLOW_TEST_DATABASE_URL=postgres://postgres@127.0.0.1/postgres
+    /// cargo test --lib statement_cache -- --ignored`.
+    #[tokio::test]
+    #[ignore]
+    async fn statement_cache_skips_the_prepare_round_trip() {
+        let url = std::env::var("ROWFLOW_TEST_DATABASE_URL")
+            .expect("ROWFLOW_TEST_DATABASE_URL is not set");
+        let config: tokio_postgres::Config = url.parse().expect("invalid database URL");
+        let host = match &config.get_hosts()[0] {
+            tokio_postgres::config::Host::Tcp(host) => host.clone(),
+            #[cfg(unix)]
+            tokio_postgres::config::Host::Unix(path) => path.display().to_string(),
+        };
+        let profile = serde_json::from_value(serde_json::json!({
+            "name": "statement-cache",
+            "host": host,
+            "port": config.get_ports().first().copied().unwrap_or(5432),
+            "database": config.get_dbname().unwrap_or("postgres"),
+            "username": config.get_user().unwrap_or("postgres"),
+            "password": config.get_password().map(String::from_utf8_lossy),
+            "useSsh": false,
+            "readOnly": false,
+        }))
+        .unwrap();
+
+        let state = AppState::new();
+        let connection_id = state.create_connection(profile).await.unwrap();
+        let client = state.get_client(&connection_id).await.unwrap();
+        let sql = "SELECT n, md5(n::text) FROM generate_series(1, $1::int) AS n";
+        let iterations = 2_000;
+
+        let start = Instant::now();
+        for i in 0..iterations {
+            client.query(sql, &[&(i % 10)]).await.unwrap();
+        }
+        let unprepared = start.elapsed();
+
+        state.prepare_cached(&connection_id, &client, sql).await.unwrap();
+        let start = Instant::now();
+        for i in 0..iterations {
+            let statement = state.prepare_cached(&connection_id, &client, sql).await.unwrap();
+            client.query(&statement, &[&(i % 10)]).await.unwrap();
+        }
+        let cached = start.elapsed();
+        assert_eq!(client.statement_cache.size(), 1);
+        assert!(
+            cached < unprepared,
+            "{:?} through the statement cache against {:?} preparing each time",
+            cached,
+            unprepared
+        );
+    }
+}
@@ -23,6 +23,65 @@ pub struct ConnectionProfile {
     pub lock_timeout: Option<u64>,       // milliseconds
     pub idle_timeout: Option<u64>,       // seconds
     pub read_only: bool,
+    pub search_path: Option<Vec<String>>,
+    pub session_timezone: Option<String>, // IANA name such as America/New_York; defaults to UTC
+    #[serde(default)]
+    pub recycling_method: PoolRecyclingMethod,
+    #[serde(default)]
+    pub channel_binding: ChannelBindingMode,
+    #[serde(default)]
+    pub ssl_negotiation: SslNegotiationMode,
+    #[serde(default)]
+    pub slow_query_threshold: Option<u64>, // milliseconds; slower queries log at warn, default 500
+}
+
+/// Whether SCRAM authentication binds to the TLS channel, as libpq's `channel_binding`
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChannelBindingMode {
+    Disable,
+    /// Use channel binding when the server offers it
+    #[default]
+    Prefer,
+    /// Fail to connect unless the server authenticates with channel binding; requires TLS
+    Require,
+}
+
+/// How TLS is started, as libpq's `sslnegotiation`
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SslNegotiationMode {
+    /// Ask the server to switch to TLS first, which every server version supports
+    #[default]
+    Postgres,
+    /// Start TLS immediately without the extra round trip; needs PostgreSQL 17 or later
+    Direct,
+}
+
+/// How pooled connections are checked before being handed out again
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PoolRecyclingMethod {
+    /// Only check that the client isn't closed; a dead server connection fails the next query
+    #[default]
+    Fast,
+    /// Run an empty query first, replacing connections that no longer answer
+    Verified,
+    /// Also reset session state left behind by the previous user of the connection
+    Clean,
+}
+
+/// Overrides applied to an existing connection's profile when cloning it
+#[typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionOverrides {
+    pub database: Option<String>,
+    pub read_only: Option<bool>,
+    pub search_path: Option<Vec<String>>,
 }
 
 /// SSH tunnel configuration
@@ -60,6 +119,28 @@ pub struct QueryResult {
     pub row_count: usize,
     pub execution_time: f64, // milliseconds
     pub has_more: bool,
+    pub backend_pid: Option<i32>, // Backend that ran the query, for cancel_query
+}
+
+/// Text format produced by `format_query_result`
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResultFormat {
+    Markdown,
+    Tsv,
+    Json,
+}
+
+/// Request payload for aggregating a query's results into chart-ready groups
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateQueryRequest {
+    pub sql: String,
+    pub group_by: String,
+    pub value_column: Option<String>, // may be omitted for count
+    pub aggregate: String,            // count, sum, avg, min, max
 }
 
 /// Information about a query result field
@@ -73,6 +154,280 @@ pub struct FieldInfo {
     pub nullable: bool,
 }
 
+/// Options for exporting a schema's DDL to a file
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDdlExportRequest {
+    pub schema: String,
+    pub path: String,
+    #[serde(default)]
+    pub include_views: bool,
+    #[serde(default)]
+    pub include_functions: bool,
+}
+
+/// File written by a schema export
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaExportResult {
+    pub path: String,
+    pub statement_count: usize,
+}
+
+/// Machine-readable structure of a schema
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaStructure {
+    pub schema: String,
+    pub exported_at: String, // RFC 3339
+    pub tables: Vec<TableStructure>,
+}
+
+/// A table with everything introspected about it
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableStructure {
+    pub table: Table,
+    pub columns: Vec<Column>,
+    pub primary_key: Vec<String>,
+    pub indexes: Vec<Index>,
+    pub constraints: Vec<Constraint>,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+/// Table to copy from one connection to another
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableTransferRequest {
+    pub source_connection_id: String,
+    pub destination_connection_id: String,
+    pub schema: String,
+    pub table: String,
+    #[serde(default)]
+    pub create_table: bool,
+}
+
+/// Progress of a COPY, sent as the `copy-progress` event
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    pub operation: String, // command running the COPY, e.g. transfer_table
+    pub target: String,    // table or file being copied
+    pub bytes: u64,
+    pub rows: u64,
+    pub total_bytes: Option<u64>,
+    pub total_rows: Option<u64>, // often a planner estimate
+    pub bytes_per_second: f64,
+    pub eta_seconds: Option<f64>, // None without a known total
+    pub done: bool,
+}
+
+/// Outcome of a table transfer
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableTransferResult {
+    pub rows_transferred: u64,
+    pub table_created: bool,
+    pub execution_time: f64, // milliseconds
+}
+
+/// Request to export a query's rows to an S3 object
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryS3ExportRequest {
+    pub connection_id: String,
+    pub sql: String,
+    pub s3_connection_id: String,
+    pub key: String, // Relative to the S3 connection's path prefix
+    pub format: ExportFileFormat,
+    pub header: Option<bool>,          // CSV header row; defaults to true
+    pub if_match: Option<String>,      // Only overwrite when the current ETag matches
+    pub if_none_match: Option<String>, // "*" to only create when the key does not exist
+    pub server_side_encryption: Option<String>, // "AES256" or "aws:kms"
+    pub sse_kms_key_id: Option<String>,
+    pub compress: Option<Compression>, // Compress rows as they stream, setting Content-Encoding
+}
+
+/// File format written by `export_query_to_s3`
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFileFormat {
+    Csv,
+    Ndjson, // One JSON object per row
+}
+
+/// Outcome of exporting a query to S3
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryS3ExportResult {
+    pub key: String, // Full key, including the path prefix
+    pub etag: Option<String>,
+    pub rows_exported: u64,
+    pub bytes: u64,
+    pub part_count: usize,
+    pub execution_time: f64, // milliseconds
+}
+
+/// Request to load an S3 object holding CSV into a table
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3TableImportRequest {
+    pub connection_id: String,
+    pub s3_connection_id: String,
+    pub key: String, // Relative to the S3 connection's path prefix
+    pub schema: String,
+    pub table: String,
+    pub columns: Option<Vec<String>>, // Table columns in CSV field order; all columns when unset
+    pub header: Option<bool>,         // Skip a header row; defaults to true
+    pub delimiter: Option<String>,    // Single character; defaults to a comma
+}
+
+/// Outcome of loading an S3 object into a table
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3TableImportResult {
+    pub rows_imported: u64,
+    pub bytes: u64,          // Decompressed size of the loaded data
+    pub execution_time: f64, // milliseconds
+}
+
+/// Fingerprint of a table's rows
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableChecksum {
+    pub schema: String,
+    pub table: String,
+    pub row_count: u64,
+    pub checksum: String,    // hex-encoded BLAKE3 of the ordered row hashes
+    pub execution_time: f64, // milliseconds
+}
+
+/// Summary of an imported SQL script
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlImportResult {
+    pub statements_executed: usize,
+    pub copy_blocks: usize,
+    pub rows_copied: u64,
+    pub skipped_meta_commands: Vec<String>,
+    pub execution_time: f64, // milliseconds
+}
+
+/// Event sent over the channel of a streamed query
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+pub enum QueryStreamEvent {
+    #[serde(rename_all = "camelCase")]
+    Started { stream_id: String, fields: Vec<FieldInfo> },
+    #[serde(rename_all = "camelCase")]
+    Rows { batch_index: usize, rows: Vec<serde_json::Value> },
+    #[serde(rename_all = "camelCase")]
+    Finished { row_count: usize, execution_time: f64 },
+}
+
+/// Lifecycle state of a database connection
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionStatus {
+    Connecting,
+    Connected,
+    Reconnecting,
+    Disconnected,
+    Error,
+}
+
+/// A connection's latest status, also sent as the `connection-status` event
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionStatusUpdate {
+    pub connection_id: String,
+    pub status: ConnectionStatus,
+    pub error: Option<String>, // set with the error status
+    pub updated_at: String,    // RFC 3339
+}
+
+/// A NOTIFY message received by a listener, sent as the `pg-notification` event
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PgNotification {
+    pub listener_id: String,
+    pub channel: String,
+    pub payload: String,
+    pub process_id: i32, // backend that sent the notification
+}
+
+/// A listener and the channels it is subscribed to
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSubscription {
+    pub listener_id: String,
+    pub channels: Vec<String>, // empty once the listener has been closed
+}
+
+/// Outcome of connecting under one sslmode, from `diagnose_tls`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsModeDiagnosis {
+    pub ssl_mode: String, // disable, prefer, require, verify-ca or verify-full
+    pub ok: bool,
+    pub encrypted: Option<bool>, // whether the connection used TLS; None when it failed
+    pub error: Option<String>,
+}
+
+/// Stage of connecting that failed, from `diagnose_connection`
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionFailureCategory {
+    /// The host name did not resolve
+    Dns,
+    /// The host answered but nothing listens on the port
+    ConnectionRefused,
+    Timeout,
+    Tls,
+    /// Wrong password (28P01), or a role or pg_hba.conf rejection (28000)
+    Authentication,
+    /// 3D000
+    DatabaseNotFound,
+    /// 53300
+    TooManyConnections,
+    /// Any other error the server reported
+    Server,
+    Unknown,
+}
+
+/// Outcome of a connection attempt, with the cause and a suggested fix when it failed
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionDiagnosis {
+    pub ok: bool,
+    pub category: Option<ConnectionFailureCategory>, // None when the connection worked
+    pub sql_state: Option<String>,                   // when the server reported the error
+    pub error: Option<String>,
+    pub hint: Option<String>,
+}
+
 /// Database connection information
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +444,120 @@ pub struct ConnectionInfo {
     pub current_schema: String,
 }
 
+/// A server configuration setting from `pg_settings`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSetting {
+    pub name: String,
+    pub value: String,
+    pub unit: Option<String>,
+    pub category: String,
+    pub description: Option<String>,
+}
+
+/// An installed extension, from `pg_extension`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Extension {
+    pub name: String,
+    pub version: String,
+    pub schema: String,
+    pub description: Option<String>,
+}
+
+/// Execution statistics of a normalized statement, from `pg_stat_statements`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementStats {
+    pub query_id: Option<i64>,
+    pub query: String,
+    pub calls: i64,
+    pub rows: i64,
+    pub total_time: f64, // milliseconds
+    pub mean_time: f64,  // milliseconds
+    pub max_time: f64,   // milliseconds
+}
+
+/// What `reset_all` tore down
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetSummary {
+    pub database_connections: usize,
+    pub s3_connections: usize,
+    pub ollama_stopped: bool, // false when no supervised instance was running
+    pub schema_cache_cleared: bool,
+}
+
+/// Latency and error rate of recent `execute_query` calls on a connection
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryMetrics {
+    pub connection_id: String,
+    pub count: usize,
+    pub error_count: usize,
+    pub error_rate: f64,  // 0.0 to 1.0
+    pub p50: Option<f64>, // milliseconds, None without successful queries
+    pub p95: Option<f64>, // milliseconds
+    pub p99: Option<f64>, // milliseconds
+}
+
+/// A standby connected to this server, from `pg_stat_replication`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaStatus {
+    pub pid: i32,
+    pub application_name: String,
+    pub client_address: Option<String>,
+    pub state: Option<String>,
+    pub sync_state: Option<String>,
+    pub sent_lsn: Option<String>,
+    pub write_lsn: Option<String>,
+    pub flush_lsn: Option<String>,
+    pub replay_lsn: Option<String>,
+    pub replay_lag_bytes: Option<i64>,
+    pub write_lag_seconds: Option<f64>,
+    pub flush_lag_seconds: Option<f64>,
+    pub replay_lag_seconds: Option<f64>,
+}
+
+/// Replication overview for a primary or standby server
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicationStatus {
+    pub is_in_recovery: bool,
+    pub replicas: Vec<ReplicaStatus>,
+    pub upstream_host: Option<String>,
+    pub receive_lsn: Option<String>,
+    pub replay_lsn: Option<String>,
+    pub replay_lag_seconds: Option<f64>,
+    pub note: Option<String>,
+}
+
+/// A session waiting on a lock together with the session holding it
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockingLock {
+    pub blocked_pid: i32,
+    pub blocked_user: Option<String>,
+    pub blocked_query: Option<String>,
+    pub blocked_wait_seconds: Option<f64>,
+    pub blocking_pid: i32,
+    pub blocking_user: Option<String>,
+    pub blocking_query: Option<String>,
+    pub blocking_state: Option<String>,
+    pub lock_type: String,
+    pub lock_mode: String,
+    pub relation: Option<String>,
+}
+
 /// Database schema information
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +592,10 @@ pub struct Column {
     pub data_type: String,
     pub is_nullable: bool,
     pub column_default: Option<String>,
+    pub is_identity: bool,
+    pub identity_generation: Option<String>, // ALWAYS or BY DEFAULT for identity columns
+    pub is_generated: bool,                  // GENERATED ALWAYS AS (expr) STORED
+    pub generation_expression: Option<String>,
     pub character_maximum_length: Option<i32>,
     pub numeric_precision: Option<i32>,
     pub numeric_scale: Option<i32>,
@@ -135,6 +608,38 @@ pub struct Column {
     pub description: Option<String>,
 }
 
+/// A column a table is expected to have, for `assert_table_schema`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedColumn {
+    pub name: String,
+    pub data_type: String, // any common spelling, e.g. int4, varchar(255), text[]
+    pub is_nullable: Option<bool>, // not checked when omitted
+}
+
+/// A column whose type or nullability differs from what was expected
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnMismatch {
+    pub column: String,
+    pub field: String, // dataType or isNullable
+    pub expected: String,
+    pub actual: String,
+}
+
+/// How a table's columns compare to an expected schema
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableSchemaAssertion {
+    pub matches: bool,
+    pub missing_columns: Vec<String>,
+    pub extra_columns: Vec<String>,
+    pub mismatches: Vec<ColumnMismatch>,
+}
+
 /// Index information
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -176,6 +681,29 @@ pub struct TableStats {
     pub last_autoanalyze: Option<String>,
 }
 
+/// Estimated bloat of a table or index
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BloatEstimate {
+    pub name: String,
+    pub real_size: i64,  // bytes
+    pub bloat_size: i64, // bytes beyond what the live tuples need at the fillfactor
+    pub bloat_percent: f64,
+    pub reliable: bool,
+}
+
+/// Bloat estimates for a table and its btree indexes
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableBloat {
+    pub schema: String,
+    pub table: String,
+    pub table_bloat: Option<BloatEstimate>,
+    pub indexes: Vec<BloatEstimate>,
+}
+
 /// Query execution plan
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -243,11 +771,12 @@ pub struct OllamaInstallInfo {
 #[serde(rename_all = "camelCase")]
 pub struct EmbeddingJobRequest {
     pub connection_id: String,
-    pub schema: String,
+    pub schema: Option<String>,
     pub table: String,
     pub columns: Vec<String>,
     pub model: String,
     pub limit: Option<i64>,
+    pub job_id: Option<String>, // lets the caller cancel the job while it runs
 }
 
 /// Result summary from an embedding job
@@ -255,6 +784,7 @@ pub struct EmbeddingJobRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EmbeddingJobResult {
+    pub job_id: String,
     pub embedded_rows: usize,
     pub skipped_rows: usize,
 }
@@ -264,11 +794,16 @@ pub struct EmbeddingJobResult {
 #[serde(rename_all = "camelCase")]
 pub struct GenerateTestDataRequest {
     pub connection_id: String,
-    pub schema: String,
+    pub schema: Option<String>,
     pub table: String,
     pub row_count: usize,
     pub instructions: Option<String>,
     pub user_template: Option<serde_json::Value>,
+    pub job_id: Option<String>, // lets the caller cancel the job while it runs
+    pub attempts_per_row: Option<usize>, // model calls allowed per requested row, default 3
+    pub min_attempts: Option<usize>, // floor on the attempt budget, default 3
+    // e.g. fr-FR; how well the data is localized depends on the model's multilingual ability
+    pub locale: Option<String>,
 }
 
 #[typeshare]
@@ -276,65 +811,291 @@ pub struct GenerateTestDataRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GeneratedTestRow {
     pub values: serde_json::Value,
+    #[serde(default)]
+    pub synthetic: bool, // filled in without the model after it ran out of attempts
 }
 
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GenerateTestDataResponse {
+    pub job_id: String,
     pub rows: Vec<GeneratedTestRow>,
     pub model: String,
+    pub requested_rows: usize,
+    pub attempts: usize,
+    pub max_attempts: usize,
+    pub failed_attempts: Vec<FailedTestRowAttempt>,
+    pub template_warnings: Vec<String>, // template keys that don't match a generated column
+    pub inferred_semantics: Vec<InferredColumnSemantic>,
 }
 
-/// Request to perform semantic search against stored embeddings
+/// The prompt and JSON schema `generate_test_data` would send to the model for one row
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct EmbeddingSearchRequest {
-    pub connection_id: String,
-    pub schema: Option<String>,
-    pub table: Option<String>,
-    pub query: String,
-    pub model: String,
-    pub top_k: usize,
+pub struct TestDataPromptPreview {
+    pub prompt: String,
+    pub row_schema: serde_json::Value,
+    pub template_warnings: Vec<String>,
+    pub inferred_semantics: Vec<InferredColumnSemantic>,
 }
 
-/// A semantic search match result
+/// Kind of value a text column holds, inferred from its existing data
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct EmbeddingSearchMatch {
-    pub row_reference: String,
-    pub schema: String,
-    pub table: String,
-    pub score: f32,
-    pub content: String,
-    pub metadata: serde_json::Value,
+pub enum ColumnSemantic {
+    Email,
+    Phone,
+    Url,
+    Uuid,
+    Json,
 }
 
-/// Metadata about embeddings for a table
+/// What most of a column's sampled values look like
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct EmbeddingTableMetadata {
-    pub connection_id: String,
-    pub schema_name: String,
-    pub table_name: String,
-    pub row_count: i64,
-    pub last_updated: i64,
+pub struct InferredColumnSemantic {
+    pub column: String,
+    pub semantic: ColumnSemantic,
+    pub matched: usize, // sampled values matching the pattern
+    pub sampled: usize,
 }
 
-/// Constraint information
+/// Generated test rows together with how many of them were inserted
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Constraint {
-    pub name: String,
+pub struct GenerateAndInsertTestDataResponse {
+    pub generation: GenerateTestDataResponse,
+    pub inserted_rows: u64,
+}
+
+/// A generation attempt that did not produce a usable row
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedTestRowAttempt {
+    pub attempt: usize,
+    pub row_index: usize, // 1-based index of the row being generated
+    pub reason: String,
+}
+
+/// One message of a chat conversation with the model
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentChatMessage {
+    pub role: String, // "system", "user" or "assistant"
+    pub content: String,
+}
+
+/// A long-running AI operation that can be cancelled
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiJobInfo {
+    pub job_id: String,
+    pub kind: String, // "embed_table" or "generate_test_data"
+    pub description: String,
+    pub completed: usize,
+    pub total: usize,
+    pub started_at: String, // RFC 3339
+}
+
+/// Request to perform semantic search against stored embeddings
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSearchRequest {
+    pub connection_id: String,
+    pub schema: Option<String>,
+    pub table: Option<String>,
+    pub tables: Option<Vec<EmbeddingTableRef>>, // search only these tables
+    pub query: String,
+    pub model: String,
+    pub top_k: usize,
+}
+
+/// A table whose embeddings a search should include
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingTableRef {
+    pub schema: String,
+    pub table: String,
+}
+
+/// Request to find rows similar to a row identified by its primary key
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarRowsRequest {
+    pub connection_id: String,
+    pub schema: Option<String>,
+    pub table: String,
+    pub key: TableRowData,
+    pub model: String,
+    pub top_k: usize,
+}
+
+/// A semantic search match result
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSearchMatch {
+    pub row_reference: String,
+    pub schema: String,
+    pub table: String,
+    pub score: f32,
+    pub content: String,
+    pub metadata: serde_json::Value,
+}
+
+/// Metadata about embeddings for a table
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingTableMetadata {
+    pub connection_id: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub row_count: i64,
+    pub last_updated: i64,
+}
+
+/// Outcome of importing an embeddings export
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingImportResult {
+    pub imported_rows: usize,
+    pub skipped_rows: usize, // already stored with the same chunk hash
+}
+
+/// Constraint information
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Constraint {
+    pub name: String,
     pub constraint_type: String, // PRIMARY KEY, FOREIGN KEY, UNIQUE, CHECK
     pub columns: Vec<String>,
     pub definition: Option<String>,
 }
 
+/// Kind of schema object `get_object_definition` looks up
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ObjectKind {
+    Constraint,
+    Index,
+    /// A view or materialized view
+    View,
+    Sequence,
+}
+
+/// Trigger information
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trigger {
+    pub name: String,
+    pub timing: String,      // BEFORE, AFTER, INSTEAD OF
+    pub events: Vec<String>, // INSERT, UPDATE, DELETE, TRUNCATE
+    pub level: String,       // ROW, STATEMENT
+    pub function_schema: String,
+    pub function_name: String,
+    pub enabled: bool,
+    pub definition: String,
+}
+
+/// Stored function or procedure information
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseFunction {
+    pub oid: u32,
+    pub schema: String,
+    pub name: String,
+    pub arguments: String,
+    pub argument_types: Vec<String>,
+    pub return_type: Option<String>,
+    pub language: String,
+    pub volatility: String, // IMMUTABLE, STABLE, VOLATILE
+    pub kind: String,       // FUNCTION, PROCEDURE, AGGREGATE, WINDOW
+    pub description: Option<String>,
+}
+
+/// A composite type created with `CREATE TYPE ... AS (...)`
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositeType {
+    pub oid: u32,
+    pub schema: String,
+    pub name: String,
+    pub attributes: Vec<CompositeTypeAttribute>, // in declaration order
+    pub description: Option<String>,
+}
+
+/// One attribute of a composite type
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositeTypeAttribute {
+    pub name: String,
+    pub data_type: String, // with modifiers, e.g. character varying(64)
+}
+
+/// A domain: a base type narrowed by NOT NULL, a default and CHECK constraints
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Domain {
+    pub oid: u32,
+    pub schema: String,
+    pub name: String,
+    pub base_type: String, // with modifiers, e.g. numeric(10,2)
+    pub not_null: bool,
+    pub default: Option<String>,
+    pub constraints: Vec<DomainConstraint>,
+    pub description: Option<String>,
+}
+
+/// A CHECK constraint on a domain
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainConstraint {
+    pub name: String,
+    pub definition: String, // e.g. CHECK (VALUE > 0)
+}
+
+/// Request payload for calling a stored function or procedure
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFunctionRequest {
+    pub schema: String,
+    pub name: String,
+    pub arguments: Vec<serde_json::Value>,
+}
+
+/// An object that depends on a table (view, trigger, function, sequence, foreign key)
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependency {
+    pub kind: String, // VIEW, MATERIALIZED VIEW, TRIGGER, FUNCTION, SEQUENCE, FOREIGN KEY
+    pub schema: String,
+    pub name: String,
+    pub detail: Option<String>,
+}
+
 /// Definition for creating or altering table columns
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -369,6 +1130,32 @@ pub struct CreateTableRequest {
     pub table_name: String,
     pub columns: Vec<TableColumnDefinition>,
     pub if_not_exists: bool,
+    #[serde(default)]
+    pub table_constraints: Vec<TableConstraintDefinition>,
+}
+
+/// Table-level constraint spanning one or more columns
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableConstraintDefinition {
+    pub name: Option<String>,
+    pub constraint_type: String, // UNIQUE, CHECK or FOREIGN KEY
+    pub columns: Vec<String>,
+    pub check_expression: Option<String>,
+    pub references: Option<TableConstraintReference>,
+}
+
+/// Target of a multi-column foreign key constraint
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableConstraintReference {
+    pub schema: Option<String>,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub on_delete: Option<String>,
+    pub on_update: Option<String>,
 }
 
 /// Request payload for dropping a table
@@ -405,6 +1192,86 @@ pub struct DropTableColumnRequest {
     pub if_exists: bool,
 }
 
+/// Request payload for applying several column changes to a table at once
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlterTableRequest {
+    pub schema: String,
+    pub table_name: String,
+    pub operations: Vec<AlterTableOperation>, // applied in order
+    #[serde(default)]
+    pub dry_run: bool,   // only return the generated SQL
+}
+
+/// One change in an `alter_table` request
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", content = "change", rename_all = "camelCase")]
+pub enum AlterTableOperation {
+    AddColumn(TableColumnDefinition),
+    DropColumn(ColumnDrop),
+    RenameColumn(ColumnRename),
+    AlterColumnType(ColumnTypeChange),
+    SetDefault(ColumnDefault),
+    DropDefault(String), // column name
+    SetNotNull(String),
+    DropNotNull(String),
+}
+
+/// Column to drop in an `alter_table` request
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnDrop {
+    pub column_name: String,
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// Column to rename in an `alter_table` request
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnRename {
+    pub column_name: String,
+    pub new_name: String,
+}
+
+/// New type for a column in an `alter_table` request
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnTypeChange {
+    pub column_name: String,
+    pub data_type: String,
+    pub using: Option<String>, // expression converting existing values, e.g. amount::numeric
+}
+
+/// New default for a column in an `alter_table` request
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnDefault {
+    pub column_name: String,
+    pub default_expression: String,
+}
+
+/// A DDL operation whose generated SQL can be previewed before running it
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "operation", content = "request", rename_all = "camelCase")]
+pub enum DdlOperation {
+    CreateSchema(CreateSchemaRequest),
+    DropSchema(DropSchemaRequest),
+    RenameSchema(RenameSchemaRequest),
+    CreateTable(CreateTableRequest),
+    DropTable(DropTableRequest),
+    AddTableColumn(AddTableColumnRequest),
+    DropTableColumn(DropTableColumnRequest),
+    AlterTable(AlterTableRequest),
+}
+
 /// Row payload used for inserts and deletes
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -446,7 +1313,7 @@ pub struct RenameSchemaRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InsertRowRequest {
-    pub schema: String,
+    pub schema: Option<String>,
     pub table_name: String,
     pub row: TableRowData,
 }
@@ -456,12 +1323,34 @@ pub struct InsertRowRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteRowRequest {
-    pub schema: String,
+    pub schema: Option<String>,
     pub table_name: String,
     pub criteria: TableRowData,
     pub limit: Option<u32>,
 }
 
+/// Request payload for deleting the rows matching criteria in batches
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteRequest {
+    pub schema: Option<String>,
+    pub table_name: String,
+    pub criteria: TableRowData,
+    pub batch_size: Option<u32>, // Rows per batch; defaults to 10000
+    pub pause_ms: Option<u64>,   // Pause between batches; defaults to 100
+}
+
+/// Outcome of a batched delete
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteResult {
+    pub deleted: u64,
+    pub batches: u64,
+    pub execution_time: f64, // milliseconds
+}
+
 /// Request payload for searching foreign key candidates
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -483,6 +1372,196 @@ pub struct ForeignKeySearchResult {
     pub row: serde_json::Value,
 }
 
+/// Request payload for fetching a single row by its key columns
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowByKeyRequest {
+    pub schema: Option<String>,
+    pub table: String,
+    pub key: TableRowData,
+}
+
+/// Request payload for reading a single `bytea` cell
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CellBytesRequest {
+    pub schema: String,
+    pub table: String,
+    pub key: TableRowData,
+    pub column: String,
+    pub path: Option<String>,
+}
+
+/// Contents of a `bytea` cell, or where they were written
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CellBytes {
+    pub size: usize,
+    pub data: Option<Vec<u8>>,
+    pub path: Option<String>,
+}
+
+/// Request payload for resolving the parent row a foreign key column points at
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencedRowRequest {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub row: TableRowData,
+}
+
+/// Parent row resolved through a foreign key, along with the key that was followed
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencedRow {
+    pub foreign_key: ForeignKey,
+    pub row: Option<serde_json::Value>,
+}
+
+/// A foreign key on another table that references the inspected table
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundForeignKey {
+    pub name: String,
+    pub schema: String,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub referenced_columns: Vec<String>,
+    pub on_delete: String,
+    pub on_update: String,
+}
+
+/// Request payload for finding rows that reference a given row
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencingRowsRequest {
+    pub schema: String,
+    pub table: String,
+    pub key: TableRowData,
+    pub sample_limit: Option<i64>,
+    pub include_counts: bool,
+}
+
+/// Rows in one child table that reference the inspected row
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencingTableRows {
+    pub foreign_key: InboundForeignKey,
+    pub row_count: Option<i64>,
+    pub sample_rows: Vec<serde_json::Value>,
+}
+
+/// Request payload for finding rows whose foreign key references a missing parent row
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedRowsRequest {
+    pub schema: Option<String>,
+    pub table: String,
+    pub constraint_name: String,
+    pub sample_limit: Option<i64>,
+}
+
+/// Rows of a child table whose foreign key references no existing parent row
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedRows {
+    pub foreign_key: ForeignKey,
+    pub orphan_count: i64,
+    pub sample_rows: Vec<serde_json::Value>,
+}
+
+/// Request payload for previewing the effect of deleting a row
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteCascadePreviewRequest {
+    pub schema: String,
+    pub table: String,
+    pub key: TableRowData,
+}
+
+/// A table touched by a delete, reached through the foreign key it was found by
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CascadeDeleteNode {
+    pub schema: String,
+    pub table: String,
+    pub constraint_name: Option<String>,
+    pub on_delete: Option<String>,
+    pub row_count: i64,
+    pub children: Vec<CascadeDeleteNode>,
+    /// More tables cascade from these rows, past the depth the preview walks
+    pub truncated: bool,
+}
+
+/// Summary of everything a row delete would affect
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteCascadePreview {
+    pub root: CascadeDeleteNode,
+    /// An upper bound: a row reached through two foreign keys is counted twice
+    pub total_deleted_rows: i64,
+    pub blocked_by_restrict: bool,
+    /// Some branch was cut off at the depth limit, so the counts are incomplete
+    pub truncated: bool,
+}
+
+/// Request payload for column value autocomplete suggestions
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnValueSuggestionRequest {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub prefix: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A distinct column value with how often it appears in the sampled rows
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnValueSuggestion {
+    pub value: String,
+    pub frequency: i64,
+}
+
+/// Request payload for the options of a column filter dropdown
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterOptionsRequest {
+    pub schema: String,
+    pub table: String,
+    pub column: String,
+    pub limit: Option<i64>,
+}
+
+/// Values a categorical column can be filtered by
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterOptions {
+    pub data_type: String,
+    pub values: Vec<String>,
+    pub truncated: bool, // more distinct values exist than were returned
+    pub enum_labels: Option<Vec<String>>,
+}
+
 /// S3 connection profile
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -498,6 +1577,8 @@ pub struct S3ConnectionProfile {
     pub session_token: Option<String>, // For temporary credentials
     pub path_prefix: Option<String>,   // Optional path prefix
     pub force_path_style: bool,        // For S3-compatible services
+    pub max_retries: Option<u32>,      // Retries of throttled or failed requests; defaults to 3
+    pub retry_base_delay_ms: Option<u64>, // First retry delay, doubling each time; defaults to 200
 }
 
 /// S3 object metadata
@@ -514,6 +1595,40 @@ pub struct S3Object {
     pub is_directory: bool,
 }
 
+/// A version of an S3 object, or a delete marker
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3ObjectVersion {
+    pub key: String,
+    pub version_id: Option<String>, // "null" for objects written while versioning was off
+    pub is_latest: bool,
+    pub is_delete_marker: bool,
+    pub size: i64,
+    pub last_modified: String,
+    pub etag: Option<String>,
+}
+
+/// Object count and total size under an S3 prefix
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3PrefixStats {
+    pub prefix: String, // Full prefix, including the connection's path prefix
+    pub object_count: u64,
+    pub total_size: i64, // bytes
+    pub by_storage_class: BTreeMap<String, S3StorageClassStats>,
+}
+
+/// Object count and total size of one storage class under an S3 prefix
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3StorageClassStats {
+    pub object_count: u64,
+    pub total_size: i64, // bytes
+}
+
 /// S3 list objects result
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -534,6 +1649,8 @@ pub struct S3ListRequest {
     pub delimiter: Option<String>,
     pub max_keys: Option<i32>,
     pub continuation_token: Option<String>,
+    /// RFC3339; only later-modified objects are listed, reading on until the page fills
+    pub modified_after: Option<String>,
 }
 
 /// Request to download an S3 object
@@ -542,6 +1659,10 @@ pub struct S3ListRequest {
 #[serde(rename_all = "camelCase")]
 pub struct S3GetObjectRequest {
     pub key: String,
+    pub range: Option<(u64, u64)>,  // Inclusive byte range
+    pub version_id: Option<String>, // Latest version when unset
+    #[serde(default)]
+    pub keep_compressed: bool, // Return a gzip or zstd encoded body as stored
 }
 
 /// Response containing S3 object data
@@ -551,9 +1672,26 @@ pub struct S3GetObjectRequest {
 pub struct S3GetObjectResponse {
     pub content: Vec<u8>,
     pub content_type: Option<String>,
+    pub content_encoding: Option<String>, // Encoding still applied to content, if any
     pub content_length: i64,
+    pub total_size: i64, // Full object size, which differs from content_length for ranged reads
     pub last_modified: Option<String>,
     pub etag: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// S3 object details returned without downloading the body
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3ObjectHead {
+    pub key: String,
+    pub content_type: Option<String>,
+    pub content_length: i64,
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    pub storage_class: Option<String>,
+    pub metadata: BTreeMap<String, String>,
 }
 
 /// Request to upload an S3 object
@@ -563,7 +1701,43 @@ pub struct S3GetObjectResponse {
 pub struct S3PutObjectRequest {
     pub key: String,
     pub content: Vec<u8>,
-    pub content_type: Option<String>,
+    pub content_type: Option<String>, // Guessed from the key's extension when unset
+    pub if_match: Option<String>,     // Only overwrite when the current ETag matches
+    pub if_none_match: Option<String>, // "*" to only create when the key does not exist
+    pub metadata: Option<BTreeMap<String, String>>, // Sent as x-amz-meta-* headers
+    pub tags: Option<BTreeMap<String, String>>,
+    pub server_side_encryption: Option<String>, // "AES256" or "aws:kms"
+    pub sse_kms_key_id: Option<String>,
+    pub storage_class: Option<String>, // e.g. STANDARD, STANDARD_IA, GLACIER
+    pub compress: Option<Compression>, // Compress content before upload, setting Content-Encoding
+}
+
+/// Compression applied to an S3 object body
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+/// Response from uploading an S3 object
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3PutObjectResponse {
+    pub etag: String,
+    pub server_side_encryption: Option<String>,
+    pub sse_kms_key_id: Option<String>,
+}
+
+/// Request to replace the tag set of an S3 object
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3ObjectTagsRequest {
+    pub key: String,
+    pub tags: BTreeMap<String, String>,
 }
 
 /// Request to delete S3 objects
@@ -593,13 +1767,32 @@ pub struct S3DeleteError {
     pub message: String,
 }
 
+/// Request to rename (move) every object under an S3 prefix
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3RenamePrefixRequest {
+    pub source_prefix: String,
+    pub destination_prefix: String,
+}
+
+/// Result of renaming an S3 prefix
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3RenamePrefixResult {
+    pub moved: Vec<String>,
+    pub errors: Vec<S3DeleteError>,
+}
+
 /// Request to generate presigned URL
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct S3PresignedUrlRequest {
     pub key: String,
-    pub expires_in: u64, // seconds
+    pub expires_in: u64,            // seconds
+    pub version_id: Option<String>, // Latest version when unset
 }
 
 /// Response containing presigned URL
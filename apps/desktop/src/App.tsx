@@ -323,10 +323,12 @@ function AppContent() {
                         });
                       }
                     }}
-                    onExecuteSql={(sql: string) => {
+                    onExecuteSql={(sql: string, requiresConfirmation: boolean) => {
                       if (insertSqlHandler) {
                         insertSqlHandler(sql, {
-                          execute: true,
+                          // Statements the backend says may write are only loaded, never run
+                          execute: !requiresConfirmation,
+                          readOnly: true,
                           replace: true, // Clear existing SQL and replace with new
                         });
                       }
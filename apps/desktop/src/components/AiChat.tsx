@@ -14,7 +14,7 @@ import { listen } from '@tauri-apps/api/event';
 
 interface AiChatProps {
   onSelectRow?: (match: EmbeddingSearchMatch) => void;
-  onExecuteSql?: (sql: string) => void;
+  onExecuteSql?: (sql: string, requiresConfirmation: boolean) => void;
   onOpenSettings?: () => void;
 }
 
@@ -40,6 +40,9 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
   const [downloadingChatModel, setDownloadingChatModel] = useState(false);
   const [downloadingEmbeddingModel, setDownloadingEmbeddingModel] = useState(false);
   const scrollAreaRef = useRef<HTMLDivElement>(null);
+  // Lets the agent resolve follow-ups like "now filter by date" against earlier turns
+  const sessionIdRef = useRef(crypto.randomUUID());
+  const lastMatchesRef = useRef<EmbeddingSearchMatch[]>([]);
   const inputRef = useRef<HTMLInputElement>(null);
 
   const scrollToBottom = useCallback(() => {
@@ -229,6 +232,7 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
       // Step 2: Classify user intent using LangGraph-style agent
       const agentState = await invoke<import('@/types/ai').AgentState>('classify_user_message', {
         message: userInput,
+        sessionId: sessionIdRef.current,
       });
 
       // Step 3: Handle based on intent
@@ -244,11 +248,14 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
         return;
       }
 
-      // Step 4: For database queries, perform RAG search
-      const matches = await searchEmbeddings(userInput, { 
+      // Step 4: For database queries, perform RAG search. A follow-up such as "now filter by
+      // date" rarely matches anything on its own, so it keeps the previous turn's matches.
+      const searched = await searchEmbeddings(userInput, {
         topK: 5,
         onOpenSettings,
       });
+      const matches =
+        searched.length === 0 && agentState.context ? lastMatchesRef.current : searched;
 
       if (matches.length === 0) {
         const assistantMessage: ChatMessage = {
@@ -263,8 +270,10 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
       }
 
       // Step 5: Generate SQL query from RAG context
-      const { sql, matches: contextMatches } = await generateSqlFromRag(userInput, {
+      const { sql, matches: contextMatches, requiresConfirmation } = await generateSqlFromRag(userInput, {
         onOpenSettings,
+        agentState,
+        matches,
       });
 
       // Build response with matches
@@ -282,9 +291,21 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
         timestamp: Date.now(),
         matches: contextMatches,
         sqlQuery: sql,
+        sqlRequiresConfirmation: requiresConfirmation,
       };
 
       setMessages((prev) => [...prev, assistantMessage]);
+      lastMatchesRef.current = contextMatches;
+      // The answer is already shown, so failing to remember it must not read as a failed answer
+      try {
+        await invoke('record_agent_result', {
+          sessionId: sessionIdRef.current,
+          sql,
+          resultSummary: `${matches.length} matching row(s) in ${[...new Set(contextMatches.map((match) => `${match.schema}.${match.table}`))].join(', ')}`,
+        });
+      } catch (error) {
+        console.warn('Failed to record the agent result for follow-ups:', error);
+      }
     } catch (error) {
       const errorMessage = error instanceof Error ? error.message : 'Failed to process your question';
       const errorResponse: ChatMessage = {
@@ -319,9 +340,9 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
   );
 
   const handleExecuteSql = useCallback(
-    (sql: string) => {
+    (sql: string, requiresConfirmation: boolean) => {
       if (onExecuteSql) {
-        onExecuteSql(sql);
+        onExecuteSql(sql, requiresConfirmation);
       }
     },
     [onExecuteSql]
@@ -453,7 +474,10 @@ export function AiChat({ onSelectRow, onExecuteSql, onOpenSettings }: AiChatProp
                     <Button
                       variant="outline"
                       size="sm"
-                      onClick={() => handleExecuteSql(message.sqlQuery!)}
+                      onClick={() =>
+                        // Without the backend's verdict, treat the SQL as possibly writing
+                        handleExecuteSql(message.sqlQuery!, message.sqlRequiresConfirmation ?? true)
+                      }
                       className="w-full text-xs"
                     >
                       Execute SQL Query
@@ -25,7 +25,7 @@ type RowEditor = {
   id: string;
   value: string;
   error?: string;
-  source: 'ai' | 'manual';
+  source: 'ai' | 'synthetic' | 'manual';
 };
 
 const extractErrorMessage = (error: unknown): string => {
@@ -240,7 +240,7 @@ export function GenerateTestDataDialog({
       const generatedEditors: RowEditor[] = response.rows.map((row) => ({
         id: makeEditorId(),
         value: JSON.stringify(row.values ?? {}, null, 2),
-        source: 'ai',
+        source: row.synthetic ? 'synthetic' : 'ai',
         error: undefined,
       }));
 
@@ -249,16 +249,24 @@ export function GenerateTestDataDialog({
         return [...manualRows, ...generatedEditors];
       });
       setModelUsed(response.model);
-      const missingRows = Math.max(parsedRowCount - generatedEditors.length, 0);
+      const syntheticRows = response.rows.filter((row) => row.synthetic).length;
+      const modelRows = generatedEditors.length - syntheticRows;
       const generatedLabel = `row${generatedEditors.length === 1 ? '' : 's'}`;
-      const description = missingRows > 0
-        ? `Generated ${generatedEditors.length} of ${parsedRowCount} requested ${generatedLabel} using ${response.model}. Review and insert when ready.`
+      const description = syntheticRows > 0
+        ? `Generated ${modelRows}/${response.requestedRows} ${generatedLabel} after ${response.attempts} attempts using ${response.model}; ${syntheticRows} filled in synthetically. Review and insert when ready.`
         : `Generated ${generatedEditors.length} ${generatedLabel} using ${response.model}. Review and insert when ready.`;
 
       toast({
-        title: missingRows > 0 ? 'Partial generation' : 'Rows prepared',
+        title: syntheticRows > 0 ? 'Partial generation' : 'Rows prepared',
         description,
       });
+
+      if (response.templateWarnings.length > 0) {
+        toast({
+          title: 'Template issues',
+          description: response.templateWarnings.join('\n'),
+        });
+      }
     } catch (error) {
       console.error('[GenerateTestDataDialog] Failed to generate rows:', error);
       const message = extractErrorMessage(error);
@@ -565,8 +573,8 @@ export function GenerateTestDataDialog({
                       <div className="flex items-center justify-between text-sm">
                         <div className="flex items-center gap-2">
                           <span className="font-medium">Row {index + 1}</span>
-                          <Badge variant={row.source === 'ai' ? 'default' : 'outline'}>
-                            {row.source === 'ai' ? 'AI' : 'Manual'}
+                          <Badge variant={row.source === 'ai' ? 'default' : row.source === 'synthetic' ? 'secondary' : 'outline'}>
+                            {row.source === 'ai' ? 'AI' : row.source === 'synthetic' ? 'Synthetic' : 'Manual'}
                           </Badge>
                         </div>
                         <Button
@@ -94,6 +94,7 @@ function toSqlLiteral(value: any, typeName?: string): string {
     lowerType.includes('int') ||
     lowerType.includes('numeric') ||
     lowerType.includes('decimal') ||
+    lowerType.includes('real') ||
     lowerType.includes('float') ||
     lowerType.includes('double')
   ) {
@@ -117,6 +118,7 @@ function toSqlLiteral(value: any, typeName?: string): string {
 
 export interface InsertSqlOptions {
   execute?: boolean;
+  readOnly?: boolean;
   replace?: boolean;
   tabName?: string;
   context?: QueryTabContext;
@@ -309,7 +311,7 @@ export function QueryPanel({
             updateTabSql(targetTabId, finalSql);
             
             if (options?.execute) {
-              executeQuery(finalSql).catch(console.error);
+              executeQuery(finalSql, [], options.readOnly).catch(console.error);
             }
           }, 50);
         }
@@ -356,7 +358,7 @@ export function QueryPanel({
         }
         onTableContextChange?.(undefined);
         lastContextRef.current = undefined;
-        executeQuery(finalSql);
+        executeQuery(finalSql, [], options.readOnly);
       }
     },
     [
@@ -87,6 +87,7 @@ function coerceInputValue(input: string, columnType?: string): any {
       lower.includes('int') ||
       lower.includes('numeric') ||
       lower.includes('decimal') ||
+      lower.includes('real') ||
       lower.includes('float') ||
       lower.includes('double')
     ) {
@@ -112,6 +113,7 @@ function coerceInputValue(input: string, columnType?: string): any {
     lower.includes('int') ||
     lower.includes('numeric') ||
     lower.includes('decimal') ||
+    lower.includes('real') ||
     lower.includes('float') ||
     lower.includes('double')
   ) {
@@ -29,7 +29,7 @@ export function useQueryExecution(options: UseQueryExecutionOptions) {
    * Execute a SQL query
    */
   const executeQuery = useCallback(
-    async (sql: string, params: any[] = []) => {
+    async (sql: string, params: any[] = [], readOnly = false) => {
       if (!connectionId) {
         const error = 'No active database connection';
         setState({
@@ -87,6 +87,7 @@ export function useQueryExecution(options: UseQueryExecutionOptions) {
           connectionId,
           sql,
           params,
+          readOnly,
         });
 
         const duration = performance.now() - startTime;
@@ -224,6 +225,7 @@ export function useQueryExecution(options: UseQueryExecutionOptions) {
           result: combinedResult,
           error: null,
           duration: totalDuration,
+          backendPid: result.backendPid,
         };
 
         setState(nextState);
@@ -282,14 +284,16 @@ export function useQueryExecution(options: UseQueryExecutionOptions) {
    * Cancel the currently running query
    */
   const cancelQuery = useCallback(async () => {
-    if (state.status !== 'running' || !connectionId || !backendPidRef.current) {
+    if (state.status !== 'running' || !connectionId) {
       return;
     }
 
     try {
+      // Streamed queries run on their own pooled client; without a known PID the
+      // backend falls back to the connection's active streaming backend
       await invoke('cancel_query', {
         connectionId,
-        backendPid: backendPidRef.current,
+        backendPid: backendPidRef.current ?? undefined,
       });
 
       setState((prev) => ({
@@ -4,6 +4,7 @@ import { useToast } from '@/hooks/use-toast';
 import { quoteIdentifier } from '@/lib/sqlPlaceholders';
 import { checkAndPromptForMissingModels, EMBEDDING_MODEL, CHAT_MODEL } from '@/lib/modelCheck';
 import type {
+  AgentState,
   EmbeddingSearchRequest,
   EmbeddingSearchMatch,
   EmbeddingJobRequest,
@@ -128,8 +129,12 @@ export function useRag(connectionId: string | null) {
         table?: string;
         topK?: number;
         onOpenSettings?: () => void;
+        /** The classified message the SQL answers */
+        agentState?: AgentState;
+        /** Context the caller already found, skipping the search */
+        matches?: EmbeddingSearchMatch[];
       }
-    ): Promise<{ sql: string; matches: EmbeddingSearchMatch[] }> => {
+    ): Promise<{ sql: string; matches: EmbeddingSearchMatch[]; requiresConfirmation: boolean }> => {
       // Check for chat model before generating SQL
       const chatModelAvailable = await checkAndPromptForMissingModels(
         { type: 'chat', model: DEFAULT_CHAT_MODEL },
@@ -141,13 +146,15 @@ export function useRag(connectionId: string | null) {
         throw new Error('Chat model not available');
       }
 
-      // First, search for relevant context
-      const matches = await searchEmbeddings(question, {
-        schema: options?.schema,
-        table: options?.table,
-        topK: options?.topK || 5,
-        onOpenSettings: options?.onOpenSettings,
-      });
+      // First, search for relevant context unless the caller already has it
+      const matches =
+        options?.matches ??
+        (await searchEmbeddings(question, {
+          schema: options?.schema,
+          table: options?.table,
+          topK: options?.topK || 5,
+          onOpenSettings: options?.onOpenSettings,
+        }));
 
       if (matches.length === 0) {
         throw new Error('No relevant data found. Please embed some tables first.');
@@ -202,11 +209,18 @@ export function useRag(connectionId: string | null) {
 
       // Use chat model to generate SQL from the question and context
       try {
-        const sql = await invoke<string>('generate_sql_from_question', {
-          question,
+        // The backend classifies the SQL, flagging anything that may write for confirmation
+        const agentState = await invoke<AgentState>('generate_agent_sql', {
+          state: options?.agentState ?? {
+            message: question,
+            intent: 'databaseQuery',
+            shouldSearch: true,
+            requiresConfirmation: false,
+          },
           context: context,
           model: DEFAULT_CHAT_MODEL,
         });
+        const sql = agentState.sql ?? '';
 
         // Clean up the SQL - remove markdown code blocks if present
         let cleanedSql = sql.trim();
@@ -222,7 +236,7 @@ export function useRag(connectionId: string | null) {
           cleanedSql += ';';
         }
 
-        return { sql: cleanedSql, matches };
+        return { sql: cleanedSql, matches, requiresConfirmation: agentState.requiresConfirmation };
       } catch (error) {
         console.error('Failed to generate SQL with chat model:', error);
         // Fallback to simple query if chat generation fails
@@ -234,7 +248,7 @@ export function useRag(connectionId: string | null) {
           throw new Error('Failed to determine a fallback match for SQL generation.');
         }
         const sql = `SELECT * FROM ${quoteIdentifier(firstMatch.schema)}.${quoteIdentifier(firstMatch.table)} LIMIT 100;`;
-        return { sql, matches };
+        return { sql, matches, requiresConfirmation: false };
       }
     },
     [searchEmbeddings, connectionId, toast]
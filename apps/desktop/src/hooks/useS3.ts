@@ -8,6 +8,7 @@ import type {
   S3GetObjectRequest,
   S3GetObjectResponse,
   S3PutObjectRequest,
+  S3PutObjectResponse,
   S3DeleteObjectsRequest,
   S3DeleteResult,
   S3PresignedUrlRequest,
@@ -108,15 +109,15 @@ export function useS3() {
   );
 
   const putS3Object = useCallback(
-    async (connectionId: string, request: S3PutObjectRequest): Promise<string> => {
+    async (connectionId: string, request: S3PutObjectRequest): Promise<S3PutObjectResponse> => {
       setLoading(true);
       setError(null);
       try {
-        const etag = await invoke<string>('put_s3_object', {
+        const response = await invoke<S3PutObjectResponse>('put_s3_object', {
           connectionId,
           request,
         });
-        return etag;
+        return response;
       } catch (err) {
         const message = err instanceof Error ? err.message : String(err);
         setError(message);
@@ -1,5 +1,6 @@
 import { useState, useCallback, useEffect, useMemo } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import type { Schema, Table, Column } from '@/types/connection';
 import type {
   SchemaNode,
@@ -42,6 +43,11 @@ interface UseSchemaReturn {
 // Cache for schema data per connection
 const schemaCache = new Map<string, SchemaNode[]>();
 
+// Forget cached schema trees for every connection, e.g. after reset_all
+export function clearSchemaCache() {
+  schemaCache.clear();
+}
+
 export function useSchema({ connectionId, autoLoad = true }: UseSchemaOptions): UseSchemaReturn {
   const [nodes, setNodes] = useState<SchemaNode[]>([]);
   const [loading, setLoading] = useState(false);
@@ -283,6 +289,19 @@ export function useSchema({ connectionId, autoLoad = true }: UseSchemaOptions):
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [connectionId, autoLoad]); // Remove fetchSchemas to prevent loop
 
+  // Drop cached trees when reset_all is asked to clear the schema cache
+  useEffect(() => {
+    const unlisten = listen('schema-cache-cleared', () => {
+      clearSchemaCache();
+      setNodes([]);
+      setExpandedNodes(new Set());
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Calculate filtered nodes
   const filteredNodes = useMemo(() => {
     return filterNodes(nodes, searchQuery, filterType);
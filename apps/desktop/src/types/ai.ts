@@ -2,27 +2,49 @@
 
 export interface EmbeddingJobRequest {
   connectionId: string;
-  schema: string;
+  schema?: string;
   table: string;
   columns: string[];
   model: string;
   limit?: number;
+  jobId?: string; // lets the caller cancel the job while it runs
 }
 
 export interface EmbeddingJobResult {
+  jobId: string;
   embeddedRows: number;
   skippedRows: number;
 }
 
+export interface EmbeddingImportResult {
+  importedRows: number;
+  skippedRows: number; // already stored with the same chunk hash
+}
+
+export interface EmbeddingTableRef {
+  schema: string;
+  table: string;
+}
+
 export interface EmbeddingSearchRequest {
   connectionId: string;
   schema?: string;
   table?: string;
+  tables?: EmbeddingTableRef[]; // search only these tables
   query: string;
   model: string;
   topK: number;
 }
 
+export interface SimilarRowsRequest {
+  connectionId: string;
+  schema?: string;
+  table: string;
+  key: { values: Record<string, any> };
+  model: string;
+  topK: number;
+}
+
 export interface EmbeddingSearchMatch {
   rowReference: string;
   schema: string;
@@ -72,9 +94,17 @@ export interface ChatMessage {
   timestamp: number;
   matches?: EmbeddingSearchMatch[];
   sqlQuery?: string;
+  sqlRequiresConfirmation?: boolean; // the backend found `sqlQuery` may write
 }
 
-export type AgentIntent = 'greeting' | 'smallTalk' | 'databaseQuery' | 'unknown';
+export type AgentIntent =
+  | 'greeting'
+  | 'smallTalk'
+  | 'databaseQuery'
+  | 'schemaQuestion'
+  | 'dataModification'
+  | 'visualization'
+  | 'unknown';
 
 export interface AgentState {
   message: string;
@@ -83,22 +113,78 @@ export interface AgentState {
   response?: string;
   sql?: string;
   shouldSearch: boolean;
+  /** `sql` may write, so it must never run without the user's approval */
+  requiresConfirmation: boolean;
 }
 
 export interface GenerateTestDataRequest {
   connectionId: string;
-  schema: string;
+  schema?: string;
   table: string;
   rowCount: number;
   instructions?: string;
   userTemplate?: Record<string, any> | any[];
+  jobId?: string; // lets the caller cancel the job while it runs
+  attemptsPerRow?: number; // model calls allowed per requested row, default 3
+  minAttempts?: number;    // floor on the attempt budget, default 3
+  // e.g. fr-FR; how well the data is localized depends on the model's multilingual ability
+  locale?: string;
 }
 
 export interface GeneratedTestRow {
   values: Record<string, any>;
+  synthetic: boolean; // filled in without the model after it ran out of attempts
 }
 
 export interface GenerateTestDataResponse {
+  jobId: string;
   rows: GeneratedTestRow[];
   model: string;
+  requestedRows: number;
+  attempts: number;
+  maxAttempts: number;
+  failedAttempts: FailedTestRowAttempt[];
+  templateWarnings: string[]; // template keys that don't match a generated column
+  inferredSemantics: InferredColumnSemantic[];
+}
+
+export interface TestDataPromptPreview {
+  prompt: string;
+  rowSchema: Record<string, any>;
+  templateWarnings: string[];
+  inferredSemantics: InferredColumnSemantic[];
+}
+
+export type ColumnSemantic = 'email' | 'phone' | 'url' | 'uuid' | 'json';
+
+export interface InferredColumnSemantic {
+  column: string;
+  semantic: ColumnSemantic;
+  matched: number; // sampled values matching the pattern
+  sampled: number;
+}
+
+export interface GenerateAndInsertTestDataResponse {
+  generation: GenerateTestDataResponse;
+  insertedRows: number;
+}
+
+export interface FailedTestRowAttempt {
+  attempt: number;
+  rowIndex: number; // 1-based index of the row being generated
+  reason: string;
+}
+
+export interface AgentChatMessage {
+  role: 'system' | 'user' | 'assistant';
+  content: string;
+}
+
+export interface AiJobInfo {
+  jobId: string;
+  kind: 'embed_table' | 'generate_test_data';
+  description: string;
+  completed: number;
+  total: number;
+  startedAt: string; // RFC 3339
 }
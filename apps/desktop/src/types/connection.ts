@@ -1,3 +1,5 @@
+import type { Compression } from './s3';
+
 // TypeScript types matching Rust backend types (from types.rs)
 
 export interface SshConfig {
@@ -33,8 +35,22 @@ export interface ConnectionProfile {
   lockTimeout?: number;       // milliseconds
   idleTimeout?: number;       // seconds
   readOnly: boolean;
+  searchPath?: string[];
+  sessionTimezone?: string;   // e.g. 'America/New_York'; UTC when unset
+  recyclingMethod?: PoolRecyclingMethod; // 'fast' when unset
+  channelBinding?: ChannelBindingMode;   // 'prefer' when unset; 'require' needs TLS
+  sslNegotiation?: SslNegotiationMode;   // 'postgres' when unset; 'direct' needs TLS and PostgreSQL 17+
+  slowQueryThreshold?: number;           // milliseconds; slower queries log at warn, 500 when unset
 }
 
+export type ChannelBindingMode = 'disable' | 'prefer' | 'require';
+
+export type SslNegotiationMode = 'postgres' | 'direct';
+
+// How pooled connections are checked before reuse: 'verified' runs an empty query first,
+// 'clean' also resets session state
+export type PoolRecyclingMethod = 'fast' | 'verified' | 'clean';
+
 export interface FieldInfo {
   name: string;
   typeOid: number;
@@ -48,6 +64,7 @@ export interface QueryResult {
   rowCount: number;
   executionTime: number; // milliseconds
   hasMore: boolean;
+  backendPid?: number; // backend that ran the query, for cancel_query
 }
 
 export interface ConnectionInfo {
@@ -62,6 +79,42 @@ export interface ConnectionInfo {
   currentSchema: string;
 }
 
+// Outcome of connecting under one sslmode, from diagnose_tls
+export interface TlsModeDiagnosis {
+  sslMode: 'disable' | 'prefer' | 'require' | 'verify-ca' | 'verify-full';
+  ok: boolean;
+  encrypted?: boolean; // whether the connection used TLS; absent when it failed
+  error?: string;
+}
+
+export type ConnectionFailureCategory =
+  | 'dns'
+  | 'connectionRefused'
+  | 'timeout'
+  | 'tls'
+  | 'authentication'
+  | 'databaseNotFound'
+  | 'tooManyConnections'
+  | 'server'
+  | 'unknown';
+
+// Outcome of diagnose_connection, with the cause and a suggested fix when it failed
+export interface ConnectionDiagnosis {
+  ok: boolean;
+  category?: ConnectionFailureCategory; // absent when the connection worked
+  sqlState?: string;                    // e.g. '28P01', when the server reported the error
+  error?: string;
+  hint?: string;
+}
+
+// What reset_all tore down
+export interface ResetSummary {
+  databaseConnections: number;
+  s3Connections: number;
+  ollamaStopped: boolean; // false when no supervised instance was running
+  schemaCacheCleared: boolean;
+}
+
 export interface Schema {
   name: string;
   owner: string;
@@ -84,6 +137,10 @@ export interface Column {
   dataType: string;
   isNullable: boolean;
   columnDefault?: string;
+  isIdentity: boolean;
+  identityGeneration?: 'ALWAYS' | 'BY DEFAULT';
+  isGenerated: boolean; // GENERATED ALWAYS AS (expr) STORED
+  generationExpression?: string;
   characterMaximumLength?: number;
   numericPrecision?: number;
   numericScale?: number;
@@ -96,6 +153,27 @@ export interface Column {
   description?: string;
 }
 
+// A column a table is expected to have, for assert_table_schema
+export interface ExpectedColumn {
+  name: string;
+  dataType: string;     // any common spelling, e.g. 'int4', 'varchar(255)', 'text[]'
+  isNullable?: boolean; // not checked when omitted
+}
+
+export interface ColumnMismatch {
+  column: string;
+  field: 'dataType' | 'isNullable';
+  expected: string;
+  actual: string;
+}
+
+export interface TableSchemaAssertion {
+  matches: boolean;
+  missingColumns: string[];
+  extraColumns: string[];
+  mismatches: ColumnMismatch[];
+}
+
 export interface ForeignKeySearchRequest {
   schema: string;
   table: string;
@@ -109,6 +187,20 @@ export interface ForeignKeySearchResult {
   row: Record<string, unknown>;
 }
 
+export interface FilterOptionsRequest {
+  schema: string;
+  table: string;
+  column: string;
+  limit?: number;
+}
+
+export interface FilterOptions {
+  dataType: string;
+  values: string[];
+  truncated: boolean;
+  enumLabels?: string[];
+}
+
 export interface ForeignKey {
   name: string;
   columns: string[];
@@ -119,6 +211,21 @@ export interface ForeignKey {
   onUpdate: string;
 }
 
+// Request for find_orphaned_rows
+export interface OrphanedRowsRequest {
+  schema?: string;
+  table: string;
+  constraintName: string;
+  sampleLimit?: number;
+}
+
+// Child rows whose foreign key references no existing parent row
+export interface OrphanedRows {
+  foreignKey: ForeignKey;
+  orphanCount: number;
+  sampleRows: Record<string, any>[];
+}
+
 export interface TableColumnDefinition {
   name: string;
   dataType: string;
@@ -141,6 +248,23 @@ export interface CreateTableRequest {
   tableName: string;
   columns: TableColumnDefinition[];
   ifNotExists: boolean;
+  tableConstraints?: TableConstraintDefinition[];
+}
+
+export interface TableConstraintDefinition {
+  name?: string;
+  constraintType: 'UNIQUE' | 'CHECK' | 'FOREIGN KEY';
+  columns: string[];
+  checkExpression?: string;
+  references?: TableConstraintReference;
+}
+
+export interface TableConstraintReference {
+  schema?: string;
+  table: string;
+  columns: string[];
+  onDelete?: string;
+  onUpdate?: string;
 }
 
 export interface DropTableRequest {
@@ -165,6 +289,27 @@ export interface DropTableColumnRequest {
   ifExists: boolean;
 }
 
+// Apply several column changes at once; with dryRun only the SQL is returned
+export interface AlterTableRequest {
+  schema: string;
+  tableName: string;
+  operations: AlterTableOperation[]; // applied in order
+  dryRun?: boolean;
+}
+
+export type AlterTableOperation =
+  | { action: 'addColumn'; change: TableColumnDefinition }
+  | { action: 'dropColumn'; change: { columnName: string; cascade?: boolean } }
+  | { action: 'renameColumn'; change: { columnName: string; newName: string } }
+  | {
+      action: 'alterColumnType';
+      change: { columnName: string; dataType: string; using?: string };
+    }
+  | { action: 'setDefault'; change: { columnName: string; defaultExpression: string } }
+  | { action: 'dropDefault'; change: string } // column name
+  | { action: 'setNotNull'; change: string }
+  | { action: 'dropNotNull'; change: string };
+
 export interface TableRowData {
   values: Record<string, any>;
 }
@@ -186,20 +331,161 @@ export interface RenameSchemaRequest {
 }
 
 export interface InsertRowRequest {
-  schema: string;
+  schema?: string;
   tableName: string;
   row: TableRowData;
 }
 
 export interface DeleteRowRequest {
-  schema: string;
+  schema?: string;
   tableName: string;
   criteria: TableRowData;
   limit?: number;
 }
 
+export interface BatchDeleteRequest {
+  schema?: string;
+  tableName: string;
+  criteria: TableRowData;
+  batchSize?: number; // rows per batch; defaults to 10000
+  pauseMs?: number; // pause between batches; defaults to 100
+}
+
+export interface BatchDeleteResult {
+  deleted: number;
+  batches: number;
+  executionTime: number; // milliseconds
+}
+
+export interface SchemaDdlExportRequest {
+  schema: string;
+  path: string;
+  includeViews?: boolean;
+  includeFunctions?: boolean;
+}
+
+export interface SchemaExportResult {
+  path: string;
+  statementCount: number;
+}
+
+export interface TableTransferRequest {
+  sourceConnectionId: string;
+  destinationConnectionId: string;
+  schema: string;
+  table: string;
+  createTable?: boolean;
+}
+
+export interface TableTransferResult {
+  rowsTransferred: number;
+  tableCreated: boolean;
+  executionTime: number; // milliseconds
+}
+
+export type ExportFileFormat = 'csv' | 'ndjson';
+
+export interface QueryS3ExportRequest {
+  connectionId: string;
+  sql: string;
+  s3ConnectionId: string;
+  key: string; // relative to the S3 connection's path prefix
+  format: ExportFileFormat;
+  header?: boolean; // CSV header row; defaults to true
+  ifMatch?: string;     // only overwrite when the current ETag matches
+  ifNoneMatch?: string; // "*" to only create when the key does not exist
+  serverSideEncryption?: string; // "AES256" or "aws:kms"
+  sseKmsKeyId?: string;
+  compress?: Compression; // compress rows as they stream, setting Content-Encoding
+}
+
+export interface QueryS3ExportResult {
+  key: string; // full key, including the path prefix
+  etag?: string;
+  rowsExported: number;
+  bytes: number;
+  partCount: number;
+  executionTime: number; // milliseconds
+}
+
+export interface S3TableImportRequest {
+  connectionId: string;
+  s3ConnectionId: string;
+  key: string; // relative to the S3 connection's path prefix
+  schema: string;
+  table: string;
+  columns?: string[]; // table columns in CSV field order; all columns when unset
+  header?: boolean; // skip a header row; defaults to true
+  delimiter?: string; // single character; defaults to a comma
+}
+
+export interface S3TableImportResult {
+  rowsImported: number;
+  bytes: number; // decompressed size of the loaded data
+  executionTime: number; // milliseconds
+}
+
+export interface TableChecksum {
+  schema: string;
+  table: string;
+  rowCount: number;
+  checksum: string; // equal checksums mean equal rows
+  executionTime: number; // milliseconds
+}
+
+// A composite type created with CREATE TYPE ... AS (...)
+export interface CompositeType {
+  oid: number;
+  schema: string;
+  name: string;
+  attributes: { name: string; dataType: string }[]; // in declaration order
+  description?: string;
+}
+
+// A domain: a base type narrowed by NOT NULL, a default and CHECK constraints
+export interface Domain {
+  oid: number;
+  schema: string;
+  name: string;
+  baseType: string; // with modifiers, e.g. 'numeric(10,2)'
+  notNull: boolean;
+  default?: string;
+  constraints: { name: string; definition: string }[];
+  description?: string;
+}
+
+// Kind of schema object get_object_definition looks up
+export type ObjectKind = 'constraint' | 'index' | 'view' | 'sequence';
+
 // Connection status for UI state management
-export type ConnectionStatus = 'disconnected' | 'connecting' | 'connected' | 'error';
+export type ConnectionStatus =
+  | 'disconnected'
+  | 'connecting'
+  | 'connected'
+  | 'reconnecting'
+  | 'error';
+
+// Payload of the connection-status event and the result of get_connection_status
+export interface ConnectionStatusUpdate {
+  connectionId: string;
+  status: ConnectionStatus;
+  error?: string;
+  updatedAt: string; // RFC 3339
+}
+
+// Payload of the pg-notification event
+export interface PgNotification {
+  listenerId: string;
+  channel: string;
+  payload: string;
+  processId: number; // backend that sent the notification
+}
+
+// Result of listen_channel and unlisten_channel
+export interface NotificationSubscription {
+  listenerId: string;
+  channels: string[]; // empty once the listener has been closed
+}
 
 export interface ConnectionState {
   profile: ConnectionProfile;
@@ -11,6 +11,48 @@ export interface QueryResult {
   rowCount: number;
   executionTime: number; // milliseconds
   hasMore: boolean;
+  backendPid?: number; // backend that ran the query, for cancel_query
+}
+
+// Payload of the copy-progress event emitted while COPY moves data
+export interface CopyProgress {
+  operation: string; // command running the COPY, e.g. 'transfer_table'
+  target: string;    // table or COPY statement being run
+  bytes: number;
+  rows: number;
+  totalBytes?: number;
+  totalRows?: number; // often a planner estimate
+  bytesPerSecond: number;
+  etaSeconds?: number; // absent without a known total
+  done: boolean;
+}
+
+// Text format produced by format_query_result
+export type ResultFormat = 'markdown' | 'tsv' | 'json';
+
+// Latency and error rate of recent execute_query calls, from get_query_metrics
+export interface QueryMetrics {
+  connectionId: string;
+  count: number;
+  errorCount: number;
+  errorRate: number; // 0 to 1
+  p50?: number; // milliseconds, absent without successful queries
+  p95?: number; // milliseconds
+  p99?: number; // milliseconds
+}
+
+// Events delivered over the channel passed to stream_query; ack each `rows` event with ack_query_stream
+export type QueryStreamEvent =
+  | { event: 'started'; data: { streamId: string; fields: FieldInfo[] } }
+  | { event: 'rows'; data: { batchIndex: number; rows: any[] } }
+  | { event: 'finished'; data: { rowCount: number; executionTime: number } };
+
+export interface SqlImportResult {
+  statementsExecuted: number;
+  copyBlocks: number;
+  rowsCopied: number;
+  skippedMetaCommands: string[];
+  executionTime: number; // milliseconds
 }
 
 export interface QueryExecutionState {
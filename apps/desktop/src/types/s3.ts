@@ -11,6 +11,8 @@ export interface S3ConnectionProfile {
   sessionToken?: string; // For temporary credentials
   pathPrefix?: string; // Optional path prefix
   forcePathStyle: boolean; // For S3-compatible services
+  maxRetries?: number; // Retries of throttled or failed requests; defaults to 3
+  retryBaseDelayMs?: number; // First retry delay, doubling each time; defaults to 200
 }
 
 export interface S3Object {
@@ -23,6 +25,28 @@ export interface S3Object {
   isDirectory: boolean;
 }
 
+export interface S3PrefixStats {
+  prefix: string; // full prefix, including the connection's path prefix
+  objectCount: number;
+  totalSize: number; // bytes
+  byStorageClass: Record<string, S3StorageClassStats>;
+}
+
+export interface S3StorageClassStats {
+  objectCount: number;
+  totalSize: number; // bytes
+}
+
+export interface S3ObjectVersion {
+  key: string;
+  versionId?: string; // "null" for objects written while versioning was off
+  isLatest: boolean;
+  isDeleteMarker: boolean;
+  size: number;
+  lastModified: string;
+  etag?: string;
+}
+
 export interface S3ListResult {
   objects: S3Object[];
   commonPrefixes: string[]; // Directories
@@ -35,24 +59,62 @@ export interface S3ListRequest {
   delimiter?: string;
   maxKeys?: number;
   continuationToken?: string;
+  modifiedAfter?: string; // RFC3339; only later-modified objects are listed, reading on until the page fills
 }
 
 export interface S3GetObjectRequest {
   key: string;
+  range?: [number, number]; // inclusive byte range
+  keepCompressed?: boolean; // return a gzip or zstd encoded body as stored
+  versionId?: string; // latest version when unset
 }
 
 export interface S3GetObjectResponse {
   content: number[]; // byte array
   contentType?: string;
+  contentEncoding?: string; // encoding still applied to content, if any
+  contentLength: number;
+  totalSize: number;
+  lastModified?: string;
+  etag?: string;
+  metadata: Record<string, string>;
+}
+
+export interface S3ObjectHead {
+  key: string;
+  contentType?: string;
   contentLength: number;
   lastModified?: string;
   etag?: string;
+  storageClass?: string;
+  metadata: Record<string, string>;
 }
 
 export interface S3PutObjectRequest {
   key: string;
   content: number[]; // byte array
-  contentType?: string;
+  contentType?: string; // guessed from the key's extension when unset
+  ifMatch?: string;     // only overwrite when the current ETag matches
+  ifNoneMatch?: string; // "*" to only create when the key does not exist
+  metadata?: Record<string, string>; // sent as x-amz-meta-* headers
+  tags?: Record<string, string>;
+  serverSideEncryption?: string; // "AES256" or "aws:kms"
+  sseKmsKeyId?: string;
+  storageClass?: string; // e.g. STANDARD, STANDARD_IA, GLACIER
+  compress?: Compression; // compress content before upload, setting Content-Encoding
+}
+
+export type Compression = 'gzip' | 'zstd';
+
+export interface S3PutObjectResponse {
+  etag: string;
+  serverSideEncryption?: string;
+  sseKmsKeyId?: string;
+}
+
+export interface S3ObjectTagsRequest {
+  key: string;
+  tags: Record<string, string>;
 }
 
 export interface S3DeleteObjectsRequest {
@@ -73,6 +135,7 @@ export interface S3DeleteError {
 export interface S3PresignedUrlRequest {
   key: string;
   expiresIn: number; // seconds
+  versionId?: string; // latest version when unset
 }
 
 export interface S3PresignedUrlResponse {
@@ -122,6 +122,25 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+-- A same-named table in a second schema, so table listings can be checked for
+-- cross-schema mixups (owner, size and row count must come from the right table)
+CREATE SCHEMA IF NOT EXISTS test_archive;
+
+CREATE TABLE IF NOT EXISTS test_archive.users (
+    id SERIAL PRIMARY KEY,
+    username VARCHAR(50) NOT NULL,
+    archived_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    payload TEXT
+);
+
+INSERT INTO test_archive.users (username, payload)
+SELECT 'archived_' || n, repeat('x', 500)
+FROM generate_series(1, 1000) AS n
+WHERE NOT EXISTS (SELECT 1 FROM test_archive.users);
+
+ANALYZE test_schema.users;
+ANALYZE test_archive.users;
+
 -- Grant permissions
 GRANT USAGE ON SCHEMA test_schema TO rowflow_test;
 GRANT ALL PRIVILEGES ON ALL TABLES IN SCHEMA test_schema TO rowflow_test;
@@ -133,3 +152,6 @@ ALTER DEFAULT PRIVILEGES IN SCHEMA test_schema GRANT ALL ON TABLES TO rowflow_te
 ALTER DEFAULT PRIVILEGES IN SCHEMA test_schema GRANT ALL ON SEQUENCES TO rowflow_test;
 ALTER DEFAULT PRIVILEGES IN SCHEMA test_schema GRANT EXECUTE ON FUNCTIONS TO rowflow_test;
 
+GRANT USAGE ON SCHEMA test_archive TO rowflow_test;
+GRANT ALL PRIVILEGES ON ALL TABLES IN SCHEMA test_archive TO rowflow_test;
+GRANT ALL PRIVILEGES ON ALL SEQUENCES IN SCHEMA test_archive TO rowflow_test;